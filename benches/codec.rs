@@ -0,0 +1,125 @@
+//! Throughput of the encoding schemes and entry points.
+//!
+//! Run with `cargo bench`; pass a filter to run one group, e.g.
+//! `cargo bench -- streaming`. Payloads are pseudo-random so no scheme benefits
+//! from repeated bytes.
+
+use std::hint::black_box;
+use std::io::Write;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use qr_base44::{
+    DecoderWriter, EncoderBuilder, EncoderWriter, Mode, decode, decode_auto, encode, encode_auto,
+    encode_parallel,
+};
+
+/// From a short QR payload up to a file-sized buffer.
+const SIZES: [usize; 4] = [16, 256, 4096, 65536];
+
+fn payload(len: usize) -> Vec<u8> {
+    let mut state = 0x2545_F491_4F6C_DD1Du64;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect()
+}
+
+fn byte_pair(c: &mut Criterion) {
+    let mut group = c.benchmark_group("byte_pair");
+    for len in SIZES {
+        let data = payload(len);
+        let text = encode(&data);
+        group.throughput(Throughput::Bytes(len as u64));
+        group.bench_with_input(BenchmarkId::new("encode", len), &data, |b, data| {
+            b.iter(|| encode(black_box(data)))
+        });
+        group.bench_with_input(BenchmarkId::new("decode", len), &text, |b, text| {
+            b.iter(|| decode(black_box(text)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+/// Byte-pair against the denser schemes at QR-sized payloads; whole-message
+/// optimal encoding is quadratic, so larger inputs are not worth timing.
+fn modes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("modes");
+    let optimal = EncoderBuilder::new().mode(Mode::Optimal).build();
+    for len in [16, 64, 256] {
+        let data = payload(len);
+        group.throughput(Throughput::Bytes(len as u64));
+        group.bench_with_input(BenchmarkId::new("byte_pair", len), &data, |b, data| {
+            b.iter(|| encode(black_box(data)))
+        });
+        group.bench_with_input(BenchmarkId::new("auto", len), &data, |b, data| {
+            b.iter(|| encode_auto(black_box(data)))
+        });
+        group.bench_with_input(BenchmarkId::new("optimal", len), &data, |b, data| {
+            b.iter(|| optimal.encode(black_box(data)))
+        });
+        let auto = encode_auto(&data);
+        group.bench_with_input(BenchmarkId::new("decode_auto", len), &auto, |b, text| {
+            b.iter(|| decode_auto(black_box(text)).unwrap())
+        });
+        let text = optimal.encode(&data);
+        group.bench_with_input(BenchmarkId::new("decode_optimal", len), &text, |b, text| {
+            b.iter(|| optimal.decode(black_box(text)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+/// One-shot calls against the `io::Write` adapters fed in 1 KiB writes.
+fn streaming(c: &mut Criterion) {
+    let mut group = c.benchmark_group("streaming");
+    let len = 65536;
+    let data = payload(len);
+    let text = encode(&data);
+    group.throughput(Throughput::Bytes(len as u64));
+    group.bench_function("encode_one_shot", |b| b.iter(|| encode(black_box(&data))));
+    group.bench_function("encode_writer", |b| {
+        b.iter(|| {
+            let mut writer = EncoderWriter::new(Vec::with_capacity(text.len()));
+            for chunk in black_box(&data).chunks(1024) {
+                writer.write_all(chunk).unwrap();
+            }
+            writer.finish().unwrap()
+        })
+    });
+    group.bench_function("decode_one_shot", |b| {
+        b.iter(|| decode(black_box(&text)).unwrap())
+    });
+    group.bench_function("decode_writer", |b| {
+        b.iter(|| {
+            let mut writer = DecoderWriter::new(Vec::with_capacity(len));
+            for chunk in black_box(&text).as_bytes().chunks(1024) {
+                writer.write_all(chunk).unwrap();
+            }
+            writer.finish().unwrap()
+        })
+    });
+    group.finish();
+}
+
+fn parallel(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parallel");
+    let len = 4 << 20;
+    let data = payload(len);
+    group.throughput(Throughput::Bytes(len as u64));
+    group.sample_size(20);
+    for threads in [1, 2, 4, 8] {
+        group.bench_with_input(
+            BenchmarkId::new("encode", threads),
+            &threads,
+            |b, &threads| b.iter(|| encode_parallel(black_box(&data), threads)),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, byte_pair, modes, streaming, parallel);
+criterion_main!(benches);
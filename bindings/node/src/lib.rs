@@ -0,0 +1,54 @@
+//! Node.js bindings for `qr-base44` via napi-rs.
+//!
+//! Build the addon with `napi build --release` in this directory; napi-rs emits
+//! an `index.d.ts` alongside the `.node` binary:
+//!
+//! ```ts
+//! export function encode(input: Buffer): string
+//! export function decode(s: string): Buffer
+//! export function encodeBits(bits: number, input: Buffer): string
+//! export function decodeBits(bits: number, s: string): Buffer
+//! ```
+//!
+//! These call straight into the Rust implementation, so Node services get the
+//! exact same edge-case behaviour (tail groups, overflow checks) as the backend.
+
+use napi::bindgen_prelude::Buffer;
+use napi::{Error, Result, Status};
+use napi_derive::napi;
+use qr_base44::Base44Error;
+
+fn to_napi_error(e: Base44Error) -> Error {
+    Error::new(Status::InvalidArg, e.to_string())
+}
+
+/// Encode bytes with the byte-pair scheme (see [`qr_base44::encode`]).
+#[napi]
+pub fn encode(input: Buffer) -> String {
+    qr_base44::encode(&input)
+}
+
+/// Decode a byte-pair encoded string (see [`qr_base44::decode`]).
+#[napi]
+pub fn decode(s: String) -> Result<Buffer> {
+    qr_base44::decode(&s)
+        .map(Buffer::from)
+        .map_err(to_napi_error)
+}
+
+/// Encode exactly `bits` bits with optimal length (see [`qr_base44::try_encode_bits`]).
+#[napi]
+pub fn encode_bits(bits: u32, input: Buffer) -> Result<String> {
+    qr_base44::try_encode_bits(bits as usize, &input).map_err(to_napi_error)
+}
+
+/// Decode an optimal-length string holding `bits` bits (see [`qr_base44::try_decode_bits`]).
+///
+/// Strings longer than [`qr_base44::chars_needed`]`(bits)` are rejected before
+/// decoding.
+#[napi]
+pub fn decode_bits(bits: u32, s: String) -> Result<Buffer> {
+    qr_base44::try_decode_bits(bits as usize, &s)
+        .map(Buffer::from)
+        .map_err(to_napi_error)
+}
@@ -0,0 +1,13 @@
+//! UniFFI library for `qr-base44`.
+//!
+//! The interface is defined by the `uniffi` feature of `qr-base44` (see
+//! `qr_base44::ffi`); this crate only links it into a `cdylib`, so the main
+//! crate stays an `rlib` that any target can link. Generate bindings from the
+//! built library:
+//!
+//! ```text
+//! cargo build --release -p qr-base44-uniffi
+//! uniffi-bindgen generate --library target/release/libqr_base44_uniffi.so --language kotlin --out-dir out
+//! ```
+
+pub use qr_base44::ffi::*;
@@ -0,0 +1,8 @@
+//! WebAssembly package for `qr-base44`.
+//!
+//! The exports are defined by the `wasm` feature of `qr-base44` (see
+//! `qr_base44::wasm`); this crate only links them into a `cdylib`, so the main
+//! crate stays an `rlib` that any target can link. Build with
+//! `wasm-pack build bindings/wasm`.
+
+pub use qr_base44::wasm::*;
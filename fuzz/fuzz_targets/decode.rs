@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Raw input: decode must never panic, and anything it accepts is canonical.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(bytes) = qr_base44::decode_bytes(data) {
+        assert_eq!(qr_base44::encode(&bytes).as_bytes(), data);
+    }
+});
@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &str| {
+    if let Ok(bytes) = qr_base44::decode_103bits(data) {
+        assert_eq!(bytes[0] & 0x80, 0, "value exceeds 103 bits");
+        assert_eq!(qr_base44::encode_103bits(&bytes), data);
+    }
+});
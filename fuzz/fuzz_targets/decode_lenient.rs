@@ -0,0 +1,19 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use qr_base44::DecoderBuilder;
+
+// Lenient decoding must agree with strict decoding of the same text after
+// separators and ASCII whitespace are removed.
+fuzz_target!(|data: &str| {
+    let lenient = DecoderBuilder::new().separator('_').strict(false).build();
+    let stripped: String = data
+        .chars()
+        .filter(|&c| c != '_' && !c.is_ascii_whitespace())
+        .collect();
+    match (lenient.decode(data), qr_base44::decode(&stripped)) {
+        (Ok(a), Ok(b)) => assert_eq!(a, b),
+        (Err(_), Err(_)) => {}
+        (a, b) => panic!("lenient {a:?} vs strict {b:?}"),
+    }
+});
@@ -0,0 +1,18 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use qr_base44::Base44Input;
+
+// Valid encodings with one corruption each, to reach the tail-group and
+// overflow paths that raw bytes rarely get past the alphabet check to.
+fuzz_target!(|input: Base44Input| {
+    let result = qr_base44::decode(input.as_str());
+    match &input {
+        Base44Input::Valid(s) => assert_eq!(result.unwrap(), s.decode()),
+        _ => {
+            if let Ok(bytes) = result {
+                assert_eq!(qr_base44::encode(&bytes), input.as_str());
+            }
+        }
+    }
+});
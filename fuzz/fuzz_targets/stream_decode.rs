@@ -0,0 +1,31 @@
+#![no_main]
+
+use std::io::Write;
+
+use libfuzzer_sys::fuzz_target;
+use qr_base44::DecoderWriter;
+
+// The streaming decoder must agree with one-shot decoding wherever the input is
+// split, including inside a group.
+fuzz_target!(|input: (Vec<u8>, Vec<u8>)| {
+    let (text, splits) = input;
+    let mut dec = DecoderWriter::new(Vec::new());
+    let mut rest = &text[..];
+    let mut streamed = Ok(());
+    for &split in &splits {
+        let at = (split as usize).min(rest.len());
+        streamed = dec.write_all(&rest[..at]);
+        rest = &rest[at..];
+        if streamed.is_err() {
+            break;
+        }
+    }
+    let streamed = streamed
+        .and_then(|()| dec.write_all(rest))
+        .and_then(|()| dec.finish());
+    match (streamed, qr_base44::decode_bytes(&text)) {
+        (Ok(a), Ok(b)) => assert_eq!(a, b),
+        (Err(_), Err(_)) => {}
+        (a, b) => panic!("streamed {a:?} vs one-shot {b:?}"),
+    }
+});
@@ -0,0 +1,130 @@
+//! Lookups into the Base44 alphabet, for tools that validate or transcribe
+//! codes (keyboards, OCR post-processors) without copying the table.
+//!
+//! ```
+//! use qr_base44::alphabet;
+//!
+//! assert_eq!(alphabet::index_of('Z'), Some(35));
+//! assert_eq!(alphabet::char_at(35), 'Z');
+//! assert_eq!(alphabet::index_of(' '), None);
+//! assert_eq!(alphabet::iter().count(), alphabet::LEN);
+//! assert_eq!(alphabet::DECODE_TABLE[b'Z' as usize], 35);
+//! ```
+
+use crate::BASE44_ALPHABET;
+
+/// Number of characters in the alphabet.
+pub const LEN: usize = BASE44_ALPHABET.len();
+
+/// Marks bytes outside the alphabet in [`DECODE_TABLE`].
+pub const INVALID: u8 = 0xFF;
+
+/// Digit value of every byte, or [`INVALID`]: the table the decoders use.
+///
+/// Indexed by raw input byte, so it can be copied verbatim into C headers or
+/// firmware that decodes without this crate.
+pub const DECODE_TABLE: [u8; 256] = {
+    let mut table = [INVALID; 256];
+    let mut i = 0;
+    while i < LEN {
+        table[BASE44_ALPHABET[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+};
+
+/// Digit value of `ch`, or `None` if it is not an alphabet character.
+///
+/// Matching is exact: lower-case letters are not in the alphabet.
+pub const fn index_of(ch: char) -> Option<u8> {
+    if !ch.is_ascii() {
+        return None;
+    }
+    match DECODE_TABLE[ch as usize] {
+        INVALID => None,
+        val => Some(val),
+    }
+}
+
+/// Whether `ch` is an alphabet character.
+pub const fn contains(ch: char) -> bool {
+    index_of(ch).is_some()
+}
+
+/// Character for digit value `index`.
+///
+/// # Panics
+///
+/// Panics if `index >= 44`. Use [`try_char_at`] to get `None` instead.
+pub const fn char_at(index: u8) -> char {
+    BASE44_ALPHABET[index as usize] as char
+}
+
+/// [`char_at`], or `None` if `index >= 44`.
+pub const fn try_char_at(index: u8) -> Option<char> {
+    if index as usize >= LEN {
+        return None;
+    }
+    Some(char_at(index))
+}
+
+/// The alphabet characters in digit order, `0` through `:`.
+pub fn iter() -> impl DoubleEndedIterator<Item = char> + ExactSizeIterator + Clone {
+    BASE44_ALPHABET.iter().map(|&b| b as char)
+}
+
+/// Offset of the first byte of `bytes` outside the alphabet.
+///
+/// Each 64-byte block is checked with a branch-free fold, and only a failing
+/// block is scanned byte by byte, so valid input costs one table lookup per
+/// byte and no early-exit branches.
+pub(crate) fn first_invalid(bytes: &[u8]) -> Option<usize> {
+    const BLOCK: usize = 64;
+    let invalid = |b: &u8| DECODE_TABLE[*b as usize] == INVALID;
+    bytes.chunks(BLOCK).enumerate().find_map(|(n, block)| {
+        let bad = block.iter().fold(false, |acc, b| acc | invalid(b));
+        bad.then(|| {
+            n * BLOCK
+                + block
+                    .iter()
+                    .position(invalid)
+                    .expect("block has a bad byte")
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookups_agree_with_the_table() {
+        for (i, ch) in iter().enumerate() {
+            assert_eq!(index_of(ch), Some(i as u8));
+            assert_eq!(char_at(i as u8), ch);
+        }
+        let members = (0..=0x10FFFF)
+            .filter_map(char::from_u32)
+            .filter(|&c| contains(c))
+            .count();
+        assert_eq!(members, LEN);
+        assert_eq!(try_char_at(43), Some(':'));
+        assert_eq!(try_char_at(44), None);
+        assert_eq!(index_of('a'), None);
+        let valid = DECODE_TABLE.iter().filter(|&&v| v != INVALID).count();
+        assert_eq!(valid, LEN);
+    }
+
+    #[test]
+    fn first_invalid_across_blocks() {
+        let mut bytes = [b'0'; 200];
+        assert_eq!(first_invalid(&bytes), None);
+        assert_eq!(first_invalid(b""), None);
+        bytes[199] = 0xFF;
+        for at in [0, 63, 64, 130, 199] {
+            bytes[at] = b'a';
+            assert_eq!(first_invalid(&bytes), Some(at));
+            bytes[at] = b'0';
+        }
+    }
+}
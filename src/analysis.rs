@@ -0,0 +1,417 @@
+//! Density comparison against other text encodings.
+//!
+//! [`compare`] computes, for a payload of `byte_len` bytes, the encoded length in
+//! each [`Encoding`] and the smallest QR Code version that holds it at every
+//! error-correction level. This is the data behind the README's comparison table,
+//! available for your own payload sizes:
+//!
+//! ```
+//! use qr_base44::analysis::{EcLevel, Encoding, compare};
+//!
+//! let c = compare(32); // e.g. a SHA-256 hash
+//! assert_eq!(c.get(Encoding::Base44).chars, 48);
+//! assert_eq!(c.get(Encoding::Base44Optimal).chars, 47);
+//! assert_eq!(c.get(Encoding::Base64).chars, 43);
+//! // Base64 needs QR byte mode, so it takes a larger symbol despite being shorter.
+//! assert_eq!(c.qr_version_delta(Encoding::Base64, EcLevel::M), Some(1));
+//! ```
+//!
+//! QR sizes assume a single segment (mode indicator, character count and data,
+//! no terminator). Base64 and hex are the unpadded forms; Base32 is RFC 4648
+//! upper-case without padding, so it fits alphanumeric mode.
+//!
+//! For a concrete encoded string, [`split_segments`] goes further: runs of
+//! digits are denser in QR numeric mode (10 bits per 3 digits instead of 16.5),
+//! so it picks the cheapest mix of numeric and alphanumeric segments, and
+//! [`qr_version_segmented`] sizes the symbol for it.
+
+use core::ops::Range;
+
+use crate::{chars_needed, encoded_len};
+
+/// A text encoding to compare.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// [`encode`](crate::encode): 2 bytes → 3 characters.
+    Base44,
+    /// [`encode_bits`](crate::encode_bits) over `8 * byte_len` bits.
+    Base44Optimal,
+    /// RFC 9285 Base45 (same lengths as byte-pair Base44).
+    Base45,
+    /// RFC 4648 Base64 without padding.
+    Base64,
+    /// RFC 4648 Base32 without padding.
+    Base32,
+    /// Upper-case hexadecimal.
+    Hex,
+}
+
+impl Encoding {
+    /// All encodings, in the order used by [`Comparison::entries`].
+    pub const ALL: [Encoding; 6] = [
+        Encoding::Base44,
+        Encoding::Base44Optimal,
+        Encoding::Base45,
+        Encoding::Base64,
+        Encoding::Base32,
+        Encoding::Hex,
+    ];
+
+    /// Encoded length of `byte_len` bytes, in characters.
+    pub fn encoded_len(self, byte_len: usize) -> usize {
+        match self {
+            Encoding::Base44 | Encoding::Base45 => encoded_len(byte_len),
+            Encoding::Base44Optimal => chars_needed(byte_len * 8),
+            Encoding::Base64 => (byte_len * 4).div_ceil(3),
+            Encoding::Base32 => (byte_len * 8).div_ceil(5),
+            Encoding::Hex => byte_len * 2,
+        }
+    }
+
+    /// The QR Code mode needed to store this encoding's output.
+    pub fn qr_mode(self) -> QrMode {
+        match self {
+            Encoding::Base64 => QrMode::Byte,
+            _ => QrMode::Alphanumeric,
+        }
+    }
+}
+
+/// QR Code data mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QrMode {
+    /// Digits only, 10 bits per 3 characters.
+    Numeric,
+    /// 45-character set, 11 bits per 2 characters.
+    Alphanumeric,
+    /// 8 bits per character.
+    Byte,
+}
+
+impl QrMode {
+    /// Bits used by a single segment of `chars` characters in `version`.
+    pub fn segment_bits(self, chars: usize, version: u8) -> usize {
+        let (count_bits, data_bits) = match self {
+            QrMode::Numeric => (
+                match version {
+                    1..=9 => 10,
+                    10..=26 => 12,
+                    _ => 14,
+                },
+                chars / 3 * 10 + [0, 4, 7][chars % 3],
+            ),
+            QrMode::Alphanumeric => (
+                match version {
+                    1..=9 => 9,
+                    10..=26 => 11,
+                    _ => 13,
+                },
+                chars / 2 * 11 + chars % 2 * 6,
+            ),
+            QrMode::Byte => (if version <= 9 { 8 } else { 16 }, chars * 8),
+        };
+        4 + count_bits + data_bits
+    }
+}
+
+/// QR Code error-correction level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EcLevel {
+    /// ~7% recovery.
+    L,
+    /// ~15% recovery.
+    M,
+    /// ~25% recovery.
+    Q,
+    /// ~30% recovery.
+    H,
+}
+
+impl EcLevel {
+    /// All levels, lowest redundancy first.
+    pub const ALL: [EcLevel; 4] = [EcLevel::L, EcLevel::M, EcLevel::Q, EcLevel::H];
+}
+
+/// Data codewords per version (1..=40), for levels L, M, Q, H.
+const DATA_CODEWORDS: [[u16; 4]; 40] = [
+    [19, 16, 13, 9],
+    [34, 28, 22, 16],
+    [55, 44, 34, 26],
+    [80, 64, 48, 36],
+    [108, 86, 62, 46],
+    [136, 108, 76, 60],
+    [156, 124, 88, 66],
+    [194, 154, 110, 86],
+    [232, 182, 132, 100],
+    [274, 216, 154, 122],
+    [324, 254, 180, 140],
+    [370, 290, 206, 158],
+    [428, 334, 244, 180],
+    [461, 365, 261, 197],
+    [523, 415, 295, 223],
+    [589, 453, 325, 253],
+    [647, 507, 367, 283],
+    [721, 563, 397, 313],
+    [795, 627, 445, 341],
+    [861, 669, 485, 385],
+    [932, 714, 512, 406],
+    [1006, 782, 568, 442],
+    [1094, 860, 614, 464],
+    [1174, 914, 664, 514],
+    [1276, 1000, 718, 538],
+    [1370, 1062, 754, 596],
+    [1468, 1128, 808, 628],
+    [1531, 1193, 871, 661],
+    [1631, 1267, 911, 701],
+    [1735, 1373, 985, 745],
+    [1843, 1455, 1033, 793],
+    [1955, 1541, 1115, 845],
+    [2071, 1631, 1171, 901],
+    [2191, 1725, 1231, 961],
+    [2306, 1812, 1286, 986],
+    [2434, 1914, 1354, 1054],
+    [2566, 1992, 1426, 1096],
+    [2702, 2102, 1502, 1142],
+    [2812, 2216, 1582, 1222],
+    [2956, 2334, 1666, 1276],
+];
+
+/// Smallest QR version (1..=40) holding `chars` characters in `mode` at `ec`,
+/// or `None` if it does not fit in version 40.
+pub fn qr_version(mode: QrMode, chars: usize, ec: EcLevel) -> Option<u8> {
+    (1..=40u8).find(|&v| {
+        let capacity = DATA_CODEWORDS[v as usize - 1][ec as usize] as usize * 8;
+        mode.segment_bits(chars, v) <= capacity
+    })
+}
+
+/// A run of characters stored in one QR segment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    /// Mode of the segment.
+    pub mode: QrMode,
+    /// Byte range of the encoded string it covers.
+    pub range: Range<usize>,
+}
+
+/// Split Base44 text `s` into the numeric and alphanumeric segments that take
+/// the fewest bits in `version`.
+///
+/// All-digit text becomes a single numeric segment; a digit run inside other
+/// text gets its own segment only when that pays for the extra segment header.
+/// Characters are not validated: anything other than `0`-`9` is assumed to be
+/// alphanumeric-mode text, which every Base44 character is.
+///
+/// ```
+/// use qr_base44::analysis::{QrMode, split_segments};
+///
+/// let digits = split_segments("0123456789", 1);
+/// assert_eq!(digits.len(), 1);
+/// assert_eq!(digits[0].mode, QrMode::Numeric);
+/// // A short digit run is not worth a segment switch.
+/// assert_eq!(split_segments("AB12CD", 1).len(), 1);
+/// ```
+pub fn split_segments(s: &str, version: u8) -> Vec<Segment> {
+    let s = s.as_bytes();
+    let n = s.len();
+    // digit_run[i]: digits starting at i.
+    let mut digit_run = vec![0; n + 1];
+    for i in (0..n).rev() {
+        if s[i].is_ascii_digit() {
+            digit_run[i] = digit_run[i + 1] + 1;
+        }
+    }
+    // best[j]: fewest bits for s[..j], and the last segment achieving it.
+    let mut best: Vec<(usize, usize, QrMode)> = vec![(0, 0, QrMode::Alphanumeric); n + 1];
+    for j in 1..=n {
+        best[j] = (usize::MAX, 0, QrMode::Alphanumeric);
+        for i in 0..j {
+            let mut consider = |mode: QrMode| {
+                let bits = best[i].0 + mode.segment_bits(j - i, version);
+                if bits < best[j].0 {
+                    best[j] = (bits, i, mode);
+                }
+            };
+            consider(QrMode::Alphanumeric);
+            if digit_run[i] >= j - i {
+                consider(QrMode::Numeric);
+            }
+        }
+    }
+    let mut segments = Vec::new();
+    let mut end = n;
+    while end > 0 {
+        let (_, start, mode) = best[end];
+        segments.push(Segment {
+            mode,
+            range: start..end,
+        });
+        end = start;
+    }
+    segments.reverse();
+    segments
+}
+
+/// Bits used by `segments` in `version`.
+pub fn segments_bits(segments: &[Segment], version: u8) -> usize {
+    segments
+        .iter()
+        .map(|seg| seg.mode.segment_bits(seg.range.len(), version))
+        .sum()
+}
+
+/// Smallest QR version (1..=40) holding Base44 text `s` at `ec` when split with
+/// [`split_segments`], and the segments to use, or `None` if it does not fit.
+///
+/// Can be smaller than [`qr_version`] for [`QrMode::Alphanumeric`] when `s`
+/// contains long digit runs, and is never larger.
+pub fn qr_version_segmented(s: &str, ec: EcLevel) -> Option<(u8, Vec<Segment>)> {
+    // Segment costs only change where the count field widens.
+    [1..=9u8, 10..=26, 27..=40]
+        .into_iter()
+        .find_map(|versions| {
+            let segments = split_segments(s, *versions.start());
+            let bits = segments_bits(&segments, *versions.start());
+            versions
+                .into_iter()
+                .find(|&v| bits <= DATA_CODEWORDS[v as usize - 1][ec as usize] as usize * 8)
+                .map(|v| (v, segments))
+        })
+}
+
+/// One row of a [`Comparison`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    /// The encoding.
+    pub encoding: Encoding,
+    /// Encoded length in characters.
+    pub chars: usize,
+    /// Smallest QR version per error-correction level, indexed like [`EcLevel::ALL`].
+    pub qr_versions: [Option<u8>; 4],
+}
+
+impl Entry {
+    /// Smallest QR version at `ec`, or `None` if the output exceeds version 40.
+    pub fn qr_version(&self, ec: EcLevel) -> Option<u8> {
+        self.qr_versions[ec as usize]
+    }
+}
+
+/// Encoded sizes of one payload length across all [`Encoding`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Comparison {
+    /// The payload length in bytes.
+    pub byte_len: usize,
+    entries: [Entry; 6],
+}
+
+impl Comparison {
+    /// All rows, in [`Encoding::ALL`] order.
+    pub fn entries(&self) -> &[Entry; 6] {
+        &self.entries
+    }
+
+    /// The row for `encoding`.
+    pub fn get(&self, encoding: Encoding) -> &Entry {
+        &self.entries[encoding as usize]
+    }
+
+    /// QR version of `encoding` minus that of byte-pair Base44 at `ec`; positive
+    /// means `encoding` needs a larger symbol. `None` if either exceeds version 40.
+    pub fn qr_version_delta(&self, encoding: Encoding, ec: EcLevel) -> Option<i16> {
+        let base = self.get(Encoding::Base44).qr_version(ec)?;
+        let other = self.get(encoding).qr_version(ec)?;
+        Some(other as i16 - base as i16)
+    }
+}
+
+/// Compare encoded lengths and QR versions for a payload of `byte_len` bytes.
+pub fn compare(byte_len: usize) -> Comparison {
+    let entries = Encoding::ALL.map(|encoding| {
+        let chars = encoding.encoded_len(byte_len);
+        Entry {
+            encoding,
+            chars,
+            qr_versions: EcLevel::ALL.map(|ec| qr_version(encoding.qr_mode(), chars, ec)),
+        }
+    });
+    Comparison { byte_len, entries }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lengths_match_readme_table() {
+        for (bytes, pair, optimal) in [(13, 20, 20), (16, 24, 24), (32, 48, 47), (64, 96, 94)] {
+            let c = compare(bytes);
+            assert_eq!(c.get(Encoding::Base44).chars, pair);
+            assert_eq!(c.get(Encoding::Base44Optimal).chars, optimal);
+            assert_eq!(c.get(Encoding::Base45).chars, pair);
+        }
+        let c = compare(16);
+        assert_eq!(c.get(Encoding::Base64).chars, 22);
+        assert_eq!(c.get(Encoding::Base32).chars, 26);
+        assert_eq!(c.get(Encoding::Hex).chars, 32);
+        assert_eq!(compare(0).get(Encoding::Base44Optimal).chars, 0);
+    }
+
+    #[test]
+    fn qr_versions_match_published_capacities() {
+        // Version 1 and 40 capacities from ISO/IEC 18004 Table 7.
+        assert_eq!(qr_version(QrMode::Alphanumeric, 25, EcLevel::L), Some(1));
+        assert_eq!(qr_version(QrMode::Alphanumeric, 26, EcLevel::L), Some(2));
+        assert_eq!(qr_version(QrMode::Byte, 7, EcLevel::H), Some(1));
+        assert_eq!(qr_version(QrMode::Byte, 8, EcLevel::H), Some(2));
+        assert_eq!(qr_version(QrMode::Alphanumeric, 4296, EcLevel::L), Some(40));
+        assert_eq!(qr_version(QrMode::Alphanumeric, 4297, EcLevel::L), None);
+        assert_eq!(qr_version(QrMode::Byte, 2953, EcLevel::L), Some(40));
+        assert_eq!(qr_version(QrMode::Byte, 1273, EcLevel::H), Some(40));
+        assert_eq!(qr_version(QrMode::Byte, 1274, EcLevel::H), None);
+    }
+
+    #[test]
+    fn numeric_segments_for_digit_runs() {
+        // Version 1-H holds 17 digits in numeric mode but only 10 alphanumerics.
+        assert_eq!(qr_version(QrMode::Numeric, 17, EcLevel::H), Some(1));
+        assert_eq!(qr_version(QrMode::Numeric, 18, EcLevel::H), Some(2));
+        assert_eq!(qr_version(QrMode::Numeric, 7089, EcLevel::L), Some(40));
+        let digits = "12345678901234567";
+        assert_eq!(qr_version(QrMode::Alphanumeric, 17, EcLevel::H), Some(2));
+        let (v, segments) = qr_version_segmented(digits, EcLevel::H).unwrap();
+        assert_eq!(v, 1);
+        assert_eq!(
+            segments,
+            [Segment {
+                mode: QrMode::Numeric,
+                range: 0..17
+            }]
+        );
+
+        // A long run in the middle is split out; the pieces cover the input.
+        let mixed = format!("AB{}CD", "7".repeat(30));
+        let segments = split_segments(&mixed, 1);
+        assert_eq!(
+            segments.iter().map(|s| s.mode).collect::<Vec<_>>(),
+            [QrMode::Alphanumeric, QrMode::Numeric, QrMode::Alphanumeric]
+        );
+        assert_eq!(segments[1].range, 2..32);
+        assert!(segments_bits(&segments, 1) < QrMode::Alphanumeric.segment_bits(mixed.len(), 1));
+        assert!(split_segments("", 1).is_empty());
+        assert_eq!(qr_version_segmented(&"A".repeat(4297), EcLevel::L), None);
+    }
+
+    #[test]
+    fn deltas_are_relative_to_byte_pair() {
+        let c = compare(13);
+        assert_eq!(c.get(Encoding::Base44).qr_version(EcLevel::M), Some(1));
+        assert_eq!(c.get(Encoding::Base64).qr_version(EcLevel::M), Some(2));
+        assert_eq!(c.qr_version_delta(Encoding::Base64, EcLevel::M), Some(1));
+        assert_eq!(c.qr_version_delta(Encoding::Base45, EcLevel::M), Some(0));
+        assert_eq!(
+            compare(4000).qr_version_delta(Encoding::Hex, EcLevel::L),
+            None
+        );
+    }
+}
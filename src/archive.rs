@@ -0,0 +1,60 @@
+//! rkyv zero-copy archiving for [`Base44String`] ([`rkyv`] feature).
+//!
+//! A `Base44String` archives as a plain [`ArchivedString`], so a memory-mapped
+//! archive exposes the text with [`ArchivedString::as_str`] without copying.
+//! Deserializing back into a `Base44String` re-validates it, because archive
+//! validation only checks the layout, not the contents.
+
+use rkyv::rancor::{Fallible, Source};
+use rkyv::ser::Writer;
+use rkyv::string::{ArchivedString, StringResolver};
+use rkyv::{Archive, Deserialize, Place, Serialize};
+
+use crate::Base44String;
+
+impl Archive for Base44String {
+    type Archived = ArchivedString;
+    type Resolver = StringResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedString::resolve_from_str(&self.0, resolver, out);
+    }
+}
+
+impl<S: Fallible + Writer + ?Sized> Serialize<S> for Base44String
+where
+    S::Error: Source,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedString::serialize_from_str(&self.0, serializer)
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<Base44String, D> for ArchivedString
+where
+    D::Error: Source,
+{
+    fn deserialize(&self, _: &mut D) -> Result<Base44String, D::Error> {
+        Base44String::new(self.as_str()).map_err(D::Error::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rkyv::rancor::Error;
+
+    use super::*;
+
+    #[test]
+    fn archives_as_text_and_validates_on_deserialize() {
+        let s = Base44String::from_bytes(b"hi");
+        let bytes = rkyv::to_bytes::<Error>(&s).unwrap();
+        let archived = rkyv::access::<ArchivedString, Error>(&bytes).unwrap();
+        assert_eq!(archived.as_str(), "LZD");
+        assert_eq!(rkyv::from_bytes::<Base44String, Error>(&bytes).unwrap(), s);
+
+        // Same layout as a `String`, so a corrupted value is caught on the way out.
+        let bytes = rkyv::to_bytes::<Error>(&String::from("LZ")).unwrap();
+        assert!(rkyv::from_bytes::<Base44String, Error>(&bytes).is_err());
+    }
+}
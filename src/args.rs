@@ -0,0 +1,113 @@
+//! clap value parser for Base44 command-line arguments ([`clap`] feature).
+
+use std::ffi::OsStr;
+use std::marker::PhantomData;
+
+use clap::builder::TypedValueParser;
+use clap::error::ErrorKind;
+use clap::{Arg, Command, Error};
+
+use crate::{Base44Error, FromBase44, find_invalid_char};
+
+/// Decodes an argument from byte-pair Base44 into any [`FromBase44`] type,
+/// such as `Vec<u8>`, `[u8; N]` or [`Base44String`](crate::Base44String).
+///
+/// Failures are reported in clap's usual format, naming the argument and, for
+/// characters outside the alphabet, the offending character and its position.
+///
+/// ```
+/// use clap::{Arg, Command};
+/// use qr_base44::Base44ValueParser;
+///
+/// let cmd = Command::new("tool").arg(
+///     Arg::new("key")
+///         .long("key")
+///         .value_parser(Base44ValueParser::<[u8; 2]>::new()),
+/// );
+/// let matches = cmd.clone().try_get_matches_from(["tool", "--key", "LZD"]).unwrap();
+/// assert_eq!(matches.get_one::<[u8; 2]>("key"), Some(&*b"hi"));
+/// assert!(cmd.try_get_matches_from(["tool", "--key", "LZDJ%X"]).is_err());
+/// ```
+#[derive(Debug)]
+pub struct Base44ValueParser<T>(PhantomData<fn() -> T>);
+
+impl<T> Base44ValueParser<T> {
+    /// A parser producing `T`.
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Default for Base44ValueParser<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for Base44ValueParser<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Base44ValueParser<T> {}
+
+impl<T> TypedValueParser for Base44ValueParser<T>
+where
+    T: FromBase44 + Clone + Send + Sync + 'static,
+{
+    type Value = T;
+
+    fn parse_ref(&self, cmd: &Command, arg: Option<&Arg>, value: &OsStr) -> Result<T, Error> {
+        let arg = arg.map_or_else(|| "...".to_owned(), Arg::to_string);
+        let invalid = |detail: String| {
+            let value = value.to_string_lossy();
+            let message = format!("invalid value '{value}' for '{arg}': {detail}\n");
+            Error::raw(ErrorKind::ValueValidation, message).with_cmd(cmd)
+        };
+        let s = value
+            .to_str()
+            .ok_or_else(|| invalid(Base44Error::InvalidChar.to_string()))?;
+        T::from_base44(s).map_err(|err| match find_invalid_char(s) {
+            Some(at) if err == Base44Error::InvalidChar => invalid(at.to_string()),
+            _ => invalid(err.to_string()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command() -> Command {
+        Command::new("tool").arg(
+            Arg::new("data")
+                .long("data")
+                .value_parser(Base44ValueParser::<Vec<u8>>::new()),
+        )
+    }
+
+    #[test]
+    fn decodes_and_reports_errors() {
+        let matches = command()
+            .try_get_matches_from(["tool", "--data", "J%XL1"])
+            .unwrap();
+        assert_eq!(
+            matches.get_one::<Vec<u8>>("data"),
+            Some(&vec![0xFF, 0xFF, 0x41])
+        );
+
+        let err = command()
+            .try_get_matches_from(["tool", "--data", "J%xL1"])
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+        assert!(err.to_string().contains(
+            "invalid value 'J%xL1' for '--data <data>': invalid base44 character 'x' at index 2"
+        ));
+
+        let err = command()
+            .try_get_matches_from(["tool", "--data", "ZZZ"])
+            .unwrap_err();
+        assert!(err.to_string().contains(": value overflow"));
+    }
+}
@@ -0,0 +1,258 @@
+//! Density-driven mode selection with a one-character header.
+//!
+//! The header character's digit value is `version << 2 | mode`, so it carries a
+//! 2-bit format version alongside the 2-bit mode and the format can evolve
+//! without breaking old readers' ability to recognise it. Version 0 headers are
+//! the digits `0`, `1` and `2`.
+
+use crate::engine::optimal_byte_len;
+use crate::{
+    BASE44_ALPHABET, Base44Error, b44_val, chars_needed, decode, decode_bits, decode_v2, encode,
+    encode_bits, encode_v2, encoded_len,
+};
+
+/// How the body after the header is encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvelopeMode {
+    /// Byte-pair encoding, as [`encode`].
+    BytePair,
+    /// 15-byte blocks, each packed optimally into 22 characters.
+    Block,
+    /// The whole message as one integer, as [`encode_bits`].
+    Whole,
+    /// Mode 3, not assigned in any version yet.
+    Reserved,
+}
+
+/// What [`Envelope::probe`] reads from the header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeaderInfo {
+    /// Format version, `0..=3`.
+    pub version: u8,
+    /// Encoding of the body.
+    pub mode: EnvelopeMode,
+}
+
+/// The self-describing format written by [`encode_auto`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Envelope;
+
+impl Envelope {
+    /// Version written by [`encode_auto`].
+    pub const VERSION: u8 = 0;
+    /// Version whose byte-pair mode holds a [`encode_v2`] body.
+    pub const VERSION_V2: u8 = 1;
+
+    /// Byte-pair encode `input` in the v2 framing behind a version 1 header.
+    ///
+    /// [`decode_auto`] reads it like any other envelope.
+    ///
+    /// ```
+    /// use qr_base44::{Envelope, decode_auto};
+    ///
+    /// let s = Envelope::encode_v2(b"hi");
+    /// assert_eq!(s, "4LZD2");
+    /// assert_eq!(Envelope::probe(&s).unwrap().version, Envelope::VERSION_V2);
+    /// assert_eq!(decode_auto(&s).unwrap(), b"hi");
+    /// ```
+    pub fn encode_v2(input: &[u8]) -> String {
+        let mut out = String::from(Self::header(Self::VERSION_V2, EnvelopeMode::BytePair));
+        out.push_str(&encode_v2(input));
+        out
+    }
+
+    /// Read the header of `s` without decoding the body.
+    ///
+    /// An empty string is [`Base44Error::InvalidLength`]; a first character that
+    /// is not a header (outside the alphabet, or a digit value of 16 or more) is
+    /// [`Base44Error::InvalidChar`]. Headers of unknown versions are reported, so
+    /// callers can tell "newer format" apart from garbage.
+    ///
+    /// ```
+    /// use qr_base44::{Envelope, EnvelopeMode, HeaderInfo, encode_auto};
+    ///
+    /// let info = Envelope::probe(&encode_auto(b"hi")).unwrap();
+    /// assert_eq!(info, HeaderInfo { version: 0, mode: EnvelopeMode::BytePair });
+    /// ```
+    pub fn probe(s: &str) -> Result<HeaderInfo, Base44Error> {
+        let &first = s.as_bytes().first().ok_or(Base44Error::InvalidLength)?;
+        let value = b44_val(first).ok_or(Base44Error::InvalidChar)?;
+        if value >= 16 {
+            return Err(Base44Error::InvalidChar);
+        }
+        let mode = match value & 0b11 {
+            0 => EnvelopeMode::BytePair,
+            1 => EnvelopeMode::Block,
+            2 => EnvelopeMode::Whole,
+            _ => EnvelopeMode::Reserved,
+        };
+        Ok(HeaderInfo {
+            version: (value >> 2) as u8,
+            mode,
+        })
+    }
+
+    fn header(version: u8, mode: EnvelopeMode) -> char {
+        let mode = match mode {
+            EnvelopeMode::BytePair => 0,
+            EnvelopeMode::Block => 1,
+            EnvelopeMode::Whole => 2,
+            EnvelopeMode::Reserved => 3,
+        };
+        BASE44_ALPHABET[(version << 2 | mode) as usize] as char
+    }
+}
+
+/// Bytes per block: the largest block that fits `u128` arithmetic and beats
+/// byte-pair (15 bytes → 22 characters instead of 23).
+const BLOCK_BYTES: usize = 15;
+/// Whole-message encoding is quadratic, so it is only considered for short input.
+const WHOLE_MAX_BYTES: usize = 256;
+
+fn block_encoded_len(len: usize) -> usize {
+    let tail = len % BLOCK_BYTES;
+    len / BLOCK_BYTES * chars_needed(BLOCK_BYTES * 8) + chars_needed(tail * 8)
+}
+
+/// Encode `input` with whichever scheme is shortest, prefixed by a header
+/// character naming the scheme (see [`Envelope`]).
+///
+/// Candidates are byte-pair ([`encode`]), 15-byte blocks packed with
+/// [`encode_bits`], and (for inputs up to 256 bytes) the whole message as one
+/// integer. Ties go to the cheaper scheme. [`decode_auto`] reads the marker, so
+/// callers get the best density without fixing a mode up front.
+///
+/// ```
+/// use qr_base44::{decode_auto, encode, encode_auto};
+///
+/// let data = [0xA5; 300];
+/// let s = encode_auto(&data);
+/// assert!(s.len() < encode(&data).len());
+/// assert_eq!(decode_auto(&s).unwrap(), data);
+/// ```
+pub fn encode_auto(input: &[u8]) -> String {
+    let pair = encoded_len(input.len());
+    let block = block_encoded_len(input.len());
+    // Only sized for short input: `len * 8` bits overflow or pass `MAX_CHARS`
+    // long before the byte-pair length does.
+    let whole = (input.len() <= WHOLE_MAX_BYTES).then(|| chars_needed(input.len() * 8));
+
+    let mut out = String::new();
+    if whole.is_some_and(|whole| whole < pair.min(block)) {
+        out.push(Envelope::header(Envelope::VERSION, EnvelopeMode::Whole));
+        out.push_str(&encode_bits(input.len() * 8, input));
+    } else if block < pair {
+        out.reserve(block + 1);
+        out.push(Envelope::header(Envelope::VERSION, EnvelopeMode::Block));
+        for chunk in input.chunks(BLOCK_BYTES) {
+            out.push_str(&encode_bits(chunk.len() * 8, chunk));
+        }
+    } else {
+        out.push(Envelope::header(Envelope::VERSION, EnvelopeMode::BytePair));
+        out.push_str(&encode(input));
+    }
+    out
+}
+
+/// Decode the output of [`encode_auto`].
+///
+/// Also reads [`Envelope::encode_v2`] output. Returns
+/// [`Base44Error::InvalidLength`] for an empty string and
+/// [`Base44Error::InvalidChar`] for any other header.
+pub fn decode_auto(s: &str) -> Result<Vec<u8>, Base44Error> {
+    let header = Envelope::probe(s)?;
+    let body = &s[1..];
+    match header.version {
+        Envelope::VERSION => {}
+        Envelope::VERSION_V2 if header.mode == EnvelopeMode::BytePair => return decode_v2(body),
+        _ => return Err(Base44Error::InvalidChar),
+    }
+    match header.mode {
+        EnvelopeMode::BytePair => decode(body),
+        EnvelopeMode::Whole if body.is_empty() => Ok(Vec::new()),
+        // `encode_auto` never writes longer Whole bodies; refuse them before
+        // the quadratic decode.
+        EnvelopeMode::Whole if body.len() > chars_needed(WHOLE_MAX_BYTES * 8) => {
+            Err(Base44Error::InvalidLength)
+        }
+        EnvelopeMode::Whole => {
+            let len = optimal_byte_len(body.len()).ok_or(Base44Error::InvalidLength)?;
+            decode_bits(len * 8, body)
+        }
+        EnvelopeMode::Block => {
+            let block_chars = chars_needed(BLOCK_BYTES * 8);
+            let mut out = Vec::with_capacity(body.len() / block_chars * BLOCK_BYTES + BLOCK_BYTES);
+            for chunk in body.as_bytes().chunks(block_chars) {
+                let chunk = core::str::from_utf8(chunk).map_err(|_| Base44Error::InvalidChar)?;
+                let len = optimal_byte_len(chunk.len()).ok_or(Base44Error::InvalidLength)?;
+                out.extend_from_slice(&decode_bits(len * 8, chunk)?);
+            }
+            Ok(out)
+        }
+        EnvelopeMode::Reserved => Err(Base44Error::InvalidChar),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_the_shortest_mode_and_roundtrips() {
+        for len in 0..200 {
+            let data: Vec<u8> = (0..len).map(|i| (i * 37 + 11) as u8).collect();
+            let s = encode_auto(&data);
+            let body = s.len() - 1;
+            assert!(body <= encoded_len(len), "len {len}");
+            assert!(body <= block_encoded_len(len), "len {len}");
+            assert_eq!(decode_auto(&s).unwrap(), data, "len {len}");
+        }
+        assert!(encode_auto(b"").starts_with('0'));
+        assert!(encode_auto(&[0xFF; 58]).starts_with('2'));
+        assert!(encode_auto(&[0xFF; 300]).starts_with('1'));
+    }
+
+    #[test]
+    fn malformed_auto_strings() {
+        assert_eq!(decode_auto(""), Err(Base44Error::InvalidLength));
+        assert_eq!(decode_auto("9LZD"), Err(Base44Error::InvalidChar));
+        assert_eq!(decode_auto("2ABCD"), Err(Base44Error::InvalidLength));
+        assert_eq!(decode_auto("1ABCD"), Err(Base44Error::InvalidLength));
+        let long = format!("2{}", "0".repeat(chars_needed(WHOLE_MAX_BYTES * 8) + 1));
+        assert_eq!(decode_auto(&long), Err(Base44Error::InvalidLength));
+        assert_eq!(
+            decode_auto(&long[..long.len() - 1]).unwrap(),
+            [0; WHOLE_MAX_BYTES]
+        );
+        assert_eq!(decode_auto("0LZD").unwrap(), b"hi");
+        assert_eq!(decode_auto("3LZD"), Err(Base44Error::InvalidChar));
+        assert_eq!(decode_auto("4LZD2").unwrap(), b"hi");
+        assert_eq!(decode_auto("4LZD"), Err(Base44Error::InvalidLength));
+        assert_eq!(decode_auto("5LZD2"), Err(Base44Error::InvalidChar));
+        assert_eq!(decode_auto("4LZé"), Err(Base44Error::InvalidChar));
+    }
+
+    #[test]
+    fn probe_reads_version_and_mode() {
+        let whole = encode_auto(&[0xFF; 58]);
+        assert_eq!(
+            Envelope::probe(&whole).unwrap(),
+            HeaderInfo {
+                version: 0,
+                mode: EnvelopeMode::Whole
+            }
+        );
+        // Version 2, block mode: 2 << 2 | 1 = 9, and the body is not looked at.
+        assert_eq!(
+            Envelope::probe("9\u{0}").unwrap(),
+            HeaderInfo {
+                version: 2,
+                mode: EnvelopeMode::Block
+            }
+        );
+        assert_eq!(Envelope::probe("F").unwrap().version, 3);
+        assert_eq!(Envelope::probe("G"), Err(Base44Error::InvalidChar));
+        assert_eq!(Envelope::probe(" "), Err(Base44Error::InvalidChar));
+        assert_eq!(Envelope::probe(""), Err(Base44Error::InvalidLength));
+    }
+}
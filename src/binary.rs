@@ -0,0 +1,40 @@
+//! borsh serialization for [`Base44String`] ([`borsh`] feature).
+//!
+//! The wire format is borsh's `String` (little-endian `u32` length, then the
+//! UTF-8 bytes), so schemas can declare the field as a plain string. Reading
+//! validates the text and fails with [`ErrorKind::InvalidData`] otherwise.
+
+use borsh::io::{Error, ErrorKind, Read, Result, Write};
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::Base44String;
+
+impl BorshSerialize for Base44String {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.0.serialize(writer)
+    }
+}
+
+impl BorshDeserialize for Base44String {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let s = String::deserialize_reader(reader)?;
+        Base44String::new(s).map_err(|err| Error::new(ErrorKind::InvalidData, err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_bytes_as_a_string_and_validated_on_read() {
+        let s = Base44String::from_bytes(b"hi");
+        let bytes = borsh::to_vec(&s).unwrap();
+        assert_eq!(bytes, borsh::to_vec("LZD").unwrap());
+        assert_eq!(borsh::from_slice::<Base44String>(&bytes).unwrap(), s);
+
+        let bad = borsh::to_vec("LZ").unwrap();
+        let err = borsh::from_slice::<Base44String>(&bad).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+}
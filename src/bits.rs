@@ -0,0 +1,69 @@
+//! Bit-level API on `bitvec` types (enabled with the `bitvec` feature).
+//!
+//! Bits map onto the integer of [`encode_bits`] in `Lsb0` order: bit `i` of the
+//! slice is bit `i` of the encoded value. A 51-bit sensor sample is therefore
+//! encoded without manual padding or masking.
+
+use bitvec::prelude::*;
+
+use crate::{Base44Error, decode_bits, encode_bits};
+
+/// Encode exactly `bits.len()` bits with optimal length.
+///
+/// ```
+/// use bitvec::prelude::*;
+///
+/// let sample = bits![u8, Lsb0; 1; 51];
+/// let s = qr_base44::encode_bitslice(sample);
+/// assert_eq!(s.len(), 10);
+/// assert_eq!(qr_base44::decode_to_bitvec(&s, 51).unwrap(), sample);
+/// ```
+pub fn encode_bitslice(bits: &BitSlice<u8, Lsb0>) -> String {
+    if bits.is_empty() {
+        return String::new();
+    }
+    // Copy into a fresh vector so the bits start at index 0 of the first byte.
+    let mut packed: BitVec<u8, Lsb0> = BitVec::with_capacity(bits.len());
+    packed.extend_from_bitslice(bits);
+    packed.set_uninitialized(false);
+    encode_bits(bits.len(), packed.as_raw_slice())
+}
+
+/// Decode a string produced by [`encode_bitslice`] holding `bits` bits.
+pub fn decode_to_bitvec(s: &str, bits: usize) -> Result<BitVec<u8, Lsb0>, Base44Error> {
+    if bits == 0 {
+        return if s.is_empty() {
+            Ok(BitVec::new())
+        } else {
+            Err(Base44Error::Overflow)
+        };
+    }
+    let mut out = BitVec::from_vec(decode_bits(bits, s)?);
+    out.truncate(bits);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_byte_level_encoding() {
+        let bytes = [0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0x05];
+        let slice = &bytes.view_bits::<Lsb0>()[..51];
+        let s = encode_bitslice(slice);
+        assert_eq!(s, encode_bits(51, &bytes));
+        assert_eq!(decode_to_bitvec(&s, 51).unwrap(), slice);
+    }
+
+    #[test]
+    fn unaligned_slices_and_empty() {
+        let bytes = [0b1010_1100u8, 0xFF, 0x01];
+        // A slice that does not start on a byte boundary
+        let slice = &bytes.view_bits::<Lsb0>()[3..20];
+        let s = encode_bitslice(slice);
+        assert_eq!(decode_to_bitvec(&s, 17).unwrap(), slice);
+        assert_eq!(encode_bitslice(BitSlice::empty()), "");
+        assert!(decode_to_bitvec("", 0).unwrap().is_empty());
+    }
+}
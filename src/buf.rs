@@ -0,0 +1,120 @@
+//! Encoding between [`bytes`] buffers ([`bytes`] feature).
+//!
+//! Both functions walk the source chunk by chunk, so a `Bytes` chain or any other
+//! non-contiguous [`Buf`] is processed without flattening it first.
+
+use core::convert::Infallible;
+
+use bytes::{Buf, BufMut};
+
+use crate::sink::{Sink, encode_to_sink};
+use crate::{Base44Error, StreamDecoder};
+
+/// Byte-pair encode everything remaining in `src` into `dst`.
+///
+/// The output is identical to [`encode`](crate::encode) of the concatenated input.
+///
+/// ```
+/// use bytes::{Buf, Bytes};
+/// use qr_base44::encode_buf;
+///
+/// let src = Bytes::from_static(b"Hello, ").chain(Bytes::from_static(b"World!"));
+/// let mut dst = Vec::new();
+/// encode_buf(src, &mut dst);
+/// assert_eq!(dst, qr_base44::encode(b"Hello, World!").as_bytes());
+/// ```
+pub fn encode_buf(mut src: impl Buf, dst: impl BufMut) {
+    let mut sink = BufMutSink(dst);
+    let mut pending = None;
+    while src.has_remaining() {
+        let mut chunk = src.chunk();
+        let len = chunk.len();
+        if let Some(hi) = pending.take() {
+            let Ok(()) = encode_to_sink(&[hi, chunk[0]], &mut sink);
+            chunk = &chunk[1..];
+        }
+        let (pairs, rest) = chunk.split_at(chunk.len() & !1);
+        let Ok(()) = encode_to_sink(pairs, &mut sink);
+        if let [u] = rest {
+            pending = Some(*u);
+        }
+        src.advance(len);
+    }
+    if let Some(u) = pending {
+        let Ok(()) = encode_to_sink(&[u], &mut sink);
+    }
+}
+
+/// A [`Sink`] over a `BufMut`.
+struct BufMutSink<B>(B);
+
+impl<B: BufMut> Sink for BufMutSink<B> {
+    type Error = Infallible;
+
+    fn put(&mut self, ascii: &[u8]) -> Result<(), Infallible> {
+        self.0.put_slice(ascii);
+        Ok(())
+    }
+}
+
+/// Decode byte-pair text remaining in `src` into `dst`.
+///
+/// Accepts exactly what [`decode`](crate::decode) accepts. On error, bytes decoded
+/// before the offending group may already have been written to `dst`.
+///
+/// ```
+/// use bytes::{Buf, BytesMut};
+/// use qr_base44::decode_buf;
+///
+/// let src = (&b"J%"[..]).chain(&b"XL1"[..]);
+/// let mut dst = BytesMut::new();
+/// decode_buf(src, &mut dst).unwrap();
+/// assert_eq!(&dst[..], [0xFF, 0xFF, 0x41]);
+/// ```
+pub fn decode_buf(mut src: impl Buf, mut dst: impl BufMut) -> Result<(), Base44Error> {
+    let mut decoder = StreamDecoder::new();
+    let mut sink = |bytes: &[u8]| {
+        dst.put_slice(bytes);
+        Ok(())
+    };
+    while src.has_remaining() {
+        let chunk = src.chunk();
+        let len = chunk.len();
+        decoder.feed(chunk, &mut sink)?;
+        src.advance(len);
+    }
+    decoder.finish(sink)
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::*;
+    use crate::{decode, encode};
+
+    /// `data` as a chain of single-byte and short buffers.
+    fn fragmented(data: &[u8]) -> impl Buf {
+        let (a, rest) = data.split_at(data.len().min(1));
+        let (b, c) = rest.split_at(rest.len().min(4));
+        Bytes::copy_from_slice(a).chain(Bytes::copy_from_slice(b).chain(Bytes::copy_from_slice(c)))
+    }
+
+    #[test]
+    fn matches_contiguous_codec() {
+        let data: Vec<u8> = (0..=255).collect();
+        for len in [0, 1, 2, 3, 4, 5, 6, 255, 256] {
+            let data = &data[..len];
+            let mut text = Vec::new();
+            encode_buf(fragmented(data), &mut text);
+            assert_eq!(text, encode(data).as_bytes(), "len {len}");
+            let mut bytes = Vec::new();
+            decode_buf(fragmented(&text), &mut bytes).unwrap();
+            assert_eq!(bytes, data);
+        }
+        for bad in ["0", "ZZZ", "00a"] {
+            let err = decode_buf(fragmented(bad.as_bytes()), Vec::new()).unwrap_err();
+            assert_eq!(Err(err), decode(bad));
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Fixed-capacity `heapless` output ([`heapless`] feature).
+//!
+//! Neither direction touches the heap: the output lives in the returned
+//! container, and its capacity is checked up front.
+//!
+//! The crate itself still links `std` and needs a global allocator, so this is
+//! not a `no_std` build: it keeps the data off the heap in code that already
+//! has one, such as an interrupt handler or a fixed-size message queue.
+
+use heapless::{String, Vec};
+
+use crate::{Base44Error, BufferTooSmall, decode_into, encode_into, encoded_len};
+
+/// Byte-pair encode `input` into a `heapless::String` of capacity `CAP`.
+///
+/// If the encoding is longer than `CAP`, the error carries its length.
+///
+/// ```
+/// use qr_base44::{BufferTooSmall, encode_heapless};
+///
+/// let s = encode_heapless::<8>(b"hi").unwrap();
+/// assert_eq!(s.as_str(), "LZD");
+/// assert_eq!(encode_heapless::<4>(b"hi!"), Err(BufferTooSmall { needed: 5 }));
+/// ```
+pub fn encode_heapless<const CAP: usize>(input: &[u8]) -> Result<String<CAP>, BufferTooSmall> {
+    let needed = encoded_len(input.len());
+    let mut out = Vec::<u8, CAP>::new();
+    out.resize(needed, 0)
+        .map_err(|_| BufferTooSmall { needed })?;
+    encode_into(input, &mut out);
+    Ok(String::from_utf8(out).expect("alphabet is ASCII"))
+}
+
+/// Decode byte-pair text into a `heapless::Vec` of capacity `CAP`.
+///
+/// Input that would decode to more than `CAP` bytes is
+/// [`Base44Error::TooLarge`], checked before any character is read; other
+/// errors are those of [`decode`](crate::decode).
+///
+/// ```
+/// use qr_base44::{Base44Error, decode_heapless};
+///
+/// assert_eq!(decode_heapless::<2>("LZD").unwrap().as_slice(), b"hi");
+/// assert_eq!(decode_heapless::<1>("LZD"), Err(Base44Error::TooLarge));
+/// ```
+pub fn decode_heapless<const CAP: usize>(s: &str) -> Result<Vec<u8, CAP>, Base44Error> {
+    let len = s.len() / 3 * 2 + usize::from(s.len() % 3 == 2);
+    let mut out = Vec::<u8, CAP>::new();
+    out.resize(len, 0).map_err(|_| Base44Error::TooLarge)?;
+    decode_into(s.as_bytes(), &mut out)?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode, encode};
+
+    #[test]
+    fn matches_allocating_codec() {
+        let data: std::vec::Vec<u8> = (0..=40).rev().collect();
+        for len in 0..data.len() {
+            let s = encode_heapless::<64>(&data[..len]).unwrap();
+            assert_eq!(s.as_str(), encode(&data[..len]));
+            assert_eq!(decode_heapless::<40>(&s).unwrap(), decode(&s).unwrap()[..]);
+        }
+        assert_eq!(encode_heapless::<0>(b""), Ok(String::new()));
+        assert_eq!(
+            encode_heapless::<2>(b"hi"),
+            Err(BufferTooSmall { needed: 3 })
+        );
+        assert_eq!(decode_heapless::<8>("LZDL"), Err(Base44Error::Dangling));
+        assert_eq!(decode_heapless::<8>("ZZZ"), Err(Base44Error::Overflow));
+        assert_eq!(decode_heapless::<8>("lzd"), Err(Base44Error::InvalidChar));
+    }
+}
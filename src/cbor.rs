@@ -0,0 +1,163 @@
+//! Typed payloads: any serde type as CBOR, carried as Base44 text ([`cbor`] feature).
+
+use core::fmt;
+use core::str::FromStr;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::{Base44Error, decode, encode};
+
+/// Why parsing a [`Payload`] failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PayloadError {
+    /// The text is not valid Base44.
+    Decode(Base44Error),
+    /// The bytes are not a CBOR encoding of the target type, or are followed by
+    /// trailing data.
+    Cbor(String),
+}
+
+impl fmt::Display for PayloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PayloadError::Decode(err) => err.fmt(f),
+            PayloadError::Cbor(msg) => write!(f, "invalid CBOR payload: {msg}"),
+        }
+    }
+}
+
+impl core::error::Error for PayloadError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            PayloadError::Decode(err) => Some(err),
+            PayloadError::Cbor(_) => None,
+        }
+    }
+}
+
+impl From<Base44Error> for PayloadError {
+    fn from(err: Base44Error) -> Self {
+        PayloadError::Decode(err)
+    }
+}
+
+/// A value that displays as the byte-pair encoding of its CBOR serialization
+/// and parses back from it.
+///
+/// This lets typed structs go straight into clap arguments, config files and
+/// QR content through the standard `Display`/`FromStr` traits.
+///
+/// ```
+/// use qr_base44::Payload;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct Ticket {
+///     seat: u16,
+///     row: String,
+/// }
+///
+/// let ticket = Payload(Ticket { seat: 12, row: "F".into() });
+/// let s = ticket.to_string();
+/// let back: Payload<Ticket> = s.parse().unwrap();
+/// assert_eq!(back, ticket);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Payload<T>(pub T);
+
+impl<T> Payload<T> {
+    /// Unwrap the value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> core::ops::Deref for Payload<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> From<T> for Payload<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: Serialize> fmt::Display for Payload<T> {
+    /// Fails with [`fmt::Error`] only if `T`'s `Serialize` impl reports an error.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(&self.0, &mut bytes).map_err(|_| fmt::Error)?;
+        f.write_str(&encode(&bytes))
+    }
+}
+
+impl<T: DeserializeOwned> FromStr for Payload<T> {
+    type Err = PayloadError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = decode(s)?;
+        let mut reader = bytes.as_slice();
+        let value = ciborium::from_reader(&mut reader)
+            .map_err(|err| PayloadError::Cbor(err.to_string()))?;
+        if !reader.is_empty() {
+            return Err(PayloadError::Cbor(format!(
+                "{} trailing bytes",
+                reader.len()
+            )));
+        }
+        Ok(Self(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Config {
+        name: String,
+        retries: u8,
+        tags: Vec<String>,
+    }
+
+    #[test]
+    fn roundtrips_structs_and_primitives() {
+        let config = Payload(Config {
+            name: "edge".into(),
+            retries: 3,
+            tags: vec!["a".into(), "b".into()],
+        });
+        let s = config.to_string();
+        assert!(s.bytes().all(|b| crate::BASE44_ALPHABET.contains(&b)));
+        assert_eq!(s.parse::<Payload<Config>>().unwrap(), config);
+
+        let n: Payload<u64> = Payload(1_000_000).to_string().parse().unwrap();
+        assert_eq!(n.into_inner(), 1_000_000);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            "ZZZ".parse::<Payload<u8>>(),
+            Err(PayloadError::Decode(Base44Error::Overflow))
+        );
+        let text = Payload("hi").to_string();
+        assert!(matches!(
+            text.parse::<Payload<u32>>(),
+            Err(PayloadError::Cbor(_))
+        ));
+        // Two CBOR items: 1, 2.
+        let trailing = encode(&[0x01, 0x02]);
+        assert_eq!(
+            trailing.parse::<Payload<u8>>(),
+            Err(PayloadError::Cbor("1 trailing bytes".into()))
+        );
+    }
+}
@@ -0,0 +1,56 @@
+//! DEFLATE shared by the compressed formats (`compression` feature).
+//!
+//! [`QrPayload`](crate::QrPayload), [`PrefixScheme`](crate::PrefixScheme) and the
+//! sealed payloads compress at one level and inflate under one
+//! [`MAX_INFLATED_LEN`] cap; they differ only in whether the stream carries a
+//! zlib header.
+
+use miniz_oxide::inflate::TINFLStatus;
+
+use crate::{Base44Error, MAX_INFLATED_LEN};
+
+/// miniz_oxide's best (and slowest) level: codes are compressed once and
+/// scanned many times.
+const LEVEL: u8 = 10;
+
+/// How the DEFLATE stream is wrapped.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Framing {
+    /// Bare DEFLATE (RFC 1951).
+    Raw,
+    /// zlib header and Adler-32 trailer (RFC 1950).
+    Zlib,
+}
+
+/// Compress `data`.
+pub(crate) fn deflate(data: &[u8], framing: Framing) -> Vec<u8> {
+    match framing {
+        Framing::Raw => miniz_oxide::deflate::compress_to_vec(data, LEVEL),
+        Framing::Zlib => miniz_oxide::deflate::compress_to_vec_zlib(data, LEVEL),
+    }
+}
+
+/// Decompress `data`, or `None` if it is corrupt or inflates past
+/// [`MAX_INFLATED_LEN`].
+pub(crate) fn inflate(data: &[u8], framing: Framing) -> Option<Vec<u8>> {
+    inflate_within(data, framing, MAX_INFLATED_LEN).ok()
+}
+
+/// Decompress `data` into at most `limit` bytes.
+///
+/// A larger output is [`Base44Error::TooLarge`]; a stream that is corrupt or
+/// fails its Adler-32 trailer is [`Base44Error::ChecksumMismatch`].
+pub(crate) fn inflate_within(
+    data: &[u8],
+    framing: Framing,
+    limit: usize,
+) -> Result<Vec<u8>, Base44Error> {
+    match framing {
+        Framing::Raw => miniz_oxide::inflate::decompress_to_vec_with_limit(data, limit),
+        Framing::Zlib => miniz_oxide::inflate::decompress_to_vec_zlib_with_limit(data, limit),
+    }
+    .map_err(|err| match err.status {
+        TINFLStatus::HasMoreOutput => Base44Error::TooLarge,
+        _ => Base44Error::ChecksumMismatch,
+    })
+}
@@ -0,0 +1,154 @@
+//! Differential testing across the crate's encoding paths.
+//!
+//! [`check`] runs one input through every path that must agree: byte-pair and
+//! optimal round trips, the batched [`Base44Display`] formatter and the streaming
+//! [`EncoderWriter`]/[`DecoderWriter`] against one-shot [`encode`], the configurable [`Engine`](crate::Engine) against the free
+//! functions, and the sortable mode. [`run`] drives it over the
+//! [`corpus`](crate::corpus), and is meant to be called from downstream
+//! integration tests as well as this crate's own:
+//!
+//! ```
+//! let checked = qr_base44::conformance::run(200, 42).unwrap();
+//! assert!(checked >= 200);
+//! ```
+//!
+//! The crate has a single scalar backend, so there is no SIMD path to compare.
+
+use core::fmt;
+use std::io::Write;
+
+use crate::{
+    Base44Display, DecoderWriter, EncoderBuilder, EncoderWriter, Mode, chars_needed, corpus,
+    decode, decode_bits, decode_sortable, encode, encode_bits, encode_sortable, encoded_len,
+};
+
+/// A disagreement between two paths for one input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    /// Name of the failed check.
+    pub check: &'static str,
+    /// The input that triggered it.
+    pub input: Vec<u8>,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} failed for {} byte input {:02x?}",
+            self.check,
+            self.input.len(),
+            self.input
+        )
+    }
+}
+
+impl core::error::Error for Mismatch {}
+
+/// Cross-check every encoding path on `input`.
+pub fn check(input: &[u8]) -> Result<(), Mismatch> {
+    let fail = |check| {
+        Err(Mismatch {
+            check,
+            input: input.to_vec(),
+        })
+    };
+
+    let pair = encode(input);
+    if pair.len() != encoded_len(input.len()) || decode(&pair).as_deref() != Ok(input) {
+        return fail("byte-pair roundtrip");
+    }
+    if Base44Display(input).to_string() != pair {
+        return fail("display vs one-shot");
+    }
+    if stream(EncoderWriter::new(Vec::new()), input, EncoderWriter::finish).as_deref()
+        != Some(pair.as_bytes())
+        || stream(
+            DecoderWriter::new(Vec::new()),
+            pair.as_bytes(),
+            DecoderWriter::finish,
+        )
+        .as_deref()
+            != Some(input)
+    {
+        return fail("streaming vs one-shot");
+    }
+
+    let engine = EncoderBuilder::new().mode(Mode::Optimal).build();
+    let optimal = engine.encode(input);
+    if !input.is_empty() {
+        let bits = input.len() * 8;
+        if optimal != encode_bits(bits, input)
+            || optimal.len() != chars_needed(bits)
+            || decode_bits(bits, &optimal).as_deref() != Ok(input)
+        {
+            return fail("optimal roundtrip");
+        }
+    }
+    if optimal.len() > pair.len() || engine.decode(&optimal).as_deref() != Ok(input) {
+        return fail("optimal vs byte-pair");
+    }
+
+    let grouped = EncoderBuilder::new().group(4, '_').build();
+    let g = grouped.encode(input);
+    if g.replace('_', "") != pair || grouped.decode(&g).as_deref() != Ok(input) {
+        return fail("grouped engine");
+    }
+
+    let sortable = encode_sortable(input);
+    if sortable.len() != pair.len() || decode_sortable(&sortable).as_deref() != Ok(input) {
+        return fail("sortable roundtrip");
+    }
+    Ok(())
+}
+
+/// Feed `data` to `writer` in uneven chunks that split groups, then finish it.
+fn stream<W: Write>(
+    mut writer: W,
+    data: &[u8],
+    finish: impl FnOnce(W) -> std::io::Result<Vec<u8>>,
+) -> Option<Vec<u8>> {
+    let mut rest = data;
+    for size in [1, 2, 4, 7].into_iter().cycle() {
+        if rest.is_empty() {
+            break;
+        }
+        let (head, tail) = rest.split_at(size.min(rest.len()));
+        writer.write_all(head).ok()?;
+        rest = tail;
+    }
+    finish(writer).ok()
+}
+
+/// Run [`check`] over the [`corpus`](crate::corpus) for `seed`: its fixed edge
+/// cases followed by `cases` random inputs of up to 300 bytes. Returns the number
+/// of inputs checked.
+pub fn run(cases: usize, seed: u64) -> Result<usize, Mismatch> {
+    let corpus = corpus::generate(seed, cases);
+    for case in &corpus {
+        check(&case.bytes)?;
+    }
+    Ok(corpus.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_paths_agree() {
+        assert_eq!(run(500, 0x5eed).unwrap(), 311 + 500);
+    }
+
+    #[test]
+    fn mismatch_reports_check_and_input() {
+        let m = Mismatch {
+            check: "byte-pair roundtrip",
+            input: vec![0xAB],
+        };
+        assert_eq!(
+            m.to_string(),
+            "byte-pair roundtrip failed for 1 byte input [ab]"
+        );
+    }
+}
@@ -0,0 +1,208 @@
+//! HC1-style prefixed containers: `<prefix><Base44(zlib(payload))>`.
+//!
+//! EU Digital COVID Certificates are `HC1:` followed by Base45 text of a
+//! zlib-compressed COSE_Sign1 structure. [`PrefixScheme`] carries the same
+//! layering in Base44 under a prefix of your choosing (`XX1:`), so an
+//! organisation can define its own scheme and version it through the prefix.
+//! The payload is opaque here: sign and parse the COSE/CBOR layers with the
+//! library of your choice, and hand the resulting bytes to the scheme.
+//!
+//! As in DCC decoders, compression is detected from the zlib header rather than
+//! flagged: a COSE_Sign1 structure starts with `0xD2` (tag 18) or `0x84` (array
+//! of 4), never with the zlib `0x78`, so uncompressed payloads stay readable.
+
+use core::fmt;
+
+#[cfg(feature = "compression")]
+use crate::compress::{self, Framing};
+use crate::{Base44Error, decode, encode};
+
+/// First byte of a zlib stream with a 32 KiB window, as written by every
+/// mainstream deflater.
+const ZLIB_HEADER: u8 = 0x78;
+
+/// Why [`PrefixScheme::decode`] failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ContainerError {
+    /// The text does not start with the scheme's prefix.
+    WrongPrefix,
+    /// The text after the prefix is not valid Base44.
+    Decode(Base44Error),
+    /// The payload is zlib-compressed, or when encoding starts like a zlib
+    /// stream, but the `compression` feature is disabled.
+    CompressionUnsupported,
+    /// The zlib stream is corrupt or inflates past [`MAX_INFLATED_LEN`](crate::MAX_INFLATED_LEN).
+    Decompress,
+}
+
+impl fmt::Display for ContainerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContainerError::WrongPrefix => f.write_str("missing or unexpected scheme prefix"),
+            ContainerError::Decode(err) => err.fmt(f),
+            ContainerError::CompressionUnsupported => {
+                f.write_str("payload is compressed but the `compression` feature is disabled")
+            }
+            ContainerError::Decompress => f.write_str("compressed payload is corrupt or too large"),
+        }
+    }
+}
+
+impl core::error::Error for ContainerError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            ContainerError::Decode(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<Base44Error> for ContainerError {
+    fn from(err: Base44Error) -> Self {
+        ContainerError::Decode(err)
+    }
+}
+
+/// A prefixed container scheme such as `XX1:`.
+///
+/// ```
+/// use qr_base44::{ContainerError, PrefixScheme};
+///
+/// const TICKETS: PrefixScheme = PrefixScheme::new("TK1:");
+///
+/// let cose = [0xD2, 0x84, 0x43, 0xA1, 0x01, 0x26]; // start of a COSE_Sign1
+/// let code = TICKETS.encode(&cose);
+/// assert!(code.starts_with("TK1:"));
+/// assert_eq!(TICKETS.decode(&code).unwrap(), cose);
+/// assert_eq!(PrefixScheme::new("TK2:").decode(&code), Err(ContainerError::WrongPrefix));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrefixScheme {
+    prefix: &'static str,
+}
+
+impl PrefixScheme {
+    /// A scheme whose codes start with `prefix`.
+    pub const fn new(prefix: &'static str) -> Self {
+        Self { prefix }
+    }
+
+    /// The prefix.
+    pub const fn prefix(&self) -> &'static str {
+        self.prefix
+    }
+
+    /// Prefix and encode `payload`, zlib-compressing it first when the
+    /// `compression` feature is enabled and that makes the code shorter.
+    ///
+    /// # Panics
+    ///
+    /// Without the `compression` feature, panics if `payload` starts with
+    /// `0x78`: it could not be told apart from a zlib stream when decoding.
+    /// COSE payloads never do. Use [`try_encode`](Self::try_encode) to get an
+    /// error instead.
+    pub fn encode(&self, payload: &[u8]) -> String {
+        match self.try_encode(payload) {
+            Ok(code) => code,
+            Err(_) => panic!("payload starting with 0x78 needs the `compression` feature"),
+        }
+    }
+
+    /// [`encode`](Self::encode) that returns
+    /// [`ContainerError::CompressionUnsupported`] instead of panicking.
+    pub fn try_encode(&self, payload: &[u8]) -> Result<String, ContainerError> {
+        let deflated = deflate(payload);
+        if deflated.is_none() && payload.first() == Some(&ZLIB_HEADER) {
+            return Err(ContainerError::CompressionUnsupported);
+        }
+        let body = encode(deflated.as_deref().unwrap_or(payload));
+        let mut out = String::with_capacity(self.prefix.len() + body.len());
+        out.push_str(self.prefix);
+        out.push_str(&body);
+        Ok(out)
+    }
+
+    /// Check the prefix, decode, and inflate the payload if it is a zlib
+    /// stream.
+    pub fn decode(&self, s: &str) -> Result<Vec<u8>, ContainerError> {
+        let body = s
+            .strip_prefix(self.prefix)
+            .ok_or(ContainerError::WrongPrefix)?;
+        let bytes = decode(body)?;
+        match bytes.first() {
+            Some(&ZLIB_HEADER) => inflate(&bytes),
+            _ => Ok(bytes),
+        }
+    }
+}
+
+#[cfg(feature = "compression")]
+fn deflate(payload: &[u8]) -> Option<Vec<u8>> {
+    let deflated = compress::deflate(payload, Framing::Zlib);
+    // Kept uncompressed, a payload starting with the zlib header byte would be
+    // misread, so it is always compressed.
+    (deflated.len() < payload.len() || payload.first() == Some(&ZLIB_HEADER)).then_some(deflated)
+}
+
+#[cfg(not(feature = "compression"))]
+fn deflate(_: &[u8]) -> Option<Vec<u8>> {
+    None
+}
+
+#[cfg(feature = "compression")]
+fn inflate(bytes: &[u8]) -> Result<Vec<u8>, ContainerError> {
+    compress::inflate(bytes, Framing::Zlib).ok_or(ContainerError::Decompress)
+}
+
+#[cfg(not(feature = "compression"))]
+fn inflate(_: &[u8]) -> Result<Vec<u8>, ContainerError> {
+    Err(ContainerError::CompressionUnsupported)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEME: PrefixScheme = PrefixScheme::new("XX1:");
+
+    #[test]
+    fn prefix_and_roundtrip() {
+        for payload in [&b""[..], &[0xD2, 0x84], &[0x84; 300]] {
+            let code = SCHEME.encode(payload);
+            assert!(code.starts_with("XX1:"));
+            assert_eq!(SCHEME.decode(&code).unwrap(), payload);
+        }
+        assert_eq!(SCHEME.prefix(), "XX1:");
+        assert_eq!(SCHEME.decode("XX1"), Err(ContainerError::WrongPrefix));
+        assert_eq!(
+            SCHEME.decode("XX1:ZZZ"),
+            Err(ContainerError::Decode(Base44Error::Overflow))
+        );
+    }
+
+    #[cfg(not(feature = "compression"))]
+    #[test]
+    fn zlib_lookalikes_need_compression() {
+        assert_eq!(
+            SCHEME.try_encode(&[ZLIB_HEADER, 0x01]),
+            Err(ContainerError::CompressionUnsupported)
+        );
+        assert!(std::panic::catch_unwind(|| SCHEME.encode(&[ZLIB_HEADER])).is_err());
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn compresses_when_it_helps() {
+        let payload = [0x84; 300];
+        let code = SCHEME.encode(&payload);
+        assert!(code.len() < SCHEME.prefix().len() + crate::encoded_len(payload.len()));
+        // Payloads that look like zlib are always wrapped, so they survive.
+        assert_eq!(
+            SCHEME.decode(&SCHEME.encode(&[ZLIB_HEADER])).unwrap(),
+            [ZLIB_HEADER]
+        );
+        let corrupt = format!("XX1:{}", encode(&[ZLIB_HEADER, 0x9C, 0xFF]));
+        assert_eq!(SCHEME.decode(&corrupt), Err(ContainerError::Decompress));
+    }
+}
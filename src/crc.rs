@@ -0,0 +1,128 @@
+//! CRC-32 integrity envelope.
+//!
+//! Layout: a [varint](crate::varint) payload length, then the byte-pair encoding
+//! of `payload || crc32(payload)` with the CRC big-endian. The CRC is the common
+//! IEEE 802.3 polynomial (as in zlib, PNG and Ethernet), so the checksum can be
+//! verified with any standard implementation.
+
+use crate::varint;
+use crate::{Base44Error, decode, encode};
+
+/// Reflected IEEE 802.3 polynomial.
+const POLY: u32 = 0xEDB8_8320;
+
+const TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ POLY
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// CRC-32/ISO-HDLC of `data`.
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, &b| {
+        TABLE[((crc ^ b as u32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+/// Wrap `payload` in a length-prefixed, CRC-32-checked envelope.
+///
+/// Adds 7 characters of overhead (one length digit and the checksum) for
+/// payloads under 22 bytes, growing by one character per base-22 length digit.
+/// Use [`open_crc32`] to verify and unwrap.
+///
+/// ```
+/// use qr_base44::{Base44Error, open_crc32, seal_crc32};
+///
+/// let sealed = seal_crc32(b"hello");
+/// assert_eq!(open_crc32(&sealed).unwrap(), b"hello");
+///
+/// let mut tampered = sealed.into_bytes();
+/// tampered[3] = if tampered[3] == b'0' { b'1' } else { b'0' };
+/// assert!(open_crc32(core::str::from_utf8(&tampered).unwrap()).is_err());
+/// ```
+pub fn seal_crc32(payload: &[u8]) -> String {
+    let mut body = Vec::with_capacity(payload.len() + 4);
+    body.extend_from_slice(payload);
+    body.extend_from_slice(&crc32(payload).to_be_bytes());
+    let mut out = String::new();
+    varint::encode_into(payload.len() as u64, &mut out);
+    out.push_str(&encode(&body));
+    out
+}
+
+/// Verify and unwrap an envelope produced by [`seal_crc32`].
+///
+/// Returns [`Base44Error::InvalidLength`] if the length prefix disagrees with the
+/// body and [`Base44Error::ChecksumMismatch`] if the CRC does not match.
+pub fn open_crc32(s: &str) -> Result<Vec<u8>, Base44Error> {
+    let (len, used) = varint::decode(s)?;
+    let mut body = decode(&s[used..])?;
+    if body.len() < 4 || (body.len() - 4) as u64 != len {
+        return Err(Base44Error::InvalidLength);
+    }
+    let crc = body.split_off(body.len() - 4);
+    if crc != crc32(&body).to_be_bytes() {
+        return Err(Base44Error::ChecksumMismatch);
+    }
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b""), 0);
+    }
+
+    #[test]
+    fn seal_open_roundtrip_and_overhead() {
+        for len in [0usize, 1, 21, 22, 500] {
+            let payload: Vec<u8> = (0..len).map(|i| i as u8).collect();
+            let sealed = seal_crc32(&payload);
+            assert_eq!(open_crc32(&sealed).unwrap(), payload);
+            let digits = if len < 22 {
+                1
+            } else if len < 484 {
+                2
+            } else {
+                3
+            };
+            assert_eq!(sealed.len(), digits + crate::encoded_len(len + 4));
+        }
+    }
+
+    #[test]
+    fn corruption_is_detected() {
+        let sealed = seal_crc32(b"payload");
+        // Wrong length prefix.
+        assert_eq!(
+            open_crc32(&format!("8{}", &sealed[1..])),
+            Err(Base44Error::InvalidLength)
+        );
+        // Flipped payload byte with a consistent length.
+        let mut body = decode(&sealed[1..]).unwrap();
+        body[0] ^= 1;
+        assert_eq!(
+            open_crc32(&format!("{}{}", &sealed[..1], encode(&body))),
+            Err(Base44Error::ChecksumMismatch)
+        );
+        assert_eq!(open_crc32(""), Err(Base44Error::Dangling));
+    }
+}
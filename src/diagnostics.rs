@@ -0,0 +1,126 @@
+//! Locating errors in user input for display.
+
+use core::fmt;
+
+use crate::{alphabet, b44_val};
+
+/// Where the first character outside the alphabet occurs in a string.
+///
+/// [`Base44Error::InvalidChar`](crate::Base44Error::InvalidChar) is a plain
+/// variant so that decoding stays allocation- and bookkeeping-free; call
+/// [`find_invalid_char`] after a failure to get a position a UI can use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct InvalidCharAt {
+    /// Index in `char`s, for placing a caret in a text field.
+    pub index: usize,
+    /// Byte offset into the UTF-8 string, for slicing.
+    pub byte_offset: usize,
+    /// The offending character.
+    pub ch: char,
+}
+
+impl fmt::Display for InvalidCharAt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid base44 character {:?} at index {}",
+            self.ch, self.index
+        )
+    }
+}
+
+impl core::error::Error for InvalidCharAt {}
+
+/// Find the first character of `s` outside the Base44 alphabet.
+///
+/// Positions count characters, not bytes, so multi-byte input such as emoji or
+/// full-width forms is reported where the user sees it:
+///
+/// ```
+/// use qr_base44::find_invalid_char;
+///
+/// let at = find_invalid_char("😀LZD!").unwrap();
+/// assert_eq!((at.index, at.byte_offset, at.ch), (0, 0, '😀'));
+///
+/// let at = find_invalid_char("LZ😀D!").unwrap();
+/// assert_eq!((at.index, at.byte_offset), (2, 2));
+/// assert_eq!(at.to_string(), "invalid base44 character '😀' at index 2");
+///
+/// assert_eq!(find_invalid_char("LZD"), None);
+/// ```
+pub fn find_invalid_char(s: &str) -> Option<InvalidCharAt> {
+    invalid_chars(s).next()
+}
+
+/// Every character of `s` outside the Base44 alphabet, in order, so a form can
+/// flag them all at once instead of one per submit.
+///
+/// Valid text is confirmed by a single table pass; positions are only worked
+/// out when something is wrong.
+///
+/// ```
+/// use qr_base44::find_invalid_chars;
+///
+/// let bad = find_invalid_chars("lZD 1");
+/// let found: Vec<_> = bad.iter().map(|at| (at.index, at.ch)).collect();
+/// assert_eq!(found, [(0, 'l'), (3, ' ')]);
+/// assert!(find_invalid_chars("LZD").is_empty());
+/// ```
+pub fn find_invalid_chars(s: &str) -> Vec<InvalidCharAt> {
+    match alphabet::first_invalid(s.as_bytes()) {
+        None => Vec::new(),
+        Some(_) => invalid_chars(s).collect(),
+    }
+}
+
+fn invalid_chars(s: &str) -> impl Iterator<Item = InvalidCharAt> + '_ {
+    s.char_indices()
+        .enumerate()
+        .filter(|(_, (_, ch))| !ch.is_ascii() || b44_val(*ch as u8).is_none())
+        .map(|(index, (byte_offset, ch))| InvalidCharAt {
+            index,
+            byte_offset,
+            ch,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Base44Error, decode};
+
+    #[test]
+    fn agrees_with_decode() {
+        for s in [
+            "LZD",
+            "",
+            "0",
+            "ZZZ",
+            "é00",
+            "00\u{FF10}",
+            "000100a",
+            "LZ\u{1F600}",
+        ] {
+            let invalid = find_invalid_char(s);
+            assert_eq!(
+                invalid.is_some(),
+                decode(s) == Err(Base44Error::InvalidChar),
+                "{s:?}"
+            );
+            if let Some(at) = invalid {
+                assert_eq!(s[at.byte_offset..].chars().next(), Some(at.ch));
+                assert_eq!(s.chars().nth(at.index), Some(at.ch));
+            }
+            assert_eq!(find_invalid_chars(s).first(), invalid.as_ref(), "{s:?}");
+        }
+    }
+
+    #[test]
+    fn reports_every_position() {
+        let s = "é0a0\u{FF10}0!";
+        let all = find_invalid_chars(s);
+        let found: Vec<_> = all.iter().map(|at| (at.index, at.byte_offset)).collect();
+        assert_eq!(found, [(0, 0), (2, 3), (4, 5), (6, 9)]);
+    }
+}
@@ -0,0 +1,38 @@
+//! [`Base44Display`]: lazy byte-pair encoding through `fmt::Display`.
+
+use core::fmt;
+
+use crate::sink::{FmtSink, encode_to_sink};
+
+/// Formats the wrapped bytes as [`encode`](crate::encode) would, writing straight
+/// into the formatter without building an intermediate `String`.
+///
+/// ```
+/// use qr_base44::Base44Display;
+///
+/// let bytes = [0xFF, 0xFF, 0x41];
+/// assert_eq!(format!("code={}", Base44Display(&bytes)), "code=J%XL1");
+/// ```
+///
+/// Width, fill and alignment flags are ignored.
+#[derive(Debug, Clone, Copy)]
+pub struct Base44Display<'a>(pub &'a [u8]);
+
+impl fmt::Display for Base44Display<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        encode_to_sink(self.0, &mut FmtSink(f))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_encode_across_batch_boundaries() {
+        for len in [0, 1, 2, 63, 64, 65, 128, 129, 1000] {
+            let data: Vec<u8> = (0..len).map(|i| (i * 7 + 3) as u8).collect();
+            assert_eq!(Base44Display(&data).to_string(), crate::encode(&data));
+        }
+    }
+}
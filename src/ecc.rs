@@ -0,0 +1,521 @@
+//! Error-correcting codes: Reed–Solomon parity interleaved through the text.
+//!
+//! [`encode_ecc`] splits the input round-robin across Reed–Solomon codewords
+//! over GF(2^8), each carrying [`EccOptions::new`]`(parity)` parity bytes, and
+//! byte-pair encodes them interleaved: byte `j` of every codeword, then byte
+//! `j + 1`, and so on. Within a codeword the parity bytes sit at even intervals
+//! rather than at the end, so parity characters are spread through the whole
+//! string.
+//!
+//! A codeword corrects up to `parity / 2` wrong bytes, and a wrong character
+//! spoils at most the two bytes of its group. Interleaving across
+//! [`EccOptions::interleave`]`(depth)` codewords or more spreads a burst of
+//! neighbouring corrupt characters over all of them, so a smudge in the middle
+//! of a hand-copied code costs each codeword only a byte or two.
+//!
+//! ```
+//! use qr_base44::{EccOptions, decode_ecc, encode_ecc};
+//!
+//! let opts = EccOptions::new(4).interleave(4);
+//! let code = encode_ecc(b"serial 0042-ZX", opts);
+//!
+//! // Four neighbouring characters mistyped.
+//! let mut typo = code.clone().into_bytes();
+//! typo[6..10].copy_from_slice(b"0000");
+//! let typo = String::from_utf8(typo).unwrap();
+//! assert_eq!(decode_ecc(&typo, opts).unwrap(), b"serial 0042-ZX");
+//! ```
+
+use core::fmt;
+
+use crate::{Base44Error, alphabet, encode};
+
+/// Longest Reed–Solomon codeword over GF(2^8).
+const MAX_CODEWORD: usize = 255;
+
+/// `exp[i] = α^i` for the field GF(2^8) mod x^8 + x^4 + x^3 + x^2 + 1,
+/// doubled so products of two logarithms need no reduction.
+const EXP: [u8; 512] = {
+    let mut exp = [0u8; 512];
+    let mut x: u16 = 1;
+    let mut i = 0;
+    while i < 512 {
+        exp[i] = x as u8;
+        x <<= 1;
+        if x & 0x100 != 0 {
+            x ^= 0x11D;
+        }
+        i += 1;
+    }
+    exp
+};
+
+/// `log[α^i] = i`; `log[0]` is unused.
+const LOG: [u8; 256] = {
+    let mut log = [0u8; 256];
+    let mut i = 0;
+    while i < 255 {
+        log[EXP[i] as usize] = i as u8;
+        i += 1;
+    }
+    log
+};
+
+fn mul(a: u8, b: u8) -> u8 {
+    match (a, b) {
+        (0, _) | (_, 0) => 0,
+        _ => EXP[LOG[a as usize] as usize + LOG[b as usize] as usize],
+    }
+}
+
+fn div(a: u8, b: u8) -> u8 {
+    debug_assert_ne!(b, 0);
+    match a {
+        0 => 0,
+        _ => EXP[LOG[a as usize] as usize + 255 - LOG[b as usize] as usize],
+    }
+}
+
+/// Evaluate a polynomial stored lowest degree first.
+fn eval(poly: &[u8], x: u8) -> u8 {
+    poly.iter().rev().fold(0, |acc, &c| mul(acc, x) ^ c)
+}
+
+/// Why [`decode_ecc`] failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EccError {
+    /// The text has a length no [`encode_ecc`] output with these options has.
+    Decode(Base44Error),
+    /// This codeword has more errors than its parity can correct.
+    Uncorrectable {
+        /// Index of the codeword.
+        codeword: usize,
+    },
+}
+
+impl fmt::Display for EccError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EccError::Decode(err) => err.fmt(f),
+            EccError::Uncorrectable { codeword } => {
+                write!(f, "too many errors to correct in codeword {codeword}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for EccError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            EccError::Decode(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<Base44Error> for EccError {
+    fn from(err: Base44Error) -> Self {
+        EccError::Decode(err)
+    }
+}
+
+/// Parity and interleave depth for [`encode_ecc`] and [`decode_ecc`].
+///
+/// Both sides must use the same options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EccOptions {
+    parity: usize,
+    depth: usize,
+}
+
+impl EccOptions {
+    /// `parity` Reed–Solomon bytes per codeword, correcting `parity / 2` byte
+    /// errors in each; interleave depth 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `parity` is 0 or over 254.
+    pub const fn new(parity: u8) -> Self {
+        assert!(
+            parity >= 1 && (parity as usize) < MAX_CODEWORD,
+            "ECC parity must be 1..=254 bytes"
+        );
+        Self {
+            parity: parity as usize,
+            depth: 1,
+        }
+    }
+
+    /// Spread the input over at least `depth` codewords. More codewords cost
+    /// more parity but tolerate longer bursts: a run of corrupt characters
+    /// decoding to `b` wrong bytes touches each codeword about `b / depth`
+    /// times.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `depth` is 0.
+    pub const fn interleave(mut self, depth: usize) -> Self {
+        assert!(depth >= 1, "ECC interleave depth must be at least 1");
+        self.depth = depth;
+        self
+    }
+
+    /// Parity bytes per codeword.
+    pub const fn parity(&self) -> usize {
+        self.parity
+    }
+
+    /// Minimum number of interleaved codewords.
+    pub const fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Codewords used for `len` input bytes: at least the depth, and enough
+    /// to keep every codeword within 255 bytes, but never an empty one.
+    fn codewords(&self, len: usize) -> usize {
+        let needed = len.div_ceil(MAX_CODEWORD - self.parity);
+        self.depth.max(needed).min(len)
+    }
+
+    /// Bytes before byte-pair encoding for `len` input bytes.
+    fn total_len(&self, len: usize) -> usize {
+        len + self.codewords(len) * self.parity
+    }
+}
+
+/// Transmission slots of the parity bytes in a codeword of `n` bytes: every
+/// `n / (parity + 1)` or so.
+fn is_parity_slot(n: usize, parity: usize) -> impl Fn(usize) -> bool {
+    let slots: Vec<usize> = (1..=parity).map(|j| j * n / (parity + 1)).collect();
+    move |pos| slots.binary_search(&pos).is_ok()
+}
+
+/// The codewords of `len` input bytes as `(data_len, codeword_len)` pairs.
+fn shape(len: usize, opts: EccOptions) -> impl Iterator<Item = (usize, usize)> {
+    let words = opts.codewords(len);
+    (0..words).map(move |k| {
+        let data = len / words + usize::from(k < len % words);
+        (data, data + opts.parity)
+    })
+}
+
+/// Byte-pair encode `input` with interleaved Reed–Solomon parity.
+///
+/// The output is [`encoded_len`](crate::encoded_len) of the input plus
+/// `parity` bytes per codeword.
+pub fn encode_ecc(input: &[u8], opts: EccOptions) -> String {
+    let generator = generator(opts.parity);
+    let words = opts.codewords(input.len());
+    let codewords: Vec<Vec<u8>> = shape(input.len(), opts)
+        .enumerate()
+        .map(|(k, (_, n))| {
+            let data: Vec<u8> = input.iter().skip(k).step_by(words).copied().collect();
+            let parity = remainder(&data, &generator);
+            let is_parity = is_parity_slot(n, opts.parity);
+            let (mut d, mut p) = (data.iter(), parity.iter());
+            (0..n)
+                .map(|pos| *if is_parity(pos) { p.next() } else { d.next() }.expect("slot count"))
+                .collect()
+        })
+        .collect();
+    encode(&interleave(&codewords))
+}
+
+/// Decode [`encode_ecc`] text, correcting errors.
+///
+/// Characters outside the alphabet and groups that overflow are treated as
+/// wrong bytes and corrected like any other error. The length must be intact:
+/// a dropped or inserted character is [`Base44Error::InvalidLength`].
+pub fn decode_ecc(s: &str, opts: EccOptions) -> Result<Vec<u8>, EccError> {
+    let bytes = lenient_decode(s.as_bytes())?;
+    let total = bytes.len();
+    // total_len is strictly increasing, so at most one input length fits.
+    let len = (0..=total)
+        .find(|&len| opts.total_len(len) >= total)
+        .filter(|&len| opts.total_len(len) == total)
+        .ok_or(Base44Error::InvalidLength)?;
+    let words = opts.codewords(len);
+    let shapes: Vec<(usize, usize)> = shape(len, opts).collect();
+    let mut codewords: Vec<Vec<u8>> = shapes.iter().map(|&(_, n)| Vec::with_capacity(n)).collect();
+    let mut source = bytes.iter();
+    for pos in 0..shapes.first().map_or(0, |&(_, n)| n) {
+        for (k, &(_, n)) in shapes.iter().enumerate() {
+            if pos < n {
+                codewords[k].push(*source.next().expect("total length checked"));
+            }
+        }
+    }
+    let mut out = vec![0; len];
+    for (k, (received, (_, n))) in codewords.into_iter().zip(shapes).enumerate() {
+        let is_parity = is_parity_slot(n, opts.parity);
+        // Back to systematic order: data, then parity.
+        let (data, parity): (Vec<_>, Vec<_>) = received
+            .iter()
+            .enumerate()
+            .partition(|&(pos, _)| !is_parity(pos));
+        let mut word: Vec<u8> = data.iter().chain(&parity).map(|&(_, &b)| b).collect();
+        correct(&mut word, opts.parity).ok_or(EccError::Uncorrectable { codeword: k })?;
+        for (i, &b) in word[..n - opts.parity].iter().enumerate() {
+            out[k + i * words] = b;
+        }
+    }
+    Ok(out)
+}
+
+/// Byte-pair decode without failing on bad groups: their bytes become zero,
+/// for the Reed–Solomon decoder to repair.
+fn lenient_decode(s: &[u8]) -> Result<Vec<u8>, Base44Error> {
+    if s.len() % 3 == 1 {
+        return Err(Base44Error::InvalidLength);
+    }
+    let digit = |b: &u8| match alphabet::DECODE_TABLE[*b as usize] {
+        alphabet::INVALID => None,
+        d => Some(u32::from(d)),
+    };
+    let value = |group: &[u8]| {
+        group
+            .iter()
+            .rev()
+            .try_fold(0u32, |acc, b| Some(acc * 44 + digit(b)?))
+    };
+    let mut out = Vec::with_capacity(s.len() / 3 * 2 + 1);
+    for group in s.chunks(3) {
+        match group.len() {
+            3 => out.extend(
+                value(group)
+                    .and_then(|x| u16::try_from(x).ok())
+                    .unwrap_or(0)
+                    .to_be_bytes(),
+            ),
+            _ => out.push(value(group).and_then(|x| u8::try_from(x).ok()).unwrap_or(0)),
+        }
+    }
+    Ok(out)
+}
+
+/// Byte `j` of every codeword, for each `j` in turn.
+fn interleave(codewords: &[Vec<u8>]) -> Vec<u8> {
+    let longest = codewords.first().map_or(0, Vec::len);
+    (0..longest)
+        .flat_map(|pos| {
+            codewords
+                .iter()
+                .filter_map(move |word| word.get(pos).copied())
+        })
+        .collect()
+}
+
+/// `∏ (x - α^i)` for `i` in `0..parity`, highest degree first.
+fn generator(parity: usize) -> Vec<u8> {
+    let mut g = vec![1u8];
+    for &root in &EXP[..parity] {
+        let mut next = vec![0u8; g.len() + 1];
+        for (j, &c) in g.iter().enumerate() {
+            next[j] ^= c;
+            next[j + 1] ^= mul(c, root);
+        }
+        g = next;
+    }
+    g
+}
+
+/// Parity bytes for `data`: the remainder of `data · x^parity` divided by the
+/// generator, highest degree first.
+fn remainder(data: &[u8], generator: &[u8]) -> Vec<u8> {
+    let parity = generator.len() - 1;
+    let mut rem = vec![0u8; parity];
+    for &b in data {
+        let coef = b ^ rem[0];
+        rem.rotate_left(1);
+        rem[parity - 1] = 0;
+        for (r, &g) in rem.iter_mut().zip(&generator[1..]) {
+            *r ^= mul(g, coef);
+        }
+    }
+    rem
+}
+
+/// Correct `word` (data then parity, highest degree first) in place, or return
+/// `None` if it has more errors than `parity / 2`.
+fn correct(word: &mut [u8], parity: usize) -> Option<()> {
+    let n = word.len();
+    let syndromes = |word: &[u8]| -> Vec<u8> {
+        (0..parity)
+            .map(|i| word.iter().fold(0, |acc, &c| mul(acc, EXP[i]) ^ c))
+            .collect()
+    };
+    let s = syndromes(word);
+    if s.iter().all(|&x| x == 0) {
+        return Some(());
+    }
+
+    // Berlekamp–Massey: the error locator Λ(x), lowest degree first.
+    let (mut lambda, mut prev) = (vec![1u8], vec![1u8]);
+    let (mut errors, mut shift, mut prev_d) = (0usize, 1usize, 1u8);
+    for step in 0..parity {
+        let d = (1..=errors).fold(s[step], |d, i| {
+            d ^ mul(lambda.get(i).copied().unwrap_or(0), s[step - i])
+        });
+        if d == 0 {
+            shift += 1;
+            continue;
+        }
+        let coef = div(d, prev_d);
+        let mut next = lambda.clone();
+        next.resize(next.len().max(prev.len() + shift), 0);
+        for (i, &b) in prev.iter().enumerate() {
+            next[i + shift] ^= mul(coef, b);
+        }
+        if 2 * errors <= step {
+            errors = step + 1 - errors;
+            prev = core::mem::replace(&mut lambda, next);
+            prev_d = d;
+            shift = 1;
+        } else {
+            lambda = next;
+            shift += 1;
+        }
+    }
+    if 2 * errors > parity {
+        return None;
+    }
+
+    // Chien search: byte `pos` is the coefficient of x^(n-1-pos), so its
+    // locator is X = α^(n-1-pos) and Λ(X⁻¹) = 0 there.
+    let positions: Vec<usize> = (0..n)
+        .filter(|&pos| eval(&lambda, EXP[(255 - (n - 1 - pos) % 255) % 255]) == 0)
+        .collect();
+    if positions.len() != errors {
+        return None;
+    }
+
+    // Forney: Ω(x) = S(x)Λ(x) mod x^parity, e = X·Ω(X⁻¹) / Λ'(X⁻¹).
+    let mut omega = vec![0u8; parity];
+    for (i, &si) in s.iter().enumerate() {
+        for (j, &lj) in lambda.iter().enumerate().take(parity - i) {
+            omega[i + j] ^= mul(si, lj);
+        }
+    }
+    let derivative: Vec<u8> = lambda
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, &c)| if i % 2 == 1 { c } else { 0 })
+        .collect();
+    for pos in positions {
+        let x = EXP[(n - 1 - pos) % 255];
+        let x_inv = EXP[(255 - (n - 1 - pos) % 255) % 255];
+        let denom = eval(&derivative, x_inv);
+        if denom == 0 {
+            return None;
+        }
+        word[pos] ^= mul(x, div(eval(&omega, x_inv), denom));
+    }
+    syndromes(word).iter().all(|&x| x == 0).then_some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 97 + 13) as u8).collect()
+    }
+
+    #[test]
+    fn codewords_correct_up_to_half_the_parity() {
+        let generator = generator(8);
+        let msg = data(40);
+        let mut word = msg.clone();
+        word.extend(remainder(&msg, &generator));
+        let clean = word.clone();
+        for errors in 0..=4 {
+            let mut bad = clean.clone();
+            for e in 0..errors {
+                bad[e * 11] ^= 0x5A + e as u8;
+            }
+            assert_eq!(correct(&mut bad, 8), Some(()), "{errors} errors");
+            assert_eq!(bad, clean);
+        }
+        let mut bad = clean.clone();
+        for e in 0..5 {
+            bad[e * 9] ^= 0xA5;
+        }
+        assert!(correct(&mut bad, 8).is_none() || bad != clean);
+    }
+
+    #[test]
+    fn roundtrips_every_shape() {
+        for opts in [
+            EccOptions::new(1),
+            EccOptions::new(2).interleave(3),
+            EccOptions::new(10).interleave(8),
+            EccOptions::new(254),
+        ] {
+            for len in [0, 1, 2, 7, 64, 245, 246, 600] {
+                let input = data(len);
+                let code = encode_ecc(&input, opts);
+                assert_eq!(code.len(), crate::encoded_len(opts.total_len(len)));
+                assert_eq!(decode_ecc(&code, opts).unwrap(), input, "{opts:?} {len}");
+            }
+        }
+    }
+
+    #[test]
+    fn corrects_bursts_and_garbage() {
+        let opts = EccOptions::new(4).interleave(8);
+        let input = data(100);
+        let code = encode_ecc(&input, opts).into_bytes();
+        // A burst of 8 characters (up to 8 bytes) hits each codeword at most twice.
+        for start in (0..code.len() - 8).step_by(5) {
+            let mut bad = code.clone();
+            bad[start..start + 8].fill(b':');
+            let bad = String::from_utf8(bad).unwrap();
+            assert_eq!(decode_ecc(&bad, opts).unwrap(), input, "burst at {start}");
+        }
+        let mut bad = code.clone();
+        bad[3..6].copy_from_slice(b"a b");
+        assert_eq!(
+            decode_ecc(core::str::from_utf8(&bad).unwrap(), opts).unwrap(),
+            input
+        );
+
+        let mut wrecked = code.clone();
+        wrecked[..60].fill(b'0');
+        assert!(matches!(
+            decode_ecc(core::str::from_utf8(&wrecked).unwrap(), opts),
+            Err(EccError::Uncorrectable { .. })
+        ));
+    }
+
+    #[test]
+    fn parity_is_spread_through_the_string() {
+        let n = 30;
+        let is_parity = is_parity_slot(n, 4);
+        let slots: Vec<usize> = (0..n).filter(|&pos| is_parity(pos)).collect();
+        assert_eq!(slots, [6, 12, 18, 24]);
+    }
+
+    #[test]
+    fn rejects_wrong_lengths() {
+        // Two codewords of 5 bytes; no input length gives 12 bytes.
+        let opts = EccOptions::new(4).interleave(3);
+        let code = encode_ecc(b"ab", opts);
+        assert_eq!(
+            decode_ecc(&code[..code.len() - 1], opts),
+            Err(EccError::Decode(Base44Error::InvalidLength))
+        );
+        assert_eq!(
+            decode_ecc(&format!("{code}000"), opts),
+            Err(EccError::Decode(Base44Error::InvalidLength))
+        );
+        assert_eq!(decode_ecc("", opts), Ok(vec![]));
+    }
+
+    #[test]
+    #[should_panic(expected = "parity")]
+    fn parity_must_fit_a_codeword() {
+        let _ = EccOptions::new(255);
+    }
+}
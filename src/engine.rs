@@ -0,0 +1,727 @@
+//! Configurable encoder/decoder built from [`EncoderBuilder`] and [`DecoderBuilder`].
+//!
+//! An [`Engine`] bundles the encoding scheme, output formatting and decoder policy
+//! into one reusable, immutable value. It is `Copy + Send + Sync`, so it can live in
+//! a `static` or be shared across threads (e.g. behind an `Arc`).
+//!
+//! # Example
+//!
+//! ```
+//! use qr_base44::{EncoderBuilder, Mode};
+//!
+//! let engine = EncoderBuilder::new()
+//!     .mode(Mode::Optimal)
+//!     .group(4, '_')
+//!     .build();
+//! let s = engine.encode(b"hello");
+//! assert_eq!(s.len(), 9); // 8 optimal chars plus one separator
+//! assert_eq!(engine.decode(&s).unwrap(), b"hello");
+//!
+//! // Continue configuring the decode side of the same engine.
+//! let decoder = qr_base44::DecoderBuilder::from(engine)
+//!     .strict(false)
+//!     .max_decoded_len(1024)
+//!     .build();
+//! assert_eq!(decoder.decode(&format!(" {s}\n")).unwrap(), b"hello");
+//! ```
+//!
+//! # Framing
+//!
+//! Before encoding, an engine can compress the input (`compression` feature) and
+//! then append a big-endian CRC-32 of the result, the same trailer as
+//! [`seal_crc32`](crate::seal_crc32) and [`QrPayload`](crate::QrPayload). The
+//! decoder verifies and strips the checksum, then inflates, so both sides must
+//! agree on these options just as on the mode.
+
+#[cfg(feature = "compression")]
+use crate::compress::{self, Framing};
+use crate::crc::crc32;
+use crate::{Base44Error, chars_needed, decode, decode_bits, encode, encode_bits};
+
+/// Encoding scheme used by an [`Engine`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    /// Byte-pair scheme of [`encode`]/[`decode`]: 2 bytes -> 3 chars.
+    #[default]
+    BytePair,
+    /// Whole-message optimal scheme: the input is treated as one `8 * len`-bit
+    /// integer, as in [`encode_bits`]. The byte length is recovered from the
+    /// character count when decoding.
+    Optimal,
+    /// Order-preserving scheme of [`encode_sortable`](crate::encode_sortable):
+    /// encoded strings sort like their inputs.
+    Sortable,
+}
+
+impl Mode {
+    /// The digit order this scheme uses unless an engine overrides it:
+    /// least-significant first for [`Mode::BytePair`], most-significant first
+    /// for [`Mode::Optimal`] and [`Mode::Sortable`].
+    pub const fn native_digit_order(self) -> DigitOrder {
+        match self {
+            Mode::BytePair => DigitOrder::LsdFirst,
+            Mode::Optimal | Mode::Sortable => DigitOrder::MsdFirst,
+        }
+    }
+}
+
+/// Order in which an [`Engine`] writes the base-44 digits of each value.
+///
+/// For the byte-pair and sortable schemes a value is one group (3 characters, or
+/// 2 for a trailing byte); for the optimal scheme it is the whole message. The
+/// free functions always use each scheme's
+/// [native order](Mode::native_digit_order), so the byte-pair [`encode`] is
+/// LSD-first while [`encode_bits`] and [`encode_103bits`](crate::encode_103bits)
+/// are MSD-first. Overriding the order lets one engine match a partner's
+/// convention on every scheme. Non-native orders of [`Mode::Sortable`] do not
+/// preserve sort order.
+///
+/// ```
+/// use qr_base44::{DigitOrder, EncoderBuilder};
+///
+/// let msd = EncoderBuilder::new().digit_order(DigitOrder::MsdFirst).build();
+/// assert_eq!(qr_base44::encode(b"hi"), "LZD");
+/// assert_eq!(msd.encode(b"hi"), "DZL");
+/// assert_eq!(msd.decode("DZL").unwrap(), b"hi");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigitOrder {
+    /// Least-significant digit first.
+    LsdFirst,
+    /// Most-significant digit first.
+    MsdFirst,
+}
+
+/// Builder for an encoding [`Engine`].
+///
+/// Every option defaults to the behaviour of the free [`encode`]/[`decode`]
+/// functions.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EncoderBuilder {
+    config: Engine,
+}
+
+impl EncoderBuilder {
+    /// Start from the default configuration (byte-pair, no grouping).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Select the encoding scheme.
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.config.mode = mode;
+        self
+    }
+
+    /// Write digits in `order` instead of the mode's native order.
+    pub fn digit_order(mut self, order: DigitOrder) -> Self {
+        self.config.digit_order = Some(order);
+        self
+    }
+
+    /// Insert `separator` after every `size` output characters.
+    ///
+    /// The decoder of the built engine strips the separator again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero or `separator` is a Base44 alphabet character.
+    pub fn group(mut self, size: usize, separator: char) -> Self {
+        assert!(size > 0, "group size must be > 0");
+        assert!(
+            !separator.is_ascii() || crate::b44_val(separator as u8).is_none(),
+            "separator {separator:?} is part of the base44 alphabet"
+        );
+        self.config.group = Some((size, separator));
+        self
+    }
+
+    /// Append a CRC-32 of the (possibly compressed) input, adding 4 bytes
+    /// before encoding. The decoder reports a mismatch as
+    /// [`Base44Error::ChecksumMismatch`].
+    ///
+    /// ```
+    /// use qr_base44::{Base44Error, EncoderBuilder};
+    ///
+    /// let engine = EncoderBuilder::new().checksum(true).build();
+    /// let s = engine.encode(b"hi");
+    /// assert_eq!(s.len(), qr_base44::encoded_len(2 + 4));
+    /// assert_eq!(engine.decode(&s).unwrap(), b"hi");
+    /// assert_eq!(engine.decode(&s.replacen('L', "M", 1)), Err(Base44Error::ChecksumMismatch));
+    /// ```
+    pub fn checksum(mut self, checksum: bool) -> Self {
+        self.config.checksum = checksum;
+        self
+    }
+
+    /// zlib-compress the input before encoding.
+    ///
+    /// Always applied when on, so short or incompressible inputs can grow by
+    /// the zlib header and trailer; use it for text-like payloads of a few
+    /// hundred bytes or more.
+    #[cfg(feature = "compression")]
+    pub fn compress(mut self, compress: bool) -> Self {
+        self.config.compress = compress;
+        self
+    }
+
+    /// Finish the configuration.
+    pub fn build(self) -> Engine {
+        self.config
+    }
+}
+
+impl From<Engine> for EncoderBuilder {
+    fn from(config: Engine) -> Self {
+        Self { config }
+    }
+}
+
+/// Builder for the decoding side of an [`Engine`].
+///
+/// Mirrors [`EncoderBuilder`]: the mode, separator, checksum and compression must
+/// match the encoder's, and the decoder-only policy (strictness, size limit) is
+/// set here. Both builders
+/// produce the same [`Engine`] type, and either can be seeded from an existing
+/// engine with `From`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DecoderBuilder {
+    config: Engine,
+}
+
+impl DecoderBuilder {
+    /// Start from the default configuration (byte-pair, strict, no size limit).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Select the encoding scheme to decode.
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.config.mode = mode;
+        self
+    }
+
+    /// Read digits in `order`; see [`EncoderBuilder::digit_order`].
+    pub fn digit_order(mut self, order: DigitOrder) -> Self {
+        self.config.digit_order = Some(order);
+        self
+    }
+
+    /// Strip `separator` before decoding; see [`EncoderBuilder::group`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `separator` is a Base44 alphabet character.
+    pub fn separator(mut self, separator: char) -> Self {
+        assert!(
+            !separator.is_ascii() || crate::b44_val(separator as u8).is_none(),
+            "separator {separator:?} is part of the base44 alphabet"
+        );
+        let size = self.config.group.map_or(usize::MAX, |(size, _)| size);
+        self.config.group = Some((size, separator));
+        self
+    }
+
+    /// In strict mode (the default) only alphabet characters and the configured
+    /// separator are accepted. Lenient mode also skips ASCII whitespace, such as
+    /// line breaks picked up when codes are copied from documents.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.config.strict = strict;
+        self
+    }
+
+    /// Reject inputs that would decode to more than `limit` bytes with
+    /// [`Base44Error::TooLarge`], before any output is allocated.
+    ///
+    /// With [`compress`](Self::compress) the limit applies to the inflated
+    /// output and is checked while inflating.
+    pub fn max_decoded_len(mut self, limit: usize) -> Self {
+        self.config.max_decoded_len = Some(limit);
+        self
+    }
+
+    /// Verify and strip the trailing CRC-32; see [`EncoderBuilder::checksum`].
+    ///
+    /// A body that fails the check is [`Base44Error::ChecksumMismatch`], and
+    /// one too short to hold the checksum is [`Base44Error::InvalidLength`].
+    /// The body of a [`seal_crc32`](crate::seal_crc32) envelope, after its
+    /// length prefix, decodes with this option.
+    pub fn checksum(mut self, checksum: bool) -> Self {
+        self.config.checksum = checksum;
+        self
+    }
+
+    /// Inflate the (checksum-verified) body; see [`EncoderBuilder::compress`].
+    ///
+    /// A corrupt stream is [`Base44Error::ChecksumMismatch`], and one that
+    /// inflates past [`max_decoded_len`](Self::max_decoded_len) or
+    /// [`MAX_INFLATED_LEN`](crate::MAX_INFLATED_LEN) is [`Base44Error::TooLarge`].
+    ///
+    /// ```
+    /// use qr_base44::{DecoderBuilder, EncoderBuilder};
+    ///
+    /// let text = b"abcabcabcabcabcabcabcabcabcabcabcabcabcabcabc";
+    /// let s = EncoderBuilder::new().compress(true).checksum(true).build().encode(text);
+    /// let decoder = DecoderBuilder::new().compress(true).checksum(true).build();
+    /// assert_eq!(decoder.decode(&s).unwrap(), text);
+    /// // Without `compress` the zlib stream comes back as is.
+    /// let raw = DecoderBuilder::new().checksum(true).build().decode(&s).unwrap();
+    /// assert_eq!(raw[0], 0x78);
+    /// ```
+    #[cfg(feature = "compression")]
+    pub fn compress(mut self, compress: bool) -> Self {
+        self.config.compress = compress;
+        self
+    }
+
+    /// Finish the configuration.
+    pub fn build(self) -> Engine {
+        self.config
+    }
+}
+
+impl From<Engine> for DecoderBuilder {
+    fn from(config: Engine) -> Self {
+        Self { config }
+    }
+}
+
+/// A configured Base44 encoder/decoder. Create one with [`EncoderBuilder`] or
+/// [`DecoderBuilder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Engine {
+    mode: Mode,
+    digit_order: Option<DigitOrder>,
+    group: Option<(usize, char)>,
+    strict: bool,
+    max_decoded_len: Option<usize>,
+    checksum: bool,
+    compress: bool,
+}
+
+impl Default for Engine {
+    fn default() -> Self {
+        Self {
+            mode: Mode::BytePair,
+            digit_order: None,
+            group: None,
+            strict: true,
+            max_decoded_len: None,
+            checksum: false,
+            compress: false,
+        }
+    }
+}
+
+impl Engine {
+    /// The encoding scheme.
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// The digit order in effect: the configured one, or the mode's native order.
+    pub fn digit_order(&self) -> DigitOrder {
+        self.digit_order.unwrap_or(self.mode.native_digit_order())
+    }
+
+    /// Whether digits are written in the opposite of the mode's native order.
+    fn reversed(&self) -> bool {
+        self.digit_order() != self.mode.native_digit_order()
+    }
+
+    /// Group size and separator, if grouping is enabled.
+    pub fn group(&self) -> Option<(usize, char)> {
+        self.group
+    }
+
+    /// Whether the decoder rejects whitespace.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Maximum decoded length accepted by [`Engine::decode`], if limited.
+    pub fn max_decoded_len(&self) -> Option<usize> {
+        self.max_decoded_len
+    }
+
+    /// Whether a CRC-32 is appended before encoding and verified when decoding.
+    pub fn has_checksum(&self) -> bool {
+        self.checksum
+    }
+
+    /// Whether the input is compressed; always `false` without the
+    /// `compression` feature.
+    pub fn is_compressed(&self) -> bool {
+        self.compress
+    }
+
+    /// Encode `input` according to this configuration.
+    pub fn encode(&self, input: &[u8]) -> String {
+        let framed = self.frame(input);
+        let input = framed.as_deref().unwrap_or(input);
+        let raw = match self.mode {
+            Mode::BytePair => encode(input),
+            Mode::Optimal if input.is_empty() => String::new(),
+            Mode::Optimal => encode_bits(input.len() * 8, input),
+            Mode::Sortable => crate::encode_sortable(input),
+        };
+        let raw = match self.reversed() {
+            true => reverse_digits(raw.as_bytes(), self.mode),
+            false => raw,
+        };
+        match self.group {
+            Some((size, sep)) => insert_separators(&raw, size, sep),
+            None => raw,
+        }
+    }
+
+    /// Decode a string produced by [`Engine::encode`] with the same configuration.
+    pub fn decode(&self, s: &str) -> Result<Vec<u8>, Base44Error> {
+        let sep = self.group.map(|(_, sep)| sep);
+        let skip = |c: char| Some(c) == sep || (!self.strict && c.is_ascii_whitespace());
+        let stripped;
+        let mut s = if s.contains(skip) {
+            stripped = s.replace(skip, "");
+            stripped.as_str()
+        } else {
+            s
+        };
+        let reordered;
+        if self.reversed() {
+            if !s.is_ascii() {
+                return Err(Base44Error::InvalidChar);
+            }
+            reordered = reverse_digits(s.as_bytes(), self.mode);
+            s = reordered.as_str();
+        }
+        let len = match self.mode {
+            Mode::BytePair | Mode::Sortable => crate::decoded_len(s.len()),
+            Mode::Optimal if s.is_empty() => Some(0),
+            Mode::Optimal => optimal_byte_len(s.len()),
+        };
+        // A compressed body says nothing about the inflated size; the limit is
+        // enforced while inflating instead.
+        let trailer = if self.checksum { 4 } else { 0 };
+        if let (Some(len), Some(limit)) = (len, self.max_decoded_len) {
+            if !self.compress && len.saturating_sub(trailer) > limit {
+                return Err(Base44Error::TooLarge);
+            }
+        }
+        let bytes = match self.mode {
+            Mode::BytePair => decode(s),
+            Mode::Sortable => crate::decode_sortable(s),
+            Mode::Optimal => match len {
+                Some(0) => Ok(Vec::new()),
+                Some(len) => decode_bits(len * 8, s),
+                None => Err(Base44Error::Dangling),
+            },
+        }?;
+        self.unframe(bytes)
+    }
+
+    /// The compressed and checksummed bytes to encode, if either option is on.
+    fn frame(&self, input: &[u8]) -> Option<Vec<u8>> {
+        let mut body = self.deflate(input);
+        if self.checksum {
+            let body = body.get_or_insert_with(|| input.to_vec());
+            let check = crc32(body).to_be_bytes();
+            body.extend_from_slice(&check);
+        }
+        body
+    }
+
+    /// Undo [`frame`](Self::frame) on decoded bytes.
+    fn unframe(&self, mut bytes: Vec<u8>) -> Result<Vec<u8>, Base44Error> {
+        if self.checksum {
+            let body_len = bytes
+                .len()
+                .checked_sub(4)
+                .ok_or(Base44Error::InvalidLength)?;
+            let check = bytes.split_off(body_len);
+            if check != crc32(&bytes).to_be_bytes() {
+                return Err(Base44Error::ChecksumMismatch);
+            }
+        }
+        self.inflate(bytes)
+    }
+
+    #[cfg(feature = "compression")]
+    fn deflate(&self, input: &[u8]) -> Option<Vec<u8>> {
+        self.compress
+            .then(|| compress::deflate(input, Framing::Zlib))
+    }
+
+    #[cfg(not(feature = "compression"))]
+    fn deflate(&self, _: &[u8]) -> Option<Vec<u8>> {
+        None
+    }
+
+    #[cfg(feature = "compression")]
+    fn inflate(&self, bytes: Vec<u8>) -> Result<Vec<u8>, Base44Error> {
+        if !self.compress {
+            return Ok(bytes);
+        }
+        let limit = self
+            .max_decoded_len
+            .map_or(crate::MAX_INFLATED_LEN, |limit| {
+                limit.min(crate::MAX_INFLATED_LEN)
+            });
+        compress::inflate_within(&bytes, Framing::Zlib, limit)
+    }
+
+    #[cfg(not(feature = "compression"))]
+    fn inflate(&self, bytes: Vec<u8>) -> Result<Vec<u8>, Base44Error> {
+        Ok(bytes)
+    }
+}
+
+/// Reverse the digits of each value of `mode` in the ASCII text `s`; its own
+/// inverse.
+fn reverse_digits(s: &[u8], mode: Mode) -> String {
+    let mut out = s.to_vec();
+    match mode {
+        Mode::BytePair | Mode::Sortable => out.chunks_mut(3).for_each(<[u8]>::reverse),
+        Mode::Optimal => out.reverse(),
+    }
+    crate::ascii_to_string(out)
+}
+
+/// Byte length whose whole-message optimal encoding is exactly `chars` long.
+///
+/// Each byte adds at least one character, so there is at most one such length.
+pub(crate) fn optimal_byte_len(chars: usize) -> Option<usize> {
+    if chars > crate::MAX_CHARS {
+        return None;
+    }
+    let len = crate::bits_capacity(chars) / 8;
+    (len > 0 && chars_needed(len * 8) == chars).then_some(len)
+}
+
+pub(crate) fn insert_separators(s: &str, size: usize, sep: char) -> String {
+    let mut out = String::with_capacity(s.len() + s.len() / size * sep.len_utf8());
+    for (i, ch) in s.chars().enumerate() {
+        if i > 0 && i % size == 0 {
+            out.push(sep);
+        }
+        out.push(ch);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_engine_matches_free_functions() {
+        let engine = EncoderBuilder::new().build();
+        let data = b"Hello, world!";
+        assert_eq!(engine.encode(data), encode(data));
+        assert_eq!(engine.decode(&encode(data)).unwrap(), data);
+    }
+
+    #[test]
+    fn optimal_mode_roundtrips_every_length() {
+        let engine = EncoderBuilder::new().mode(Mode::Optimal).build();
+        for len in 0..64 {
+            let data: Vec<u8> = (0..len as u8).map(|b| b.wrapping_mul(37)).collect();
+            let s = engine.encode(&data);
+            assert_eq!(s.len(), if len == 0 { 0 } else { chars_needed(len * 8) });
+            assert_eq!(engine.decode(&s).unwrap(), data, "length {len}");
+        }
+        // 1 char can never be a whole-message optimal encoding
+        assert!(matches!(engine.decode("A"), Err(Base44Error::Dangling)));
+    }
+
+    #[test]
+    fn sortable_mode_uses_order_preserving_codec() {
+        let engine = EncoderBuilder::new().mode(Mode::Sortable).build();
+        let s = engine.encode(b"key");
+        assert_eq!(s, crate::encode_sortable(b"key"));
+        assert_eq!(engine.decode(&s).unwrap(), b"key");
+    }
+
+    #[test]
+    fn grouping_inserts_and_strips_separators() {
+        let engine = EncoderBuilder::new().group(3, '_').build();
+        let s = engine.encode(&[0xFF, 0xFF, 0x41]);
+        assert_eq!(s, "J%X_L1");
+        assert_eq!(engine.decode(&s).unwrap(), [0xFF, 0xFF, 0x41]);
+        assert_eq!(engine.decode("J%XL1").unwrap(), [0xFF, 0xFF, 0x41]);
+    }
+
+    #[test]
+    fn decoder_policy() {
+        let data = [0xFF, 0xFF, 0x41];
+        let strict = DecoderBuilder::new().build();
+        assert!(matches!(
+            strict.decode("J%X\nL1"),
+            Err(Base44Error::InvalidChar)
+        ));
+        let lenient = DecoderBuilder::new().strict(false).build();
+        assert_eq!(lenient.decode(" J%X\r\nL1\t").unwrap(), data);
+
+        let limited = DecoderBuilder::new().max_decoded_len(2).build();
+        assert_eq!(limited.decode("J%X").unwrap(), [0xFF, 0xFF]);
+        assert!(matches!(
+            limited.decode("J%XL1"),
+            Err(Base44Error::TooLarge)
+        ));
+
+        let optimal = DecoderBuilder::new()
+            .mode(Mode::Optimal)
+            .separator('_')
+            .max_decoded_len(4)
+            .build();
+        let s = EncoderBuilder::new()
+            .mode(Mode::Optimal)
+            .group(2, '_')
+            .build()
+            .encode(b"abcd");
+        assert_eq!(optimal.decode(&s).unwrap(), b"abcd");
+        assert!(matches!(
+            optimal.decode(
+                &EncoderBuilder::new()
+                    .mode(Mode::Optimal)
+                    .build()
+                    .encode(b"abcde")
+            ),
+            Err(Base44Error::TooLarge)
+        ));
+    }
+
+    #[test]
+    fn engine_is_shareable_across_threads() {
+        let engine = std::sync::Arc::new(
+            DecoderBuilder::from(EncoderBuilder::new().mode(Mode::Optimal).build())
+                .strict(false)
+                .build(),
+        );
+        let handles: Vec<_> = (0..4u8)
+            .map(|i| {
+                let engine = std::sync::Arc::clone(&engine);
+                std::thread::spawn(move || {
+                    let data = vec![i; 10];
+                    assert_eq!(engine.decode(&engine.encode(&data)).unwrap(), data);
+                })
+            })
+            .collect();
+        for h in handles {
+            h.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn digit_order_override() {
+        let data = [0xFF, 0xFF, 0x41];
+        let msd = EncoderBuilder::new()
+            .digit_order(DigitOrder::MsdFirst)
+            .build();
+        assert_eq!(msd.digit_order(), DigitOrder::MsdFirst);
+        assert_eq!(msd.encode(&data), "X%J1L");
+        assert_eq!(msd.decode("X%J1L").unwrap(), data);
+        assert_eq!(msd.decode("J%XL1"), Err(Base44Error::Overflow));
+
+        // LSD-first optimal output is the reverse of encode_bits.
+        let lsd = EncoderBuilder::new()
+            .mode(Mode::Optimal)
+            .digit_order(DigitOrder::LsdFirst)
+            .group(4, '_')
+            .build();
+        let native: String = encode_bits(24, &data).chars().rev().collect();
+        assert_eq!(lsd.encode(&data).replace('_', ""), native);
+        assert_eq!(lsd.decode(&lsd.encode(&data)).unwrap(), data);
+
+        let sortable = DecoderBuilder::new()
+            .mode(Mode::Sortable)
+            .digit_order(DigitOrder::LsdFirst)
+            .build();
+        let s = EncoderBuilder::from(sortable).build().encode(b"key");
+        assert_eq!(sortable.decode(&s).unwrap(), b"key");
+        assert_eq!(sortable.decode("\u{e9}"), Err(Base44Error::InvalidChar));
+        assert_eq!(
+            EncoderBuilder::new().build().digit_order(),
+            DigitOrder::LsdFirst
+        );
+    }
+
+    #[test]
+    fn checksum_frames_every_mode() {
+        for mode in [Mode::BytePair, Mode::Optimal, Mode::Sortable] {
+            let engine = EncoderBuilder::new().mode(mode).checksum(true).build();
+            for data in [&b""[..], b"k", b"key", &[0xFF; 20]] {
+                let s = engine.encode(data);
+                assert_eq!(engine.decode(&s).unwrap(), data, "{mode:?}");
+            }
+        }
+        let engine = EncoderBuilder::new().checksum(true).build();
+        let s = engine.encode(b"hi");
+        assert_eq!(&s[..3], "LZD");
+        assert_eq!(
+            engine.decode(&format!("LZE{}", &s[3..])),
+            Err(Base44Error::ChecksumMismatch)
+        );
+        assert_eq!(engine.decode("LZD"), Err(Base44Error::InvalidLength));
+        let limited = DecoderBuilder::from(engine).max_decoded_len(2).build();
+        assert_eq!(limited.decode(&s).unwrap(), b"hi");
+        assert_eq!(
+            limited.decode(&engine.encode(b"hi!")),
+            Err(Base44Error::TooLarge)
+        );
+    }
+
+    #[test]
+    fn decoder_builder_mirrors_the_framing() {
+        let data = b"hello";
+        let sealed = crate::seal_crc32(data);
+        let decoder = DecoderBuilder::new().checksum(true).build();
+        assert!(decoder.has_checksum());
+        assert_eq!(decoder.decode(&sealed[1..]).unwrap(), data);
+        assert_eq!(
+            decoder,
+            EncoderBuilder::new().checksum(true).build(),
+            "both builders configure the same engine"
+        );
+
+        let payload = crate::QrPayloadBuilder::new().tag(7).encode(data);
+        let body = decoder.decode(&payload).unwrap();
+        assert_eq!(body, [&[7, 0][..], data].concat());
+        assert_eq!(
+            DecoderBuilder::new()
+                .build()
+                .decode(&sealed[1..])
+                .unwrap()
+                .len(),
+            data.len() + 4
+        );
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn compression_roundtrips_and_respects_the_limit() {
+        let data = b"to be or not to be, that is the question; ".repeat(20);
+        let engine = EncoderBuilder::new().compress(true).checksum(true).build();
+        let s = engine.encode(&data);
+        assert!(s.len() < encode(&data).len() / 4);
+        assert_eq!(engine.decode(&s).unwrap(), data);
+
+        let limited = DecoderBuilder::from(engine)
+            .max_decoded_len(data.len() - 1)
+            .build();
+        assert_eq!(limited.decode(&s), Err(Base44Error::TooLarge));
+
+        // Without the CRC, corruption is caught by zlib's own Adler-32.
+        let bare = EncoderBuilder::new().compress(true).build();
+        let mut corrupt = bare.encode(&data).into_bytes();
+        let mid = corrupt.len() / 2;
+        corrupt[mid] = if corrupt[mid] == b'0' { b'1' } else { b'0' };
+        let corrupt = String::from_utf8(corrupt).unwrap();
+        assert!(bare.decode(&corrupt).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "alphabet")]
+    fn separator_from_alphabet_is_rejected() {
+        let _ = EncoderBuilder::new().group(4, '-');
+    }
+}
@@ -0,0 +1,351 @@
+//! Web framework extractors for Base44-encoded request parameters.
+//!
+//! [`Base44<T>`] decodes the route's only path parameter or, for routes without
+//! parameters, the query string (`/scan?LZD`), which is how QR codes usually
+//! carry a payload in a URL. Percent-escapes in the query are undone first, as
+//! the framework already does for path parameters, so the output of
+//! [`encode_url_component`](crate::encode_url_component) is accepted either way. The decoded size is checked against
+//! [`Base44Config`] before anything is allocated, and failures are answered with
+//! `400 Bad Request` and a small JSON body naming the error.
+
+use core::fmt;
+
+#[cfg(any(feature = "axum", feature = "actix-web"))]
+use crate::url::unescape;
+use crate::{Base44Error, FromBase44};
+
+/// A request parameter decoded from byte-pair Base44 into `T`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Base44<T>(pub T);
+
+/// Limits applied by the [`Base44`] extractor.
+///
+/// Install it per router or app (an axum `Extension`, or actix-web `app_data`);
+/// without one, the [`Default`] is used. Rocket parameters always use the default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Base44Config {
+    /// Largest accepted decoded size in bytes.
+    pub max_decoded: usize,
+}
+
+impl Default for Base44Config {
+    /// 4096 bytes, above the capacity of the largest QR code.
+    fn default() -> Self {
+        Self { max_decoded: 4096 }
+    }
+}
+
+/// Why a [`Base44`] extractor rejected a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base44Rejection {
+    /// The route has no single path parameter and the URL has no query string.
+    Missing,
+    /// The parameter failed to decode, or exceeds [`Base44Config::max_decoded`]
+    /// ([`Base44Error::TooLarge`]).
+    Invalid(Base44Error),
+}
+
+impl Base44Rejection {
+    /// Response body: `{"error":"<code>","message":"<text>"}`, where `code` is
+    /// the snake_case error name.
+    pub fn to_json(&self) -> String {
+        let code = match self {
+            Base44Rejection::Missing => "missing",
+            Base44Rejection::Invalid(err) => match err {
+                Base44Error::InvalidChar => "invalid_char",
+                Base44Error::Dangling => "dangling",
+                Base44Error::Overflow => "overflow",
+                Base44Error::TooLarge => "too_large",
+                Base44Error::InvalidLength => "invalid_length",
+                Base44Error::NonZeroPadding => "non_zero_padding",
+                Base44Error::ChecksumMismatch => "checksum_mismatch",
+            },
+        };
+        format!(r#"{{"error":"{code}","message":"{self}"}}"#)
+    }
+}
+
+impl fmt::Display for Base44Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Base44Rejection::Missing => f.write_str("missing base44 parameter"),
+            Base44Rejection::Invalid(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Base44Rejection {}
+
+/// Size-check and decode one parameter.
+fn extract<T: FromBase44>(text: &str, config: &Base44Config) -> Result<Base44<T>, Base44Rejection> {
+    let decoded_len = text.len() / 3 * 2 + text.len() % 3 / 2;
+    if decoded_len > config.max_decoded {
+        return Err(Base44Rejection::Invalid(Base44Error::TooLarge));
+    }
+    T::from_base44(text)
+        .map(Base44)
+        .map_err(Base44Rejection::Invalid)
+}
+
+/// Undo percent-escapes in a query string, then [`extract`] it.
+#[cfg(any(feature = "axum", feature = "actix-web"))]
+fn extract_query<T: FromBase44>(
+    query: &str,
+    config: &Base44Config,
+) -> Result<Base44<T>, Base44Rejection> {
+    let text = unescape(query).map_err(|_| Base44Rejection::Invalid(Base44Error::InvalidChar))?;
+    extract(&text, config)
+}
+
+#[cfg(feature = "axum")]
+mod with_axum {
+    use axum::extract::{FromRequestParts, RawPathParams};
+    use axum::http::request::Parts;
+    use axum::http::{StatusCode, header};
+    use axum::response::{IntoResponse, Response};
+
+    use super::*;
+
+    impl<T: FromBase44, S: Send + Sync> FromRequestParts<S> for Base44<T> {
+        type Rejection = Base44Rejection;
+
+        async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+            let config = parts
+                .extensions
+                .get::<Base44Config>()
+                .copied()
+                .unwrap_or_default();
+            let path = RawPathParams::from_request_parts(parts, state).await.ok();
+            let mut values = path.iter().flat_map(|p| p.iter()).map(|(_, value)| value);
+            match (values.next(), values.next()) {
+                (Some(value), None) => extract(value, &config),
+                (None, _) => {
+                    extract_query(parts.uri.query().ok_or(Base44Rejection::Missing)?, &config)
+                }
+                (Some(_), Some(_)) => Err(Base44Rejection::Missing),
+            }
+        }
+    }
+
+    impl IntoResponse for Base44Rejection {
+        fn into_response(self) -> Response {
+            let body = self.to_json();
+            let json = [(header::CONTENT_TYPE, "application/json")];
+            (StatusCode::BAD_REQUEST, json, body).into_response()
+        }
+    }
+}
+
+#[cfg(feature = "actix-web")]
+mod with_actix {
+    use std::future::{Ready, ready};
+
+    use actix_web::dev::Payload;
+    use actix_web::http::StatusCode;
+    use actix_web::{FromRequest, HttpRequest, HttpResponse, ResponseError};
+
+    use super::*;
+
+    impl<T: FromBase44> FromRequest for Base44<T> {
+        type Error = Base44Rejection;
+        type Future = Ready<Result<Self, Self::Error>>;
+
+        fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+            let config = req.app_data::<Base44Config>().copied().unwrap_or_default();
+            let mut values = req.match_info().iter().map(|(_, value)| value);
+            ready(match (values.next(), values.next()) {
+                (Some(value), None) => extract(value, &config),
+                (None, _) => req
+                    .uri()
+                    .query()
+                    .map_or(Err(Base44Rejection::Missing), |query| {
+                        extract_query(query, &config)
+                    }),
+                (Some(_), Some(_)) => Err(Base44Rejection::Missing),
+            })
+        }
+    }
+
+    impl ResponseError for Base44Rejection {
+        fn status_code(&self) -> StatusCode {
+            StatusCode::BAD_REQUEST
+        }
+
+        fn error_response(&self) -> HttpResponse {
+            HttpResponse::BadRequest()
+                .content_type("application/json")
+                .body(self.to_json())
+        }
+    }
+}
+
+#[cfg(feature = "rocket")]
+mod with_rocket {
+    use rocket::form::{self, FromFormField, ValueField};
+    use rocket::request::FromParam;
+
+    use super::*;
+    use crate::Base44String;
+
+    impl<'a, T: FromBase44> FromParam<'a> for Base44<T> {
+        type Error = Base44Rejection;
+
+        fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+            extract(param, &Base44Config::default())
+        }
+    }
+
+    impl<'a> FromParam<'a> for Base44String {
+        type Error = Base44Rejection;
+
+        fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+            Base44::from_param(param).map(|Base44(s)| s)
+        }
+    }
+
+    impl<'v, T: FromBase44 + Send> FromFormField<'v> for Base44<T> {
+        fn from_value(field: ValueField<'v>) -> form::Result<'v, Self> {
+            Ok(extract(field.value, &Base44Config::default()).map_err(form::Error::custom)?)
+        }
+    }
+
+    impl<'v> FromFormField<'v> for Base44String {
+        fn from_value(field: ValueField<'v>) -> form::Result<'v, Self> {
+            Base44::from_value(field).map(|Base44(s)| s)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_before_decoding() {
+        let config = Base44Config { max_decoded: 3 };
+        assert_eq!(
+            extract::<Vec<u8>>("J%XL1", &config),
+            Ok(Base44(vec![0xFF, 0xFF, 0x41]))
+        );
+        assert_eq!(
+            extract::<Vec<u8>>("J%XJ%X", &config),
+            Err(Base44Rejection::Invalid(Base44Error::TooLarge))
+        );
+        // Oversized garbage is rejected on length alone.
+        assert_eq!(
+            extract::<Vec<u8>>("aaaaaa", &config),
+            Err(Base44Rejection::Invalid(Base44Error::TooLarge))
+        );
+        assert_eq!(
+            Base44Rejection::Invalid(Base44Error::Overflow).to_json(),
+            r#"{"error":"overflow","message":"value overflow"}"#
+        );
+    }
+
+    #[cfg(feature = "axum")]
+    #[test]
+    fn axum_path_and_query() {
+        use std::pin::pin;
+        use std::task::{Context, Poll, Waker};
+
+        use axum::body::Body;
+        use axum::http::{Request, StatusCode};
+        use axum::routing::get;
+        use axum::{Extension, Router};
+        use tower_service::Service;
+
+        async fn echo(Base44(bytes): Base44<Vec<u8>>) -> Vec<u8> {
+            bytes
+        }
+        let mut app = Router::new()
+            .route("/p/{code}", get(echo))
+            .route("/q", get(echo))
+            .layer(Extension(Base44Config { max_decoded: 8 }));
+        let mut status = |uri: &str| {
+            let request = Request::get(uri).body(Body::empty()).unwrap();
+            let mut future = pin!(app.call(request));
+            let mut cx = Context::from_waker(Waker::noop());
+            loop {
+                if let Poll::Ready(response) = future.as_mut().poll(&mut cx) {
+                    break response.unwrap().status();
+                }
+            }
+        };
+        assert_eq!(status("/p/LZD"), StatusCode::OK);
+        assert_eq!(status("/q?LZD"), StatusCode::OK);
+        assert_eq!(status("/p/ZZZ"), StatusCode::BAD_REQUEST);
+        assert_eq!(status("/q"), StatusCode::BAD_REQUEST);
+        assert_eq!(status("/q?000000000000000"), StatusCode::BAD_REQUEST);
+        // Escaped `%`, `+` and `:` as encode_url_component writes them.
+        let payload = [0xFF, 0xFF, 0x07, 0x8B]; // "J%X+:0"
+        let escaped = crate::encode_url_component(&payload);
+        assert!(escaped.contains("%25") && escaped.contains("%2B") && escaped.contains("%3A"));
+        assert_eq!(status(&format!("/q?{escaped}")), StatusCode::OK);
+        assert_eq!(status("/q?LZD%2C"), StatusCode::BAD_REQUEST);
+    }
+
+    #[cfg(feature = "actix-web")]
+    #[test]
+    fn actix_path_query_and_app_data() {
+        use std::pin::pin;
+        use std::task::{Context, Poll, Waker};
+
+        use actix_web::FromRequest;
+        use actix_web::test::TestRequest;
+
+        let extract = |req: TestRequest| {
+            let (req, mut payload) = req
+                .app_data(Base44Config { max_decoded: 8 })
+                .to_http_parts();
+            let mut future = pin!(Base44::<Vec<u8>>::from_request(&req, &mut payload));
+            match future
+                .as_mut()
+                .poll(&mut Context::from_waker(Waker::noop()))
+            {
+                Poll::Ready(result) => result.map(|Base44(bytes)| bytes),
+                Poll::Pending => unreachable!("extraction is synchronous"),
+            }
+        };
+        let path = TestRequest::with_uri("/p/LZD").param("code", "LZD");
+        assert_eq!(extract(path), Ok(b"hi".to_vec()));
+        assert_eq!(extract(TestRequest::with_uri("/q?LZD")), Ok(b"hi".to_vec()));
+        assert_eq!(
+            extract(TestRequest::with_uri("/q")),
+            Err(Base44Rejection::Missing)
+        );
+        assert_eq!(
+            extract(TestRequest::with_uri("/q?000000000000000")),
+            Err(Base44Rejection::Invalid(Base44Error::TooLarge))
+        );
+        assert_eq!(
+            extract(TestRequest::with_uri("/q?J%25X")),
+            Ok(vec![0xFF, 0xFF])
+        );
+        assert_eq!(
+            extract(TestRequest::with_uri("/q?LZD%2C")),
+            Err(Base44Rejection::Invalid(Base44Error::InvalidChar))
+        );
+    }
+
+    #[cfg(feature = "rocket")]
+    #[test]
+    fn rocket_params_and_form_fields() {
+        use rocket::form::{FromFormField, ValueField};
+        use rocket::request::FromParam;
+
+        use crate::Base44String;
+
+        assert_eq!(
+            Base44::<Vec<u8>>::from_param("LZD"),
+            Ok(Base44(b"hi".to_vec()))
+        );
+        assert_eq!(Base44String::from_param("LZD").unwrap().as_str(), "LZD");
+        assert_eq!(
+            Base44String::from_param("L"),
+            Err(Base44Rejection::Invalid(Base44Error::Dangling))
+        );
+        let field = ValueField::parse("code=LZD");
+        assert_eq!(Base44::<Vec<u8>>::from_value(field).unwrap().0, b"hi");
+        assert!(Base44String::from_value(ValueField::parse("code=ZZZ")).is_err());
+    }
+}
@@ -0,0 +1,111 @@
+//! UniFFI bindings for Kotlin and Swift (enabled with the `uniffi` feature).
+//!
+//! The interface is declared with UniFFI proc-macros, so no separate UDL file is
+//! needed. Generate bindings from the built library, e.g.:
+//!
+//! ```text
+//! cargo build --release -p qr-base44-uniffi
+//! uniffi-bindgen generate --library target/release/libqr_base44_uniffi.so --language kotlin --out-dir out
+//! uniffi-bindgen generate --library target/release/libqr_base44_uniffi.so --language swift --out-dir out
+//! ```
+//!
+//! Mobile scanners then share the exact decoder used by the backend.
+
+use crate::Base44Error;
+
+/// Error surfaced to foreign languages.
+///
+/// Mirrors [`Base44Error`]. Every export calls a non-panicking `try_*` function,
+/// since panics must not cross the FFI.
+#[derive(Debug, uniffi::Error)]
+#[uniffi(flat_error)]
+pub enum FfiError {
+    InvalidChar,
+    Dangling,
+    Overflow,
+    TooLarge,
+    InvalidLength,
+    NonZeroPadding,
+    ChecksumMismatch,
+}
+
+impl core::fmt::Display for FfiError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FfiError::InvalidChar => write!(f, "{}", Base44Error::InvalidChar),
+            FfiError::Dangling => write!(f, "{}", Base44Error::Dangling),
+            FfiError::Overflow => write!(f, "{}", Base44Error::Overflow),
+            FfiError::TooLarge => write!(f, "{}", Base44Error::TooLarge),
+            FfiError::InvalidLength => write!(f, "{}", Base44Error::InvalidLength),
+            FfiError::NonZeroPadding => write!(f, "{}", Base44Error::NonZeroPadding),
+            FfiError::ChecksumMismatch => write!(f, "{}", Base44Error::ChecksumMismatch),
+        }
+    }
+}
+
+impl core::error::Error for FfiError {}
+
+impl From<Base44Error> for FfiError {
+    fn from(e: Base44Error) -> Self {
+        match e {
+            Base44Error::InvalidChar => FfiError::InvalidChar,
+            Base44Error::Dangling => FfiError::Dangling,
+            Base44Error::Overflow => FfiError::Overflow,
+            Base44Error::TooLarge => FfiError::TooLarge,
+            Base44Error::InvalidLength => FfiError::InvalidLength,
+            Base44Error::NonZeroPadding => FfiError::NonZeroPadding,
+            Base44Error::ChecksumMismatch => FfiError::ChecksumMismatch,
+        }
+    }
+}
+
+/// Encode bytes with the byte-pair scheme (see [`crate::encode`]).
+#[uniffi::export]
+pub fn encode(input: Vec<u8>) -> String {
+    crate::encode(&input)
+}
+
+/// Decode a byte-pair encoded string (see [`crate::decode`]).
+#[uniffi::export]
+pub fn decode(s: String) -> Result<Vec<u8>, FfiError> {
+    Ok(crate::decode(&s)?)
+}
+
+/// Encode exactly `bits` bits with optimal length (see [`crate::try_encode_bits`]).
+#[uniffi::export]
+pub fn encode_bits(bits: u32, input: Vec<u8>) -> Result<String, FfiError> {
+    Ok(crate::try_encode_bits(bits as usize, &input)?)
+}
+
+/// Decode an optimal-length string holding `bits` bits (see [`crate::try_decode_bits`]).
+#[uniffi::export]
+pub fn decode_bits(bits: u32, s: String) -> Result<Vec<u8>, FfiError> {
+    Ok(crate::try_decode_bits(bits as usize, &s)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_map_and_arguments_are_checked() {
+        assert_eq!(decode(encode(vec![1, 2, 3])).unwrap(), vec![1, 2, 3]);
+        assert!(matches!(decode("A".into()), Err(FfiError::Dangling)));
+        assert!(matches!(
+            encode_bits(0, vec![]),
+            Err(FfiError::InvalidLength)
+        ));
+        assert!(matches!(
+            encode_bits(16, vec![0]),
+            Err(FfiError::InvalidLength)
+        ));
+        assert!(matches!(
+            decode_bits(8, "0000".into()),
+            Err(FfiError::InvalidLength)
+        ));
+        assert!(matches!(
+            decode_bits(8, "ZZ".into()),
+            Err(FfiError::Overflow)
+        ));
+    }
+}
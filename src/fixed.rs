@@ -0,0 +1,476 @@
+//! Fixed-size helpers for common key, nonce and hash sizes.
+//!
+//! All use the optimal packing of [`encode_bits`]: the array is read as one
+//! little-endian integer and written with the minimum number of characters.
+//!
+//! | Function | Bytes | Chars |
+//! |----------|-------|-------|
+//! | [`encode_64bits`] | 8 | 12 |
+//! | [`encode_103bits`] | 13 (103 bits) | 19 |
+//! | [`encode_128bits`] | 16 | 24 |
+//! | [`encode_u128`] | `u128` | 24 |
+//! | [`encode_256bits`] | 32 | 47 |
+//!
+//! Decoders require exactly that many characters and report other lengths as
+//! [`Base44Error::InvalidLength`].
+//!
+//! [`Base44Array`] and [`Bits103`] wrap the same codecs in the standard conversion
+//! traits (`TryFrom<&str>`, `FromStr`, `Display`) for use in generic code.
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::sortable::decode_sortable_into;
+use crate::{Base44Error, Mode, b44_val, chars_needed, decode_into, encode_bits, encoded_len};
+
+/// Decode `s` holding exactly `bits` bits into an array of `bits.div_ceil(8)` bytes.
+pub(crate) fn decode_array_bits<const N: usize>(
+    bits: usize,
+    s: &str,
+) -> Result<[u8; N], Base44Error> {
+    debug_assert_eq!(N, bits.div_ceil(8));
+    if N == 0 {
+        return if s.is_empty() {
+            Ok([0u8; N])
+        } else {
+            Err(Base44Error::InvalidLength)
+        };
+    }
+    if s.len() != chars_needed(bits) {
+        return Err(Base44Error::InvalidLength);
+    }
+    let mut out = [0u8; N];
+    if bits <= 128 {
+        let mut value = 0u128;
+        for &ch in s.as_bytes() {
+            let digit = b44_val(ch).ok_or(Base44Error::InvalidChar)?;
+            value = value
+                .checked_mul(44)
+                .and_then(|v| v.checked_add(u128::from(digit)))
+                .ok_or(Base44Error::Overflow)?;
+        }
+        if bits < 128 && value >> bits != 0 {
+            return Err(Base44Error::Overflow);
+        }
+        out.copy_from_slice(&value.to_le_bytes()[..N]);
+    } else {
+        // Multiply-accumulate straight into the little-endian output bytes.
+        for &ch in s.as_bytes() {
+            let mut carry = u32::from(b44_val(ch).ok_or(Base44Error::InvalidChar)?);
+            for byte in &mut out {
+                let v = u32::from(*byte) * 44 + carry;
+                *byte = v as u8;
+                carry = v >> 8;
+            }
+            if carry != 0 {
+                return Err(Base44Error::Overflow);
+            }
+        }
+        if bits % 8 != 0 && out[N - 1] >> (bits % 8) != 0 {
+            return Err(Base44Error::Overflow);
+        }
+    }
+    Ok(out)
+}
+
+/// Decode `s` holding exactly `N * 8` bits into an array.
+pub(crate) fn decode_array<const N: usize>(s: &str) -> Result<[u8; N], Base44Error> {
+    decode_array_bits(N * 8, s)
+}
+
+/// Encode all `N * 8` bits of an array.
+pub(crate) fn encode_array<const N: usize>(bytes: &[u8; N]) -> String {
+    if N == 0 {
+        String::new()
+    } else {
+        encode_bits(N * 8, bytes)
+    }
+}
+
+/// Decode `s` straight into `[u8; N]`, for any `N`.
+///
+/// `mode` selects the packing `s` was written in: [`Mode::Optimal`] expects the
+/// optimal length for `N * 8` bits (as [`Base44Array`]), [`Mode::BytePair`] and
+/// [`Mode::Sortable`] the byte-pair length of `N` bytes. Any other length is
+/// [`Base44Error::InvalidLength`]. Every mode decodes straight into the
+/// returned array without allocating.
+///
+/// ```
+/// use qr_base44::{Mode, decode_fixed, encode, encode_128bits};
+///
+/// let key = [0x5A; 16];
+/// let pair: [u8; 16] = decode_fixed(&encode(&key), Mode::BytePair).unwrap();
+/// let optimal = decode_fixed::<16>(&encode_128bits(&key), Mode::Optimal).unwrap();
+/// assert_eq!((pair, optimal), (key, key));
+/// assert!(decode_fixed::<15>(&encode(&key), Mode::BytePair).is_err());
+/// ```
+pub fn decode_fixed<const N: usize>(s: &str, mode: Mode) -> Result<[u8; N], Base44Error> {
+    if mode == Mode::Optimal {
+        return decode_array(s);
+    }
+    if s.len() != encoded_len(N) {
+        return Err(Base44Error::InvalidLength);
+    }
+    let mut out = [0u8; N];
+    match mode {
+        Mode::Sortable => decode_sortable_into(s.as_bytes(), &mut out)?,
+        _ => decode_into(s.as_bytes(), &mut out)?,
+    }
+    Ok(out)
+}
+
+/// Encode 8 bytes (e.g. a 64-bit nonce) into 12 characters.
+pub fn encode_64bits(bytes: &[u8; 8]) -> String {
+    encode_bits(64, bytes)
+}
+
+/// Decode the 12-character output of [`encode_64bits`].
+pub fn decode_64bits(s: &str) -> Result<[u8; 8], Base44Error> {
+    decode_array(s)
+}
+
+/// Encode 103 bits (13 bytes, top bit of the last byte unused) into 19 characters.
+///
+/// This is the compressed-UUID layout used by qr-url.
+///
+/// # Panics
+///
+/// Panics if the unused top bit of `bytes[12]` is set; see [`try_encode_bits`](crate::try_encode_bits).
+pub fn encode_103bits(bytes: &[u8; 13]) -> String {
+    encode_bits(103, bytes)
+}
+
+/// Decode the 19-character output of [`encode_103bits`].
+pub fn decode_103bits(s: &str) -> Result<[u8; 13], Base44Error> {
+    decode_array_bits(103, s)
+}
+
+/// Encode 16 bytes (e.g. a UUID or AES-128 key) into 24 characters.
+pub fn encode_128bits(bytes: &[u8; 16]) -> String {
+    encode_bits(128, bytes)
+}
+
+/// Decode the 24-character output of [`encode_128bits`].
+pub fn decode_128bits(s: &str) -> Result<[u8; 16], Base44Error> {
+    decode_array(s)
+}
+
+/// Encode a full 128-bit value (an IPv6 address, UUID or trace ID as an
+/// integer) into exactly 24 characters, most-significant digit first.
+///
+/// The same as [`encode_128bits`] of the little-endian bytes, so the two
+/// decoders accept each other's output.
+///
+/// ```
+/// use std::net::Ipv6Addr;
+/// use qr_base44::{decode_u128, encode_u128};
+///
+/// let addr: Ipv6Addr = "2001:db8::1".parse().unwrap();
+/// let s = encode_u128(addr.to_bits());
+/// assert_eq!(s.len(), 24);
+/// assert_eq!(Ipv6Addr::from_bits(decode_u128(&s).unwrap()), addr);
+/// ```
+pub fn encode_u128(value: u128) -> String {
+    encode_bits(128, &value.to_le_bytes())
+}
+
+/// Decode the 24-character output of [`encode_u128`].
+///
+/// Any other length is [`Base44Error::InvalidLength`], and a 24-character
+/// string above `u128::MAX` (`44^24 > 2^128`) is [`Base44Error::Overflow`].
+pub fn decode_u128(s: &str) -> Result<u128, Base44Error> {
+    decode_128bits(s).map(u128::from_le_bytes)
+}
+
+/// Encode 32 bytes (e.g. a SHA-256 digest or AES-256 key) into 47 characters.
+pub fn encode_256bits(bytes: &[u8; 32]) -> String {
+    encode_bits(256, bytes)
+}
+
+/// Decode the 47-character output of [`encode_256bits`].
+pub fn decode_256bits(s: &str) -> Result<[u8; 32], Base44Error> {
+    decode_array(s)
+}
+
+/// Iterate over concatenated 19-character [`encode_103bits`] records.
+///
+/// Each record is validated independently, so one corrupt sample does not hide
+/// the rest. A trailing partial record yields [`Base44Error::InvalidLength`].
+///
+/// ```
+/// use qr_base44::{encode_103bits, iter_frames_103};
+///
+/// let packed = encode_103bits(&[1; 13]) + &encode_103bits(&[2; 13]);
+/// let samples: Vec<[u8; 13]> = iter_frames_103(&packed).collect::<Result<_, _>>().unwrap();
+/// assert_eq!(samples, [[1; 13], [2; 13]]);
+/// ```
+pub fn iter_frames_103(s: &str) -> impl Iterator<Item = Result<[u8; 13], Base44Error>> + '_ {
+    s.as_bytes().chunks(chars_needed(103)).map(|frame| {
+        let frame = core::str::from_utf8(frame).map_err(|_| Base44Error::InvalidChar)?;
+        decode_103bits(frame)
+    })
+}
+
+/// A byte array that converts to and from its optimal Base44 form
+/// (all `N * 8` bits, see [`encode_bits`]).
+///
+/// ```
+/// use qr_base44::Base44Array;
+///
+/// let key = Base44Array([0xAB; 16]);
+/// let s = key.to_string();
+/// assert_eq!(s.len(), 24);
+/// let back: [u8; 16] = Base44Array::try_from(s.as_str()).unwrap().into();
+/// assert_eq!(back, [0xAB; 16]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Base44Array<const N: usize>(pub [u8; N]);
+
+impl<const N: usize> fmt::Display for Base44Array<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&encode_array(&self.0))
+    }
+}
+
+impl<const N: usize> FromStr for Base44Array<N> {
+    type Err = Base44Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        decode_array(s).map(Self)
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for Base44Array<N> {
+    type Error = Base44Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for Base44Array<N> {
+    fn from(bytes: [u8; N]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl<const N: usize> From<Base44Array<N>> for [u8; N] {
+    fn from(array: Base44Array<N>) -> Self {
+        array.0
+    }
+}
+
+/// A 103-bit value stored in 13 bytes, converting to and from its 19-character
+/// form via [`encode_103bits`]/[`decode_103bits`].
+///
+/// The unused top bit of the last byte is always clear.
+///
+/// ```
+/// use qr_base44::Bits103;
+///
+/// let id: Bits103 = "0123456789ABCDEFGHI".try_into().unwrap();
+/// assert_eq!(id.to_string(), "0123456789ABCDEFGHI");
+/// let bytes: [u8; 13] = id.into();
+/// assert!(Bits103::new([0xFF; 13]).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bits103([u8; 13]);
+
+impl Bits103 {
+    /// Wrap `bytes`, rejecting a set top bit with [`Base44Error::NonZeroPadding`].
+    pub fn new(bytes: [u8; 13]) -> Result<Self, Base44Error> {
+        if bytes[12] & 0x80 != 0 {
+            return Err(Base44Error::NonZeroPadding);
+        }
+        Ok(Self(bytes))
+    }
+
+    /// The 13 underlying bytes.
+    pub fn as_bytes(&self) -> &[u8; 13] {
+        &self.0
+    }
+}
+
+impl TryFrom<[u8; 13]> for Bits103 {
+    type Error = Base44Error;
+
+    fn try_from(bytes: [u8; 13]) -> Result<Self, Self::Error> {
+        Self::new(bytes)
+    }
+}
+
+impl fmt::Display for Bits103 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&encode_103bits(&self.0))
+    }
+}
+
+impl FromStr for Bits103 {
+    type Err = Base44Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        decode_103bits(s).map(Self)
+    }
+}
+
+impl TryFrom<&str> for Bits103 {
+    type Error = Base44Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Bits103> for [u8; 13] {
+    fn from(bits: Bits103) -> Self {
+        bits.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_103_are_validated_independently() {
+        let mut max = [0xFF; 13];
+        max[12] = 0x7F;
+        let packed = [
+            encode_103bits(&[0; 13]),
+            "a".repeat(19),
+            encode_103bits(&max),
+        ]
+        .concat();
+        let frames: Vec<_> = iter_frames_103(&packed).collect();
+        assert_eq!(
+            frames,
+            [Ok([0; 13]), Err(Base44Error::InvalidChar), Ok(max)]
+        );
+        let partial: Vec<_> = iter_frames_103(&packed[..45]).collect();
+        assert_eq!(partial[2], Err(Base44Error::InvalidLength));
+        assert_eq!(iter_frames_103("").count(), 0);
+    }
+
+    #[test]
+    fn preset_sizes_roundtrip() {
+        let k8 = [0xFFu8; 8];
+        let k16: [u8; 16] = core::array::from_fn(|i| i as u8);
+        let k32 = [0xA5u8; 32];
+        let (s8, s16, s32) = (
+            encode_64bits(&k8),
+            encode_128bits(&k16),
+            encode_256bits(&k32),
+        );
+        assert_eq!((s8.len(), s16.len(), s32.len()), (12, 24, 47));
+        assert_eq!(decode_64bits(&s8).unwrap(), k8);
+        assert_eq!(decode_128bits(&s16).unwrap(), k16);
+        assert_eq!(decode_256bits(&s32).unwrap(), k32);
+    }
+
+    #[test]
+    fn decode_fixed_checks_length_per_mode() {
+        let data: [u8; 7] = core::array::from_fn(|i| 0xF0 | i as u8);
+        let pair = crate::encode(&data);
+        let sortable = crate::encode_sortable(&data);
+        let optimal = encode_array(&data);
+        assert_eq!(decode_fixed(&pair, Mode::BytePair), Ok(data));
+        assert_eq!(decode_fixed(&sortable, Mode::Sortable), Ok(data));
+        assert_eq!(decode_fixed(&optimal, Mode::Optimal), Ok(data));
+        assert_eq!(
+            decode_fixed::<6>(&pair, Mode::BytePair),
+            Err(Base44Error::InvalidLength)
+        );
+        assert_eq!(
+            decode_fixed::<32>(&crate::encode(&[0; 32]), Mode::Optimal),
+            Err(Base44Error::InvalidLength)
+        );
+        assert_eq!(
+            decode_fixed::<2>("ZZZ", Mode::BytePair),
+            Err(Base44Error::Overflow)
+        );
+        assert_eq!(decode_fixed::<0>("", Mode::BytePair), Ok([]));
+    }
+
+    #[test]
+    fn array_decoding_matches_decode_bits() {
+        for bits in [1, 7, 8, 63, 64, 65, 103, 127, 128, 129, 135, 256] {
+            let max = chars_needed(bits);
+            for s in ["0".repeat(max), "Z".repeat(max), ":".repeat(max)] {
+                let expected = crate::decode_bits(bits, &s);
+                let got = match bits.div_ceil(8) {
+                    1 => decode_array_bits::<1>(bits, &s).map(|a| a.to_vec()),
+                    8 => decode_array_bits::<8>(bits, &s).map(|a| a.to_vec()),
+                    9 => decode_array_bits::<9>(bits, &s).map(|a| a.to_vec()),
+                    13 => decode_array_bits::<13>(bits, &s).map(|a| a.to_vec()),
+                    16 => decode_array_bits::<16>(bits, &s).map(|a| a.to_vec()),
+                    17 => decode_array_bits::<17>(bits, &s).map(|a| a.to_vec()),
+                    _ => decode_array_bits::<32>(bits, &s).map(|a| a.to_vec()),
+                };
+                assert_eq!(got, expected, "{bits} bits, {s}");
+            }
+        }
+        let k32: [u8; 32] = core::array::from_fn(|i| (i * 37) as u8);
+        assert_eq!(decode_256bits(&encode_256bits(&k32)), Ok(k32));
+    }
+
+    #[test]
+    fn u128_codec() {
+        for value in [0, 1, 43, 44, u64::MAX as u128, u128::MAX / 3, u128::MAX] {
+            let s = encode_u128(value);
+            assert_eq!(s.len(), 24);
+            assert_eq!(decode_u128(&s), Ok(value));
+            assert_eq!(decode_128bits(&s), Ok(value.to_le_bytes()));
+        }
+        assert_eq!(encode_u128(0), "0".repeat(24));
+        assert_eq!(
+            decode_u128(&"0".repeat(23)),
+            Err(Base44Error::InvalidLength)
+        );
+        assert_eq!(
+            decode_u128(&"0".repeat(25)),
+            Err(Base44Error::InvalidLength)
+        );
+        assert_eq!(decode_u128(&":".repeat(24)), Err(Base44Error::Overflow));
+    }
+
+    #[test]
+    fn bits103_roundtrip_and_length() {
+        let mut max = [0xFFu8; 13];
+        max[12] = 0x7F;
+        let s = encode_103bits(&max);
+        assert_eq!(s.len(), 19);
+        assert_eq!(decode_103bits(&s).unwrap(), max);
+        assert!(matches!(
+            decode_103bits(&encode_bits(104, &[0; 13])),
+            Err(Base44Error::InvalidLength)
+        ));
+    }
+
+    #[test]
+    fn conversion_traits() {
+        fn parse<T: for<'a> TryFrom<&'a str, Error = Base44Error>>(s: &str) -> T {
+            T::try_from(s).unwrap()
+        }
+        let arr: Base44Array<8> = parse(&encode_64bits(&[9; 8]));
+        assert_eq!(<[u8; 8]>::from(arr), [9; 8]);
+        let id: Bits103 = parse("0123456789ABCDEFGHI");
+        assert_eq!(id.to_string().parse::<Bits103>().unwrap(), id);
+        assert_eq!(Base44Array([0u8; 0]).to_string(), "");
+        assert_eq!("".parse::<Base44Array<0>>().unwrap(), Base44Array([]));
+        assert!("".parse::<Base44Array<1>>().is_err());
+    }
+
+    #[test]
+    fn wrong_lengths_are_rejected() {
+        let s = encode_128bits(&[7; 16]);
+        assert!(matches!(
+            decode_128bits(&s[1..]),
+            Err(Base44Error::InvalidLength)
+        ));
+        assert!(matches!(decode_64bits(&s), Err(Base44Error::InvalidLength)));
+        assert!(matches!(
+            decode_64bits("::::::::::::"),
+            Err(Base44Error::Overflow)
+        ));
+    }
+}
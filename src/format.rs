@@ -0,0 +1,128 @@
+//! Presentation formats that wrap encoded text in characters outside the alphabet.
+
+use crate::engine::insert_separators;
+use crate::{Base44Error, decode, encode};
+
+/// Split encoded text into space-separated groups of `group` characters.
+///
+/// Space is the one Base45 character the Base44 alphabet excludes, so the
+/// separators can never be mistaken for payload. Decode with [`decode_grouped`].
+///
+/// ```
+/// use qr_base44::{decode_grouped, encode, format_grouped};
+///
+/// let s = encode(b"Hello, World!");
+/// let pretty = format_grouped(&s, 4);
+/// assert_eq!(pretty, "9P9$ EE$U E7C4 IWES EEX0");
+/// assert_eq!(decode_grouped(&pretty).unwrap(), b"Hello, World!");
+/// ```
+///
+/// # Panics
+///
+/// Panics if `group` is zero.
+pub fn format_grouped(s: &str, group: usize) -> String {
+    assert!(group > 0, "group size must be non-zero");
+    insert_separators(s, group, ' ')
+}
+
+/// [`format_grouped`] that returns [`Base44Error::InvalidLength`] for a zero
+/// `group` instead of panicking.
+pub fn try_format_grouped(s: &str, group: usize) -> Result<String, Base44Error> {
+    match group {
+        0 => Err(Base44Error::InvalidLength),
+        _ => Ok(format_grouped(s, group)),
+    }
+}
+
+/// Decode byte-pair text after removing spaces, e.g. the output of [`format_grouped`].
+///
+/// Only U+0020 is stripped; any other whitespace is still [`Base44Error::InvalidChar`].
+pub fn decode_grouped(s: &str) -> Result<Vec<u8>, Base44Error> {
+    if s.contains(' ') {
+        decode(&s.replace(' ', ""))
+    } else {
+        decode(s)
+    }
+}
+
+/// Encode `input` and break the output into lines of at most `width` characters.
+///
+/// Lines are joined with `\n` and there is no trailing newline, for embedding in
+/// config files, emails and PEM-like documents. Decode with [`decode_wrapped`].
+///
+/// ```
+/// use qr_base44::{decode_wrapped, encode_wrapped};
+///
+/// let text = encode_wrapped(b"Hello, World!", 8);
+/// assert_eq!(text, "9P9$EE$U\nE7C4IWES\nEEX0");
+/// assert_eq!(decode_wrapped(&text).unwrap(), b"Hello, World!");
+/// ```
+///
+/// # Panics
+///
+/// Panics if `width` is zero.
+pub fn encode_wrapped(input: &[u8], width: usize) -> String {
+    assert!(width > 0, "line width must be non-zero");
+    insert_separators(&encode(input), width, '\n')
+}
+
+/// [`encode_wrapped`] that returns [`Base44Error::InvalidLength`] for a zero
+/// `width` instead of panicking.
+pub fn try_encode_wrapped(input: &[u8], width: usize) -> Result<String, Base44Error> {
+    match width {
+        0 => Err(Base44Error::InvalidLength),
+        _ => Ok(encode_wrapped(input, width)),
+    }
+}
+
+/// Decode byte-pair text after removing line breaks (`\n` and `\r`), e.g. the
+/// output of [`encode_wrapped`] after passing through a system with CRLF endings.
+pub fn decode_wrapped(s: &str) -> Result<Vec<u8>, Base44Error> {
+    let is_break = |c| c == '\n' || c == '\r';
+    if s.contains(is_break) {
+        decode(&s.replace(is_break, ""))
+    } else {
+        decode(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grouped_roundtrip_and_strictness() {
+        let data: Vec<u8> = (0..=40).collect();
+        let s = encode(&data);
+        for group in [1, 3, 5, s.len(), s.len() + 1] {
+            let pretty = format_grouped(&s, group);
+            assert_eq!(pretty.replace(' ', ""), s);
+            assert!(pretty.split(' ').all(|g| g.len() <= group));
+            assert_eq!(decode_grouped(&pretty).unwrap(), data);
+        }
+        assert_eq!(format_grouped("", 4), "");
+        assert_eq!(decode_grouped(" LZD ").unwrap(), b"hi");
+        assert_eq!(decode_grouped("LZ\tD"), Err(Base44Error::InvalidChar));
+    }
+
+    #[test]
+    fn wrapped_roundtrip() {
+        let data: Vec<u8> = (0..=200).collect();
+        for width in [1, 64, 76, 1000] {
+            let text = encode_wrapped(&data, width);
+            assert!(text.lines().all(|l| !l.is_empty() && l.len() <= width));
+            assert!(!text.ends_with('\n'));
+            assert_eq!(decode_wrapped(&text).unwrap(), data);
+            let crlf = text.replace('\n', "\r\n") + "\r\n";
+            assert_eq!(decode_wrapped(&crlf).unwrap(), data);
+        }
+        assert_eq!(encode_wrapped(b"", 4), "");
+        assert_eq!(decode_wrapped("LZ D"), Err(Base44Error::InvalidChar));
+    }
+
+    #[test]
+    #[should_panic(expected = "group size must be non-zero")]
+    fn zero_group_panics() {
+        format_grouped("LZD", 0);
+    }
+}
@@ -0,0 +1,277 @@
+//! RaptorQ (RFC 6330) erasure-coded multi-part codes ([`raptorq`] feature).
+//!
+//! [`encode_raptorq`] splits a payload into parts, each small enough for one QR
+//! code, and adds repair parts. A receiver scans parts in any order, skipping
+//! any it missed, and [`RaptorQDecoder`] rebuilds the payload once it has seen
+//! about as many parts as there were source parts. RFC 6330 puts the chance
+//! that exactly that many parts are not enough below 1%, and each extra part
+//! divides it by roughly 100, so the number of repair parts to print follows
+//! directly from the loss rate of the channel and the failure rate you accept.
+//!
+//! Every part is self-contained: before byte-pair encoding it holds the 12-byte
+//! RFC 6330 transmission header, the 4-byte payload ID, one symbol of data and a
+//! big-endian CRC-32 of all of that. A misread part fails its checksum and is
+//! rejected rather than corrupting the result, since RaptorQ only corrects
+//! erasures.
+
+use core::fmt;
+
+use raptorq::{Decoder, Encoder, EncodingPacket, ObjectTransmissionInformation};
+
+use crate::crc::crc32;
+use crate::{Base44Error, MAX_INFLATED_LEN, decode, encode};
+
+/// Transmission header bytes.
+const OTI_LEN: usize = 12;
+/// Payload ID bytes: source block number and encoding symbol ID.
+const ID_LEN: usize = 4;
+/// Bytes a part adds around its symbol.
+const OVERHEAD: usize = OTI_LEN + ID_LEN + 4;
+/// Symbol alignment RaptorQ uses.
+const ALIGN: u16 = 8;
+/// Smallest symbol RaptorQ derives parameters for: eight aligned sub-symbols.
+const MIN_SYMBOL: u16 = 64;
+
+/// Why [`RaptorQDecoder::push`] rejected a part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RaptorQError {
+    /// The part is not valid Base44, is too short, or fails its checksum.
+    Decode(Base44Error),
+    /// The transmission header is not one [`encode_raptorq`] writes, or
+    /// announces more than [`MAX_INFLATED_LEN`] bytes.
+    InvalidHeader,
+    /// The part belongs to a different transfer than the parts before it.
+    OtherTransfer,
+}
+
+impl fmt::Display for RaptorQError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RaptorQError::Decode(err) => err.fmt(f),
+            RaptorQError::InvalidHeader => f.write_str("invalid RaptorQ transmission header"),
+            RaptorQError::OtherTransfer => f.write_str("part belongs to a different transfer"),
+        }
+    }
+}
+
+impl core::error::Error for RaptorQError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            RaptorQError::Decode(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<Base44Error> for RaptorQError {
+    fn from(err: Base44Error) -> Self {
+        RaptorQError::Decode(err)
+    }
+}
+
+/// Split `data` into `ceil(len / symbol_size)` source parts of `symbol_size`
+/// data bytes each, followed by `repair` repair parts.
+///
+/// Each part is [`encoded_len`](crate::encoded_len)`(symbol_size + 20)`
+/// characters. Payloads up to [`MAX_INFLATED_LEN`] fit one RaptorQ source
+/// block, so the overhead is exactly `repair` parts.
+///
+/// # Panics
+///
+/// Panics if `data` is empty or longer than [`MAX_INFLATED_LEN`], or if
+/// `symbol_size` is not a multiple of 8 of at least 64.
+///
+/// ```
+/// use qr_base44::{RaptorQDecoder, encode_raptorq};
+///
+/// let data: Vec<u8> = (0..=255).collect();
+/// let parts = encode_raptorq(&data, 64, 2); // 4 source parts + 2 repair parts
+/// assert_eq!(parts.len(), 6);
+///
+/// // Two parts were never scanned.
+/// let mut decoder = RaptorQDecoder::new();
+/// let mut out = None;
+/// for part in &parts[2..] {
+///     out = decoder.push(part).unwrap();
+/// }
+/// assert_eq!(out.unwrap(), data);
+/// ```
+pub fn encode_raptorq(data: &[u8], symbol_size: u16, repair: u32) -> Vec<String> {
+    assert!(
+        !data.is_empty() && data.len() <= MAX_INFLATED_LEN,
+        "RaptorQ payload must be 1..=MAX_INFLATED_LEN bytes"
+    );
+    assert!(
+        symbol_size >= MIN_SYMBOL && symbol_size % ALIGN == 0,
+        "RaptorQ symbol size must be a multiple of 8, at least 64"
+    );
+    let encoder = Encoder::with_defaults(data, symbol_size);
+    let oti = encoder.get_config().serialize();
+    encoder
+        .get_encoded_packets(repair)
+        .iter()
+        .map(|packet| {
+            let mut bytes = Vec::with_capacity(OVERHEAD + usize::from(symbol_size));
+            bytes.extend_from_slice(&oti);
+            bytes.extend_from_slice(&packet.serialize());
+            let check = crc32(&bytes).to_be_bytes();
+            bytes.extend_from_slice(&check);
+            encode(&bytes)
+        })
+        .collect()
+}
+
+/// Reassembles a payload from [`encode_raptorq`] parts, in any order.
+///
+/// The first accepted part fixes the transfer; parts of another transfer are
+/// [`RaptorQError::OtherTransfer`]. Rejected parts leave the decoder unchanged,
+/// so a receiver can report them and keep scanning.
+#[derive(Debug, Default)]
+pub struct RaptorQDecoder {
+    transfer: Option<(ObjectTransmissionInformation, Decoder)>,
+    result: Option<Vec<u8>>,
+    accepted: usize,
+}
+
+impl RaptorQDecoder {
+    /// A decoder that has seen no parts.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parts accepted so far, duplicates included.
+    pub fn accepted(&self) -> usize {
+        self.accepted
+    }
+
+    /// Whether the payload has been rebuilt.
+    pub fn is_complete(&self) -> bool {
+        self.result.is_some()
+    }
+
+    /// Add one part. Returns the payload once enough parts have arrived, and
+    /// again for every part after that.
+    pub fn push(&mut self, part: &str) -> Result<Option<Vec<u8>>, RaptorQError> {
+        let mut bytes = decode(part)?;
+        let body_len = bytes
+            .len()
+            .checked_sub(4)
+            .filter(|&len| len > OTI_LEN + ID_LEN)
+            .ok_or(Base44Error::InvalidLength)?;
+        let check = bytes.split_off(body_len);
+        if check != crc32(&bytes).to_be_bytes() {
+            return Err(Base44Error::ChecksumMismatch.into());
+        }
+        let oti = header(&bytes)?;
+        let packet = EncodingPacket::deserialize(&bytes[OTI_LEN..]);
+        if usize::from(packet.payload_id().source_block_number())
+            >= usize::from(oti.source_blocks())
+            || packet.data().len() != usize::from(oti.symbol_size())
+        {
+            return Err(RaptorQError::InvalidHeader);
+        }
+        let (expected, decoder) = self
+            .transfer
+            .get_or_insert_with(|| (oti, Decoder::new(oti)));
+        if *expected != oti {
+            return Err(RaptorQError::OtherTransfer);
+        }
+        self.accepted += 1;
+        if self.result.is_none() {
+            self.result = decoder.decode(packet);
+        }
+        Ok(self.result.clone())
+    }
+}
+
+/// The transmission header of a part, if [`encode_raptorq`] could have written
+/// it. Comparing against the parameters the encoder derives rules out headers
+/// that would make [`Decoder::new`] panic or allocate without bound.
+fn header(bytes: &[u8]) -> Result<ObjectTransmissionInformation, RaptorQError> {
+    let raw: &[u8; OTI_LEN] = bytes[..OTI_LEN].try_into().expect("length checked");
+    let oti = ObjectTransmissionInformation::deserialize(raw);
+    let len = oti.transfer_length();
+    let symbol_size = oti.symbol_size();
+    let plausible = len > 0
+        && len <= MAX_INFLATED_LEN as u64
+        && symbol_size >= MIN_SYMBOL
+        && symbol_size % ALIGN == 0;
+    match plausible && oti == ObjectTransmissionInformation::with_defaults(len, symbol_size) {
+        true => Ok(oti),
+        false => Err(RaptorQError::InvalidHeader),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 131 + 7) as u8).collect()
+    }
+
+    #[test]
+    fn recovers_from_any_lost_parts() {
+        let data = payload(1000);
+        let parts = encode_raptorq(&data, 96, 4);
+        assert_eq!(parts.len(), 11 + 4);
+        assert!(
+            parts
+                .iter()
+                .all(|p| p.len() == crate::encoded_len(96 + OVERHEAD))
+        );
+        // Lose three parts, source ones included, and feed the rest backwards.
+        let mut decoder = RaptorQDecoder::new();
+        let mut out = None;
+        for (_, part) in parts.iter().rev().enumerate().filter(|(i, _)| i % 5 != 2) {
+            out = decoder.push(part).unwrap();
+        }
+        assert!(decoder.is_complete());
+        assert_eq!(out.unwrap(), data);
+        assert_eq!(decoder.accepted(), 12);
+
+        // Source parts alone are the original data, so they always suffice.
+        let mut decoder = RaptorQDecoder::new();
+        let out = parts[..11].iter().map(|p| decoder.push(p).unwrap()).last();
+        assert_eq!(out.unwrap().unwrap(), data);
+    }
+
+    #[test]
+    fn rejects_bad_parts() {
+        let parts = encode_raptorq(&payload(100), 64, 1);
+        let mut decoder = RaptorQDecoder::new();
+        assert_eq!(decoder.push(&parts[0]), Ok(None));
+
+        let mut bytes = decode(&parts[1]).unwrap();
+        bytes[20] ^= 1;
+        assert_eq!(
+            decoder.push(&encode(&bytes)),
+            Err(RaptorQError::Decode(Base44Error::ChecksumMismatch))
+        );
+        assert_eq!(
+            decoder.push("LZD"),
+            Err(RaptorQError::Decode(Base44Error::InvalidLength))
+        );
+
+        let other = encode_raptorq(&payload(101), 64, 0);
+        assert_eq!(decoder.push(&other[0]), Err(RaptorQError::OtherTransfer));
+
+        // A well-formed part with a header the encoder never writes.
+        let mut bytes = decode(&parts[1]).unwrap();
+        bytes.truncate(bytes.len() - 4);
+        bytes[7] = 0; // symbol size 0
+        bytes.extend_from_slice(&crc32(&bytes).to_be_bytes());
+        assert_eq!(
+            RaptorQDecoder::new().push(&encode(&bytes)),
+            Err(RaptorQError::InvalidHeader)
+        );
+        assert_eq!(decoder.accepted(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "multiple of 8")]
+    fn symbol_size_is_aligned() {
+        let _ = encode_raptorq(b"data", 68, 0);
+    }
+}
@@ -0,0 +1,256 @@
+//! COBS-style framing with an in-band delimiter.
+//!
+//! One alphabet character is reserved as a frame delimiter (the sentinel). The
+//! encoded payload is split at each occurrence of the sentinel, and every block is
+//! prefixed with a code character giving its length, exactly as Consistent
+//! Overhead Byte Stuffing does with zero bytes. The sentinel therefore only ever
+//! appears between frames, so a receiver can resynchronise after line noise by
+//! skipping to the next one. Overhead is one character per 42, plus the delimiter.
+//!
+//! ```
+//! use qr_base44::Framer;
+//!
+//! let framer = Framer::default(); // ':' delimiter
+//! let mut link = framer.frame(b"first");
+//! link.push_str(&framer.frame(b"second"));
+//! let frames: Vec<Vec<u8>> = framer.frames(&link).collect::<Result<_, _>>().unwrap();
+//! assert_eq!(frames, [b"first".to_vec(), b"second".to_vec()]);
+//! ```
+
+use crate::{BASE44_ALPHABET, Base44Error, Observer, b44_val, encode};
+
+/// Largest code: a full block of 42 characters with no sentinel after it.
+const MAX_CODE: usize = 43;
+
+/// Frames payloads with a reserved delimiter character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Framer {
+    sentinel: u8,
+}
+
+impl Default for Framer {
+    /// `:` as the delimiter.
+    fn default() -> Self {
+        Self::new(':')
+    }
+}
+
+impl Framer {
+    /// Use `sentinel` as the frame delimiter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sentinel` is not a Base44 alphabet character.
+    pub const fn new(sentinel: char) -> Self {
+        assert!(
+            sentinel.is_ascii() && b44_val(sentinel as u8).is_some(),
+            "sentinel must be a base44 alphabet character"
+        );
+        Self {
+            sentinel: sentinel as u8,
+        }
+    }
+
+    /// [`new`](Self::new), or `None` if `sentinel` is not a Base44 alphabet
+    /// character.
+    pub const fn try_new(sentinel: char) -> Option<Self> {
+        match sentinel.is_ascii() && b44_val(sentinel as u8).is_some() {
+            true => Some(Self::new(sentinel)),
+            false => None,
+        }
+    }
+
+    /// The delimiter character.
+    pub fn sentinel(&self) -> char {
+        self.sentinel as char
+    }
+
+    /// Character for block code `k` (1..=43): the `k`-th non-sentinel character.
+    fn code_char(&self, k: usize) -> u8 {
+        let sentinel = b44_val(self.sentinel).expect("checked in new") as usize;
+        BASE44_ALPHABET[if k - 1 < sentinel { k - 1 } else { k }]
+    }
+
+    fn code_value(&self, ch: u8) -> Option<usize> {
+        let val = b44_val(ch)? as usize;
+        let sentinel = b44_val(self.sentinel)? as usize;
+        match val.cmp(&sentinel) {
+            core::cmp::Ordering::Less => Some(val + 1),
+            core::cmp::Ordering::Equal => None,
+            core::cmp::Ordering::Greater => Some(val),
+        }
+    }
+
+    /// Encode `payload` and stuff it into one delimited frame.
+    pub fn frame(&self, payload: &[u8]) -> String {
+        let text = encode(payload);
+        let mut out = Vec::with_capacity(text.len() + text.len() / 42 + 2);
+        let mut code_at = out.len();
+        out.push(0);
+        let mut run = 0;
+        for &ch in text.as_bytes() {
+            if ch == self.sentinel {
+                out[code_at] = self.code_char(run + 1);
+                code_at = out.len();
+                out.push(0);
+                run = 0;
+                continue;
+            }
+            out.push(ch);
+            run += 1;
+            if run == MAX_CODE - 1 {
+                out[code_at] = self.code_char(MAX_CODE);
+                code_at = out.len();
+                out.push(0);
+                run = 0;
+            }
+        }
+        out[code_at] = self.code_char(run + 1);
+        out.push(self.sentinel);
+        String::from_utf8(out).expect("framed output is ASCII")
+    }
+
+    /// Unstuff and decode one frame, with or without its trailing delimiter.
+    ///
+    /// Returns [`Base44Error::InvalidLength`] if a block runs past the end of the
+    /// frame and [`Base44Error::InvalidChar`] for a stray delimiter inside it.
+    pub fn unframe(&self, frame: &str) -> Result<Vec<u8>, Base44Error> {
+        let body = frame.as_bytes();
+        let body = body.strip_suffix(&[self.sentinel]).unwrap_or(body);
+        let mut text = Vec::with_capacity(body.len());
+        let mut i = 0;
+        while i < body.len() {
+            let k = self.code_value(body[i]).ok_or(Base44Error::InvalidChar)?;
+            let block = body.get(i + 1..i + k).ok_or(Base44Error::InvalidLength)?;
+            if block.contains(&self.sentinel) {
+                return Err(Base44Error::InvalidChar);
+            }
+            text.extend_from_slice(block);
+            i += k;
+            if k < MAX_CODE && i < body.len() {
+                text.push(self.sentinel);
+            }
+        }
+        crate::decode_bytes(&text)
+    }
+
+    /// Split a received stream at delimiters and unframe each non-empty frame.
+    ///
+    /// Empty frames (idle delimiters between frames) are skipped, and a partial
+    /// frame after the last delimiter is ignored until more input arrives.
+    pub fn frames<'a>(
+        &self,
+        stream: &'a str,
+    ) -> impl Iterator<Item = Result<Vec<u8>, Base44Error>> + 'a {
+        self.frames_with(stream, ())
+    }
+
+    /// [`frames`](Self::frames), reporting each completed frame and each rejected
+    /// one to `observer`.
+    pub fn frames_with<'a, O: Observer + 'a>(
+        &self,
+        stream: &'a str,
+        observer: O,
+    ) -> impl Iterator<Item = Result<Vec<u8>, Base44Error>> + 'a {
+        let framer = *self;
+        let complete = match stream.rfind(framer.sentinel as char) {
+            Some(end) => &stream[..end],
+            None => "",
+        };
+        complete
+            .split(framer.sentinel as char)
+            .filter(|frame| !frame.is_empty())
+            .map(move |frame| framer.unframe(frame))
+            .inspect(move |result| {
+                trace_frame(result);
+                match result {
+                    Ok(payload) => observer.frame_completed(payload.len()),
+                    Err(err) => observer.group_rejected(*err),
+                }
+            })
+    }
+}
+
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+fn trace_frame(result: &Result<Vec<u8>, Base44Error>) {
+    match result {
+        Ok(payload) => trace_event!(trace, bytes = payload.len(), "base44 frame received"),
+        Err(err) => trace_event!(debug, error = %err, "base44 frame rejected"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip_for_every_sentinel() {
+        let payloads: Vec<Vec<u8>> = vec![
+            vec![],
+            b"hi".to_vec(),
+            (0..=255).collect(),
+            vec![0xFF; 84], // long runs without any given character
+        ];
+        for &sentinel in BASE44_ALPHABET {
+            let framer = Framer::new(sentinel as char);
+            for payload in &payloads {
+                let frame = framer.frame(payload);
+                assert_eq!(frame.find(sentinel as char), Some(frame.len() - 1));
+                assert_eq!(framer.unframe(&frame).unwrap(), *payload);
+                assert_eq!(framer.unframe(&frame[..frame.len() - 1]).unwrap(), *payload);
+                assert!(frame.len() <= encode(payload).len() + encode(payload).len() / 42 + 2);
+            }
+        }
+    }
+
+    #[test]
+    fn code_characters_skip_the_sentinel() {
+        let framer = Framer::new('5');
+        for k in 1..=MAX_CODE {
+            let ch = framer.code_char(k);
+            assert_ne!(ch, b'5');
+            assert_eq!(framer.code_value(ch), Some(k));
+        }
+    }
+
+    #[test]
+    fn stream_resynchronises_after_noise() {
+        let framer = Framer::default();
+        let link = format!(
+            "{}::{}ZZ{}{}partial",
+            framer.frame(b"one"),
+            framer.frame(b"two"),
+            framer.frame(b"three"),
+            framer.frame(b"")
+        );
+        let frames: Vec<_> = framer.frames(&link).collect();
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames[0].as_deref(), Ok(&b"one"[..]));
+        assert_eq!(frames[1].as_deref(), Ok(&b"two"[..]));
+        assert!(frames[2].is_err()); // "ZZ" noise glued onto the third frame
+        assert_eq!(frames[3].as_deref(), Ok(&b""[..]));
+        assert_eq!(framer.unframe("9"), Err(Base44Error::InvalidLength));
+    }
+
+    #[test]
+    fn observer_counts_frames() {
+        use std::cell::Cell;
+
+        #[derive(Default)]
+        struct Count(Cell<usize>, Cell<usize>);
+        impl Observer for Count {
+            fn frame_completed(&self, len: usize) {
+                self.0.set(self.0.get() + len);
+            }
+            fn group_rejected(&self, _: Base44Error) {
+                self.1.set(self.1.get() + 1);
+            }
+        }
+
+        let framer = Framer::default();
+        let link = format!("{}ZZ{}", framer.frame(b"one"), framer.frame(b"three"));
+        let count = Count::default();
+        assert_eq!(framer.frames_with(&link, &count).count(), 2);
+        assert_eq!((count.0.get(), count.1.get()), (3, 1));
+    }
+}
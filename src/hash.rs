@@ -0,0 +1,160 @@
+//! Hash-then-encode helpers (enabled with the `digest` feature).
+
+use std::io::{self, Write};
+
+use digest::{Digest, Output};
+
+use crate::{DecoderWriter, EncoderWriter, encode_bits};
+
+/// Hash `data` with `D` and encode the digest with optimal packing.
+///
+/// Produces short, QR-scannable content fingerprints and cache keys in one call;
+/// a SHA-256 fingerprint is 47 characters.
+///
+/// ```
+/// use sha2::Sha256;
+///
+/// let fingerprint = qr_base44::encode_digest::<Sha256>(b"hello");
+/// assert_eq!(fingerprint.len(), 47);
+/// ```
+pub fn encode_digest<D: Digest>(data: impl AsRef<[u8]>) -> String {
+    let out = D::digest(data);
+    encode_bits(out.len() * 8, &out)
+}
+
+impl<W: Write> EncoderWriter<W> {
+    /// An encoder that also hashes the raw bytes written to it with `D`.
+    ///
+    /// ```
+    /// use sha2::{Digest, Sha256};
+    /// use std::io::Write;
+    /// use qr_base44::EncoderWriter;
+    ///
+    /// let mut enc = EncoderWriter::with_digest::<Sha256>(Vec::new());
+    /// enc.write_all(b"hello").unwrap();
+    /// let (text, hash) = enc.finish().unwrap();
+    /// assert_eq!(text, qr_base44::encode(b"hello").as_bytes());
+    /// assert_eq!(hash, Sha256::digest(b"hello"));
+    /// ```
+    pub fn with_digest<D: Digest>(inner: W) -> DigestEncoder<W, D> {
+        DigestEncoder {
+            encoder: EncoderWriter::new(inner),
+            digest: D::new(),
+        }
+    }
+}
+
+/// An [`EncoderWriter`] that hashes its input; see [`EncoderWriter::with_digest`].
+#[derive(Debug)]
+pub struct DigestEncoder<W, D> {
+    encoder: EncoderWriter<W>,
+    digest: D,
+}
+
+impl<W: Write, D: Digest> DigestEncoder<W, D> {
+    /// Finish encoding and return the inner writer with the digest of the input.
+    pub fn finish(self) -> io::Result<(W, Output<D>)> {
+        Ok((self.encoder.finish()?, self.digest.finalize()))
+    }
+}
+
+impl<W: Write, D: Digest> Write for DigestEncoder<W, D> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.encoder.write(buf)?;
+        self.digest.update(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.encoder.flush()
+    }
+}
+
+impl<W: Write> DecoderWriter<W> {
+    /// A decoder that also hashes the decoded bytes with `D`.
+    pub fn with_digest<D: Digest>(inner: W) -> DigestDecoder<W, D> {
+        DigestDecoder {
+            decoder: DecoderWriter::new(Hashing {
+                inner,
+                digest: D::new(),
+            }),
+        }
+    }
+}
+
+/// A [`DecoderWriter`] that hashes its output; see [`DecoderWriter::with_digest`].
+#[derive(Debug)]
+pub struct DigestDecoder<W, D> {
+    decoder: DecoderWriter<Hashing<W, D>>,
+}
+
+impl<W: Write, D: Digest> DigestDecoder<W, D> {
+    /// Finish decoding and return the inner writer with the digest of the output.
+    pub fn finish(self) -> io::Result<(W, Output<D>)> {
+        let Hashing { inner, digest } = self.decoder.finish()?;
+        Ok((inner, digest.finalize()))
+    }
+}
+
+impl<W: Write, D: Digest> Write for DigestDecoder<W, D> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.decoder.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.decoder.flush()
+    }
+}
+
+/// Hashes everything successfully written through to `inner`.
+#[derive(Debug)]
+struct Hashing<W, D> {
+    inner: W,
+    digest: D,
+}
+
+impl<W: Write, D: Digest> Write for Hashing<W, D> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.digest.update(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha2::{Sha256, Sha512};
+
+    #[test]
+    fn digest_is_encoded_optimally() {
+        let fp = encode_digest::<Sha256>("abc");
+        assert_eq!(fp.len(), 47);
+        let raw = crate::decode_bits(256, &fp).unwrap();
+        assert_eq!(raw, Sha256::digest(b"abc").to_vec());
+        assert_eq!(encode_digest::<Sha512>(b"abc").len(), 94);
+    }
+
+    #[test]
+    fn streaming_digests_match_one_shot() {
+        let data: Vec<u8> = (0..3000u32).map(|i| (i % 251) as u8).collect();
+        let mut enc = EncoderWriter::with_digest::<Sha256>(Vec::new());
+        for part in data.chunks(700) {
+            enc.write_all(part).unwrap();
+        }
+        let (text, enc_hash) = enc.finish().unwrap();
+        assert_eq!(enc_hash, Sha256::digest(&data));
+
+        let mut dec = DecoderWriter::with_digest::<Sha256>(Vec::new());
+        for part in text.chunks(999) {
+            dec.write_all(part).unwrap();
+        }
+        let (bytes, dec_hash) = dec.finish().unwrap();
+        assert_eq!(bytes, data);
+        assert_eq!(dec_hash, enc_hash);
+    }
+}
@@ -0,0 +1,125 @@
+//! Bech32-style codes with a human-readable prefix (HRP): `ticket_<payload><check>`.
+//!
+//! The prefix names what the code is for, the separator can never occur in the
+//! payload, and the checksum covers the prefix too, so a valid payload pasted
+//! under the wrong prefix is rejected rather than misread.
+
+use crate::crc::crc32;
+use crate::{Base44Error, decode, encode};
+
+/// Separates the prefix from the payload. Outside the Base44 alphabet, and
+/// unreserved in URLs; the last occurrence is the separator, so prefixes may
+/// contain it.
+pub const HRP_SEPARATOR: char = '_';
+
+/// Longest accepted prefix, as in Bech32.
+const MAX_HRP_LEN: usize = 83;
+
+/// Check `hrp` is 1..=83 printable ASCII characters (`!` through `~`).
+fn check_hrp(hrp: &str) -> Result<(), Base44Error> {
+    if hrp.is_empty() || hrp.len() > MAX_HRP_LEN {
+        return Err(Base44Error::InvalidLength);
+    }
+    match hrp.bytes().all(|b| b.is_ascii_graphic()) {
+        true => Ok(()),
+        false => Err(Base44Error::InvalidChar),
+    }
+}
+
+/// CRC-32 over the prefix, a zero byte and the payload.
+fn checksum(hrp: &str, payload: &[u8]) -> [u8; 4] {
+    let mut data = Vec::with_capacity(hrp.len() + 1 + payload.len());
+    data.extend_from_slice(hrp.as_bytes());
+    data.push(0);
+    data.extend_from_slice(payload);
+    crc32(&data).to_be_bytes()
+}
+
+/// Encode `payload` under the prefix `hrp`, with a 6-character checksum
+/// covering both.
+///
+/// `hrp` must be 1 to 83 printable ASCII characters: an empty or longer prefix
+/// is [`Base44Error::InvalidLength`], and a space or non-ASCII character is
+/// [`Base44Error::InvalidChar`].
+///
+/// ```
+/// use qr_base44::{decode_with_hrp, encode_with_hrp};
+///
+/// let code = encode_with_hrp("ticket", b"hi").unwrap();
+/// assert!(code.starts_with("ticket_LZD"));
+/// assert_eq!(decode_with_hrp(&code).unwrap(), ("ticket", b"hi".to_vec()));
+/// ```
+pub fn encode_with_hrp(hrp: &str, payload: &[u8]) -> Result<String, Base44Error> {
+    check_hrp(hrp)?;
+    let mut body = Vec::with_capacity(payload.len() + 4);
+    body.extend_from_slice(payload);
+    body.extend_from_slice(&checksum(hrp, payload));
+    let mut out = String::with_capacity(hrp.len() + 1 + crate::encoded_len(body.len()));
+    out.push_str(hrp);
+    out.push(HRP_SEPARATOR);
+    out.push_str(&encode(&body));
+    Ok(out)
+}
+
+/// Split and verify a code from [`encode_with_hrp`], returning the prefix and
+/// the payload.
+///
+/// A missing separator or a body too short for the checksum is
+/// [`Base44Error::InvalidLength`]; a checksum that does not match the prefix and
+/// payload is [`Base44Error::ChecksumMismatch`].
+pub fn decode_with_hrp(s: &str) -> Result<(&str, Vec<u8>), Base44Error> {
+    let (hrp, body) = s
+        .rsplit_once(HRP_SEPARATOR)
+        .ok_or(Base44Error::InvalidLength)?;
+    check_hrp(hrp)?;
+    let mut payload = decode(body)?;
+    let check = payload
+        .len()
+        .checked_sub(4)
+        .ok_or(Base44Error::InvalidLength)?;
+    let expected = payload.split_off(check);
+    if expected != checksum(hrp, &payload) {
+        return Err(Base44Error::ChecksumMismatch);
+    }
+    Ok((hrp, payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefix_is_part_of_the_checksum() {
+        let code = encode_with_hrp("wifi", b"ssid=home").unwrap();
+        assert_eq!(
+            decode_with_hrp(&code).unwrap(),
+            ("wifi", b"ssid=home".to_vec())
+        );
+        let moved = code.replacen("wifi", "ticket", 1);
+        assert_eq!(decode_with_hrp(&moved), Err(Base44Error::ChecksumMismatch));
+
+        let empty = encode_with_hrp("a_b", b"").unwrap();
+        assert_eq!(empty.len(), "a_b_".len() + 6);
+        assert_eq!(decode_with_hrp(&empty).unwrap(), ("a_b", vec![]));
+    }
+
+    #[test]
+    fn malformed_codes() {
+        assert_eq!(encode_with_hrp("", b"x"), Err(Base44Error::InvalidLength));
+        assert_eq!(
+            encode_with_hrp("has space", b"x"),
+            Err(Base44Error::InvalidChar)
+        );
+        assert_eq!(
+            encode_with_hrp(&"x".repeat(84), b"x"),
+            Err(Base44Error::InvalidLength)
+        );
+        assert_eq!(decode_with_hrp("LZD"), Err(Base44Error::InvalidLength));
+        assert_eq!(decode_with_hrp("t_LZD"), Err(Base44Error::InvalidLength));
+        let code = encode_with_hrp("t", b"payload").unwrap();
+        let mut flipped = code.into_bytes();
+        flipped[3] = if flipped[3] == b'0' { b'1' } else { b'0' };
+        let flipped = String::from_utf8(flipped).unwrap();
+        assert!(decode_with_hrp(&flipped).is_err());
+    }
+}
@@ -0,0 +1,79 @@
+//! Minimal-length numeric short IDs.
+
+use crate::{BASE44_ALPHABET, Base44Error, b44_val};
+
+/// Encode `id` with as few characters as possible (most significant digit first).
+///
+/// Leading zero digits are dropped, so the output is 1 to 12 characters long and
+/// `0` encodes as `"0"`. Suited to URL shorteners and ticket numbers.
+///
+/// ```
+/// assert_eq!(qr_base44::encode_id(0), "0");
+/// assert_eq!(qr_base44::encode_id(43), ":");
+/// assert_eq!(qr_base44::encode_id(44), "10");
+/// assert_eq!(qr_base44::decode_id("10").unwrap(), 44);
+/// ```
+pub fn encode_id(mut id: u64) -> String {
+    let mut digits = [0u8; 12];
+    let mut start = digits.len();
+    loop {
+        start -= 1;
+        digits[start] = BASE44_ALPHABET[(id % 44) as usize];
+        id /= 44;
+        if id == 0 {
+            break;
+        }
+    }
+    digits[start..].iter().map(|&b| b as char).collect()
+}
+
+/// Decode a short ID produced by [`encode_id`].
+///
+/// Returns [`Base44Error::InvalidLength`] for an empty string and
+/// [`Base44Error::Overflow`] if the value does not fit in a `u64`. Leading `0`
+/// digits are accepted.
+pub fn decode_id(s: &str) -> Result<u64, Base44Error> {
+    if s.is_empty() {
+        return Err(Base44Error::InvalidLength);
+    }
+    s.bytes().try_fold(0u64, |acc, ch| {
+        let digit = b44_val(ch).ok_or(Base44Error::InvalidChar)?;
+        acc.checked_mul(44)
+            .and_then(|v| v.checked_add(digit as u64))
+            .ok_or(Base44Error::Overflow)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_roundtrip_with_minimal_length() {
+        for id in [0, 1, 43, 44, 1935, 1936, 123_456_789, u64::MAX] {
+            let s = encode_id(id);
+            assert_eq!(decode_id(&s).unwrap(), id);
+            assert!(s == "0" || !s.starts_with('0'));
+        }
+        assert_eq!(encode_id(u64::MAX).len(), 12);
+        assert_eq!(decode_id("00010").unwrap(), 44);
+    }
+
+    #[test]
+    fn id_errors() {
+        assert!(matches!(decode_id(""), Err(Base44Error::InvalidLength)));
+        assert!(matches!(decode_id("a"), Err(Base44Error::InvalidChar)));
+        // u64::MAX + 1
+        let mut over = encode_id(u64::MAX).into_bytes();
+        let last = over.len() - 1;
+        over[last] = BASE44_ALPHABET[b44_val(over[last]).unwrap() as usize + 1];
+        assert!(matches!(
+            decode_id(core::str::from_utf8(&over).unwrap()),
+            Err(Base44Error::Overflow)
+        ));
+        assert!(matches!(
+            decode_id("::::::::::::"),
+            Err(Base44Error::Overflow)
+        ));
+    }
+}
@@ -0,0 +1,122 @@
+//! Structured fuzzer input ([`arbitrary`] feature).
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{BASE44_ALPHABET, Base44String};
+
+impl<'a> Arbitrary<'a> for Base44String {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let bytes: Vec<u8> = u.arbitrary()?;
+        Ok(Self::from_bytes(&bytes))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <Vec<u8> as Arbitrary>::size_hint(depth)
+    }
+}
+
+/// A valid or near-valid Base44 string, for structured fuzzing.
+///
+/// Raw byte soup almost never survives the first alphabet check, so a fuzzer
+/// driving [`decode`](crate::decode) with it spends its time on one error path.
+/// These variants start from a valid encoding and apply one small corruption:
+///
+/// ```
+/// use arbitrary::{Arbitrary, Unstructured};
+/// use qr_base44::Base44Input;
+///
+/// let mut u = Unstructured::new(&[3, 7, 0x41, 0x42, 0x43, 0x44]);
+/// let input = Base44Input::arbitrary(&mut u).unwrap();
+/// let _ = qr_base44::decode(input.as_str());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Base44Input {
+    /// A valid encoding.
+    Valid(Base44String),
+    /// A valid encoding with one character replaced by an arbitrary `char`.
+    Substituted(String),
+    /// A valid encoding with one extra alphabet character appended.
+    Extended(String),
+    /// A valid encoding with trailing characters removed.
+    Truncated(String),
+    /// A valid encoding in lower case.
+    Lowercased(String),
+}
+
+impl Base44Input {
+    /// The input text.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Base44Input::Valid(s) => s.as_str(),
+            Base44Input::Substituted(s)
+            | Base44Input::Extended(s)
+            | Base44Input::Truncated(s)
+            | Base44Input::Lowercased(s) => s,
+        }
+    }
+}
+
+impl<'a> Arbitrary<'a> for Base44Input {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let kind: u8 = u.int_in_range(0..=4)?;
+        let valid = Base44String::arbitrary(u)?;
+        let mut s = valid.clone().into_string();
+        Ok(match kind {
+            0 => Base44Input::Valid(valid),
+            1 => {
+                let ch: char = u.arbitrary()?;
+                if s.is_empty() {
+                    s.push(ch);
+                } else {
+                    let i = u.choose_index(s.len())?;
+                    s.replace_range(i..i + 1, ch.encode_utf8(&mut [0; 4]));
+                }
+                Base44Input::Substituted(s)
+            }
+            2 => {
+                s.push(*u.choose(BASE44_ALPHABET)? as char);
+                Base44Input::Extended(s)
+            }
+            3 => {
+                let keep = u.int_in_range(0..=s.len())?;
+                s.truncate(keep);
+                Base44Input::Truncated(s)
+            }
+            _ => Base44Input::Lowercased(s.to_ascii_lowercase()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode;
+
+    #[test]
+    fn variants_decode_as_expected() {
+        let mut seen = [false; 5];
+        for n in 0u32..500 {
+            let seed: Vec<u8> = (0..16)
+                .map(|i| (n.wrapping_mul(2_654_435_761) >> i) as u8)
+                .collect();
+            let input = Base44Input::arbitrary(&mut Unstructured::new(&seed)).unwrap();
+            let result = decode(input.as_str());
+            match &input {
+                Base44Input::Valid(s) => {
+                    seen[0] = true;
+                    assert_eq!(result.unwrap(), s.decode());
+                }
+                Base44Input::Substituted(_) => seen[1] = true,
+                Base44Input::Extended(_) => seen[2] = true,
+                Base44Input::Truncated(_) => seen[3] = true,
+                Base44Input::Lowercased(s) => {
+                    seen[4] = true;
+                    if s.bytes().any(|b| b.is_ascii_lowercase()) {
+                        assert!(result.is_err());
+                    }
+                }
+            }
+        }
+        assert_eq!(seen, [true; 5]);
+    }
+}
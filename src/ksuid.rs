@@ -0,0 +1,168 @@
+//! KSUID-style sortable identifiers.
+//!
+//! A [`Ksuid`] is 160 bits: a big-endian 32-bit timestamp (seconds since
+//! [`EPOCH`]) followed by 128 bits of payload, normally random. It is encoded with
+//! the order-preserving mode ([`encode_sortable`]) into 30 characters, so sorting
+//! the strings sorts the IDs chronologically.
+//!
+//! ```
+//! use qr_base44::ksuid::Ksuid;
+//!
+//! let a = Ksuid::from_parts(100, [0xFF; 16]);
+//! let b = Ksuid::from_parts(101, [0x00; 16]);
+//! assert!(a.to_string() < b.to_string());
+//!
+//! let parsed: Ksuid = b.to_string().parse().unwrap();
+//! assert_eq!(parsed.timestamp(), 101);
+//! ```
+
+use core::fmt;
+use core::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{Base44Error, decode_sortable, encode_sortable};
+
+/// KSUID epoch in Unix seconds (2014-05-13T16:53:20Z), as in the original format.
+pub const EPOCH: u64 = 1_400_000_000;
+
+/// Encoded length of a [`Ksuid`].
+pub const ENCODED_LEN: usize = 30;
+
+/// A 160-bit, time-ordered identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ksuid([u8; 20]);
+
+impl Ksuid {
+    /// Generate an ID for the current time with a random payload.
+    #[cfg(feature = "rand")]
+    pub fn new() -> Self {
+        Self::with_time(SystemTime::now())
+    }
+
+    /// Generate an ID for `time` with a random payload.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `time` is outside the representable range
+    /// (`EPOCH ..= EPOCH + u32::MAX` seconds).
+    #[cfg(feature = "rand")]
+    pub fn with_time(time: SystemTime) -> Self {
+        let secs = time
+            .duration_since(UNIX_EPOCH)
+            .ok()
+            .and_then(|d| d.as_secs().checked_sub(EPOCH))
+            .and_then(|s| u32::try_from(s).ok())
+            .expect("time outside KSUID range");
+        Self::from_parts(secs, rand::random())
+    }
+
+    /// Build an ID from a timestamp (seconds since [`EPOCH`]) and payload.
+    pub fn from_parts(timestamp: u32, payload: [u8; 16]) -> Self {
+        let mut bytes = [0u8; 20];
+        bytes[..4].copy_from_slice(&timestamp.to_be_bytes());
+        bytes[4..].copy_from_slice(&payload);
+        Self(bytes)
+    }
+
+    /// Wrap raw 20-byte KSUID bytes.
+    pub fn from_bytes(bytes: [u8; 20]) -> Self {
+        Self(bytes)
+    }
+
+    /// The raw 20 bytes.
+    pub fn as_bytes(&self) -> &[u8; 20] {
+        &self.0
+    }
+
+    /// Seconds since [`EPOCH`].
+    pub fn timestamp(&self) -> u32 {
+        u32::from_be_bytes([self.0[0], self.0[1], self.0[2], self.0[3]])
+    }
+
+    /// The timestamp as a `SystemTime`.
+    pub fn time(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(EPOCH + self.timestamp() as u64)
+    }
+
+    /// The 128-bit payload.
+    pub fn payload(&self) -> [u8; 16] {
+        let mut payload = [0u8; 16];
+        payload.copy_from_slice(&self.0[4..]);
+        payload
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Default for Ksuid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for Ksuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&encode_sortable(&self.0))
+    }
+}
+
+impl FromStr for Ksuid {
+    type Err = Base44Error;
+
+    /// Parse the 30-character form produced by `Display`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != ENCODED_LEN {
+            return Err(Base44Error::InvalidLength);
+        }
+        let bytes = decode_sortable(s)?;
+        let mut out = [0u8; 20];
+        out.copy_from_slice(&bytes);
+        Ok(Self(out))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn string_order_is_chronological() {
+        let ids = [
+            Ksuid::from_parts(0, [0xFF; 16]),
+            Ksuid::from_parts(1, [0x00; 16]),
+            Ksuid::from_parts(255, [0x80; 16]),
+            Ksuid::from_parts(256, [0x00; 16]),
+            Ksuid::from_parts(u32::MAX, [0x00; 16]),
+        ];
+        let strings: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+        assert!(strings.windows(2).all(|w| w[0] < w[1]));
+        for (id, s) in ids.iter().zip(&strings) {
+            assert_eq!(s.len(), ENCODED_LEN);
+            assert_eq!(&s.parse::<Ksuid>().unwrap(), id);
+        }
+    }
+
+    #[test]
+    fn components_are_recovered() {
+        let payload: [u8; 16] = core::array::from_fn(|i| i as u8);
+        let id = Ksuid::from_parts(123_456, payload);
+        assert_eq!(id.timestamp(), 123_456);
+        assert_eq!(id.payload(), payload);
+        assert_eq!(id.time(), UNIX_EPOCH + Duration::from_secs(EPOCH + 123_456));
+        assert!(matches!(
+            "ABC".parse::<Ksuid>(),
+            Err(Base44Error::InvalidLength)
+        ));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn generated_ids_carry_current_time() {
+        let id = Ksuid::new();
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        assert!(now - (EPOCH + id.timestamp() as u64) < 5);
+        assert_ne!(id.payload(), Ksuid::new().payload());
+    }
+}
@@ -2,104 +2,561 @@
 //! - Encoding groups: 2 bytes -> 3 chars; 1 byte -> 2 chars.
 //! - Alphabet: "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ$%*+-./:" (44 chars, excludes space only)
 //! - Public API encodes &[u8] -> String and decodes &str -> Vec<u8>.
+#![cfg_attr(feature = "safe", forbid(unsafe_code))]
 
 use num_bigint::BigUint;
-use num_traits::{One, Zero};
+use num_traits::Zero;
+
+/// Emit a `tracing` event when the `tracing` feature is enabled; otherwise expand
+/// to nothing, so call sites need no `cfg`.
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)+) => {{
+        #[cfg(feature = "tracing")]
+        tracing::$level!(target: "qr_base44", $($arg)+);
+    }};
+}
 
-#[derive(Debug, thiserror::Error)]
+pub mod alphabet;
+pub mod analysis;
+#[cfg(feature = "rkyv")]
+mod archive;
+#[cfg(feature = "clap")]
+mod args;
+mod auto;
+#[cfg(feature = "borsh")]
+mod binary;
+#[cfg(feature = "bitvec")]
+mod bits;
+#[cfg(feature = "bytes")]
+mod buf;
+#[cfg(feature = "heapless")]
+mod capacity;
+#[cfg(feature = "cbor")]
+mod cbor;
+#[cfg(feature = "compression")]
+mod compress;
+#[cfg(feature = "conformance")]
+pub mod conformance;
+mod container;
+#[cfg(feature = "test-support")]
+pub mod corpus;
+mod crc;
+mod diagnostics;
+mod display;
+mod ecc;
+mod engine;
+#[cfg(any(feature = "axum", feature = "actix-web", feature = "rocket"))]
+mod extract;
+#[cfg(feature = "uniffi")]
+pub mod ffi;
+mod fixed;
+mod format;
+#[cfg(feature = "raptorq")]
+mod fountain;
+mod framing;
+#[cfg(feature = "digest")]
+mod hash;
+mod hrp;
+mod id;
+#[cfg(feature = "arbitrary")]
+mod input;
+pub mod ksuid;
+mod math;
+mod multihash;
+mod observe;
+#[cfg(feature = "utoipa")]
+mod openapi;
+#[cfg(feature = "diesel")]
+mod orm;
+mod padded;
+mod parallel;
+mod payload;
+pub mod prelude;
+#[cfg(kani)]
+mod proofs;
+#[cfg(feature = "quickcheck")]
+mod property;
+mod reader;
+mod repair;
+#[cfg(feature = "schemars")]
+mod schema;
+#[cfg(feature = "secure")]
+mod secure;
+mod sink;
+mod slice;
+pub mod snowflake;
+mod sortable;
+#[cfg(feature = "sqlx")]
+mod sql;
+#[cfg(feature = "proptest")]
+pub mod strategies;
+mod stream;
+mod string;
+pub mod tagged;
+#[cfg(feature = "test_vectors")]
+pub mod test_vectors;
+mod text;
+mod timestamped;
+#[cfg(feature = "rand")]
+mod token;
+mod traits;
+mod transcode;
+mod url;
+mod v2;
+mod validator;
+pub mod varint;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "clap")]
+pub use args::Base44ValueParser;
+pub use auto::{Envelope, EnvelopeMode, HeaderInfo, decode_auto, encode_auto};
+#[cfg(feature = "bitvec")]
+pub use bits::{decode_to_bitvec, encode_bitslice};
+#[cfg(feature = "bytes")]
+pub use buf::{decode_buf, encode_buf};
+#[cfg(feature = "heapless")]
+pub use capacity::{decode_heapless, encode_heapless};
+#[cfg(feature = "cbor")]
+pub use cbor::{Payload, PayloadError};
+pub use container::{ContainerError, PrefixScheme};
+pub use crc::{open_crc32, seal_crc32};
+pub use diagnostics::{InvalidCharAt, find_invalid_char, find_invalid_chars};
+pub use display::Base44Display;
+pub use ecc::{EccError, EccOptions, decode_ecc, encode_ecc};
+pub use engine::{DecoderBuilder, DigitOrder, EncoderBuilder, Engine, Mode};
+#[cfg(any(feature = "axum", feature = "actix-web", feature = "rocket"))]
+pub use extract::{Base44, Base44Config, Base44Rejection};
+pub use fixed::{
+    Base44Array, Bits103, decode_64bits, decode_103bits, decode_128bits, decode_256bits,
+    decode_fixed, decode_u128, encode_64bits, encode_103bits, encode_128bits, encode_256bits,
+    encode_u128, iter_frames_103,
+};
+pub use format::{
+    decode_grouped, decode_wrapped, encode_wrapped, format_grouped, try_encode_wrapped,
+    try_format_grouped,
+};
+#[cfg(feature = "raptorq")]
+pub use fountain::{RaptorQDecoder, RaptorQError, encode_raptorq};
+pub use framing::Framer;
+#[cfg(feature = "digest")]
+pub use hash::{DigestDecoder, DigestEncoder, encode_digest};
+pub use hrp::{HRP_SEPARATOR, decode_with_hrp, encode_with_hrp};
+pub use id::{decode_id, encode_id};
+#[cfg(feature = "arbitrary")]
+pub use input::Base44Input;
+pub use math::{MAX_CHARS, bits_capacity, chars_needed, try_bits_capacity, try_chars_needed};
+pub use multihash::{decode_multihash, encode_multihash};
+pub use observe::{Cancelled, Observer, Progress, ProgressReporter};
+pub use padded::{decode_padded, encode_bits_padded, encode_padded};
+pub use parallel::{encode_parallel, encode_parallel_to_writer};
+pub use payload::{MAX_INFLATED_LEN, QrPayload, QrPayloadBuilder, QrPayloadError};
+pub use reader::Frame103Reader;
+pub use repair::{
+    DEFAULT_CONFUSABLES, Repair, decode_normalized, decode_repaired, normalize_width,
+};
+#[cfg(feature = "secure")]
+pub use secure::{SealOptions, SecureError, secure_open, secure_seal};
+pub use slice::{BufferTooSmall, encode_to_slice};
+pub use sortable::{SORTABLE_ALPHABET, decode_sortable, encode_sortable};
+pub use stream::{DecoderWriter, EncoderWriter, StreamDecoder, encode_to_writer};
+pub use string::{Base44Bytes, Base44String};
+pub use text::{DecodeToStringError, decode_to_string, decode_to_string_lossy, encode_str};
+#[cfg(feature = "rand")]
+pub use timestamped::generate_timestamped;
+pub use timestamped::{TIMESTAMP_LEN, extract_timestamp};
+#[cfg(feature = "rand")]
+pub use token::{generate_token, generate_token_bytes};
+pub use traits::{FromBase44, ToBase44};
+pub use transcode::{
+    Base32, Base64Transcoder, base32_to_base44, base44_to_base32, base44_to_base45, base44_to_hex,
+    base44_to_jws, base45_to_base44, hex_to_base44, jws_to_base44,
+};
+pub use url::{UrlDecodeError, decode_url_component, encode_url_component};
+pub use v2::{decode_v2, encode_v2};
+pub use validator::Validator;
+
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
+
+/// Why decoding or encoding failed.
+///
+/// New variants may be added in minor releases, so matches need a wildcard arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
 pub enum Base44Error {
-    #[error("invalid base44 character")]
     InvalidChar,
-    #[error("dangling character group")]
     Dangling,
-    #[error("value overflow")]
     Overflow,
+    /// The decoded output would exceed a configured size limit.
+    TooLarge,
+    /// The input does not have the length required by a fixed-size codec.
+    InvalidLength,
+    /// Unused high bits of the last input byte are set in a bit-length encode.
+    NonZeroPadding,
+    /// An integrity check over the decoded payload failed.
+    ChecksumMismatch,
 }
 
+impl core::fmt::Display for Base44Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Base44Error::InvalidChar => "invalid base44 character",
+            Base44Error::Dangling => "dangling character group",
+            Base44Error::Overflow => "value overflow",
+            Base44Error::TooLarge => "decoded output exceeds size limit",
+            Base44Error::InvalidLength => "unexpected encoded length",
+            Base44Error::NonZeroPadding => "unused padding bits are not zero",
+            Base44Error::ChecksumMismatch => "checksum mismatch",
+        })
+    }
+}
+
+impl core::error::Error for Base44Error {}
+
 /// Base44 alphabet: URL-safe QR-compatible subset (excludes space only)
 pub const BASE44_ALPHABET: &[u8; 44] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ$%*+-./:";
 
 #[inline]
-fn b44_val(ch: u8) -> Option<u16> {
-    match ch {
-        b'0'..=b'9' => Some((ch - b'0') as u16),
-        b'A'..=b'Z' => Some(10 + (ch - b'A') as u16),
-        b'$' => Some(36),
-        b'%' => Some(37),
-        b'*' => Some(38),
-        b'+' => Some(39),
-        b'-' => Some(40),
-        b'.' => Some(41),
-        b'/' => Some(42),
-        b':' => Some(43),
-        _ => None,
+const fn b44_val(ch: u8) -> Option<u16> {
+    match alphabet::DECODE_TABLE[ch as usize] {
+        alphabet::INVALID => None,
+        val => Some(val as u16),
+    }
+}
+
+/// Convert encoder output, which only ever contains [`BASE44_ALPHABET`] bytes, into a `String`.
+///
+/// The checked conversion is used unless the `unsafe-perf` feature is enabled
+/// without `safe`.
+#[inline]
+fn ascii_to_string(bytes: Vec<u8>) -> String {
+    #[cfg(all(feature = "unsafe-perf", not(feature = "safe")))]
+    {
+        debug_assert!(bytes.is_ascii());
+        // SAFETY: every byte comes from BASE44_ALPHABET, which is ASCII.
+        unsafe { String::from_utf8_unchecked(bytes) }
+    }
+    #[cfg(not(all(feature = "unsafe-perf", not(feature = "safe"))))]
+    {
+        String::from_utf8(bytes).expect("base44 alphabet is ASCII")
     }
 }
 
 /// Encode arbitrary bytes into a Base44 string.
 /// Groups of 2 bytes produce 3 characters; a final single byte produces 2 characters.
 pub fn encode(input: &[u8]) -> String {
-    let mut out = String::with_capacity((input.len() * 3).div_ceil(2));
+    let mut out = Vec::with_capacity(encoded_len(input.len()));
+    let Ok(()) = sink::encode_to_sink(input, &mut out);
+    ascii_to_string(out)
+}
+
+/// [`encode`] that returns [`Base44Error::TooLarge`] instead of panicking or
+/// aborting when the output length overflows `usize` or cannot be allocated.
+///
+/// Together with [`try_decode`], [`try_encode_bits`], [`try_decode_bits`] and
+/// the other `try_*` functions, this covers the codec without any panicking
+/// path, for callers that must not unwind (or abort on allocation failure).
+pub fn try_encode(input: &[u8]) -> Result<String, Base44Error> {
+    let len = try_encoded_len(input.len()).ok_or(Base44Error::TooLarge)?;
+    let mut out = Vec::new();
+    out.try_reserve_exact(len)
+        .map_err(|_| Base44Error::TooLarge)?;
+    let Ok(()) = sink::encode_to_sink(input, &mut out);
+    Ok(ascii_to_string(out))
+}
+
+/// A zeroed buffer of `len` bytes, or [`Base44Error::TooLarge`] if it cannot be allocated.
+fn try_zeroed(len: usize) -> Result<Vec<u8>, Base44Error> {
+    let mut out = Vec::new();
+    out.try_reserve_exact(len)
+        .map_err(|_| Base44Error::TooLarge)?;
+    out.resize(len, 0);
+    Ok(out)
+}
+
+/// Encode `input` into `out`, which must be exactly `encoded_len(input.len())` long.
+///
+/// Iterating exact chunks of both sides lets the compiler drop every bounds
+/// check in the loop; the odd byte is handled once, from the remainders.
+#[inline]
+fn encode_into(input: &[u8], out: &mut [u8]) {
+    debug_assert_eq!(out.len(), encoded_len(input.len()));
+    let mut pairs = input.chunks_exact(2);
+    let mut groups = out.chunks_exact_mut(3);
+    for (pair, group) in (&mut pairs).zip(&mut groups) {
+        let x = (pair[0] as u16) * 256 + pair[1] as u16;
+        // Base44 outputs least-significant digit first
+        group[0] = BASE44_ALPHABET[(x % 44) as usize];
+        group[1] = BASE44_ALPHABET[(x / 44 % 44) as usize];
+        group[2] = BASE44_ALPHABET[(x / (44 * 44)) as usize];
+    }
+    if let ([u], [b, a]) = (pairs.remainder(), groups.into_remainder()) {
+        *b = BASE44_ALPHABET[(u % 44) as usize];
+        *a = BASE44_ALPHABET[(u / 44) as usize];
+    }
+}
+
+/// Length of the byte-pair encoding of `n` input bytes: `ceil(3n / 2)`.
+pub const fn encoded_len(n: usize) -> usize {
+    (n / 2) * 3 + (n % 2) * 2
+}
+
+/// [`encoded_len`], or `None` if the result overflows `usize`.
+pub const fn try_encoded_len(n: usize) -> Option<usize> {
+    match (n / 2).checked_mul(3) {
+        Some(len) => len.checked_add((n % 2) * 2),
+        None => None,
+    }
+}
+
+/// Byte-pair encode a fixed-size array in a `const` context.
+///
+/// Produces the same characters as [`encode`], as ASCII bytes. `M` must equal
+/// [`encoded_len`]`(N)`; a mismatch is a compile error when evaluated in a `const`.
+///
+/// # Example
+///
+/// ```
+/// const LABEL: [u8; 5] = qr_base44::encode_const(b"AB\x01");
+/// assert_eq!(core::str::from_utf8(&LABEL).unwrap(), qr_base44::encode(b"AB\x01"));
+/// ```
+pub const fn encode_const<const N: usize, const M: usize>(input: &[u8; N]) -> [u8; M] {
+    assert!(M == encoded_len(N), "output length must be encoded_len(N)");
+    let mut out = [0u8; M];
     let mut i = 0;
-    while i + 1 < input.len() {
+    let mut o = 0;
+    while i + 1 < N {
         let x = (input[i] as u16) * 256 + (input[i + 1] as u16);
-        let c = x % 44; // least significant digit
-        let x = x / 44;
-        let b = x % 44;
-        let a = x / 44; // most significant digit
-        // Base44 outputs least-significant digit first
-        out.push(BASE44_ALPHABET[c as usize] as char);
-        out.push(BASE44_ALPHABET[b as usize] as char);
-        out.push(BASE44_ALPHABET[a as usize] as char);
+        out[o] = BASE44_ALPHABET[(x % 44) as usize];
+        out[o + 1] = BASE44_ALPHABET[(x / 44 % 44) as usize];
+        out[o + 2] = BASE44_ALPHABET[(x / (44 * 44)) as usize];
         i += 2;
+        o += 3;
     }
-    if i < input.len() {
+    if i < N {
         let x = input[i] as u16;
-        let b = x % 44;
-        let a = x / 44;
-        // Base44 outputs least-significant digit first for single byte too
-        out.push(BASE44_ALPHABET[b as usize] as char);
-        out.push(BASE44_ALPHABET[a as usize] as char);
+        out[o] = BASE44_ALPHABET[(x % 44) as usize];
+        out[o + 1] = BASE44_ALPHABET[(x / 44) as usize];
     }
     out
 }
 
+/// Byte-pair encode a byte-string literal into a `&'static str` at compile time.
+///
+/// ```
+/// use qr_base44::base44_encode;
+///
+/// const HEADER: &str = base44_encode!(b"\x01\x02\xFF");
+/// assert_eq!(HEADER, qr_base44::encode(&[0x01, 0x02, 0xFF]));
+/// ```
+#[macro_export]
+macro_rules! base44_encode {
+    ($b:expr) => {{
+        const ENCODED: [u8; $crate::encoded_len($b.len())] = $crate::encode_const($b);
+        const S: &str = match ::core::str::from_utf8(&ENCODED) {
+            Ok(s) => s,
+            Err(_) => panic!("base44 alphabet is ASCII"),
+        };
+        S
+    }};
+}
+
+/// Number of bytes produced by byte-pair decoding `chars` characters.
+///
+/// Returns `None` for lengths that leave a dangling character (`chars % 3 == 1`).
+pub const fn decoded_len(chars: usize) -> Option<usize> {
+    match chars % 3 {
+        0 => Some(chars / 3 * 2),
+        2 => Some(chars / 3 * 2 + 1),
+        _ => None,
+    }
+}
+
+/// Byte-pair decode a string in a `const` context, panicking on invalid input.
+///
+/// Intended for constants, where the panic becomes a compile error; prefer the
+/// [`base44!`] macro, which also infers `N`. `N` must equal [`decoded_len`] of the
+/// input length.
+pub const fn decode_const<const N: usize>(s: &str) -> [u8; N] {
+    let bytes = s.as_bytes();
+    match decoded_len(bytes.len()) {
+        Some(n) => assert!(n == N, "output length must be decoded_len(s.len())"),
+        None => panic!("dangling base44 character group"),
+    }
+    let mut out = [0u8; N];
+    let mut i = 0;
+    let mut o = 0;
+    while i < bytes.len() {
+        let c0 = match b44_val(bytes[i]) {
+            Some(v) => v as u32,
+            None => panic!("invalid base44 character"),
+        };
+        let c1 = match b44_val(bytes[i + 1]) {
+            Some(v) => v as u32,
+            None => panic!("invalid base44 character"),
+        };
+        if i + 2 < bytes.len() {
+            let c2 = match b44_val(bytes[i + 2]) {
+                Some(v) => v as u32,
+                None => panic!("invalid base44 character"),
+            };
+            let x = c2 * 44 * 44 + c1 * 44 + c0;
+            assert!(x <= 65535, "base44 value overflow");
+            out[o] = (x / 256) as u8;
+            out[o + 1] = (x % 256) as u8;
+            i += 3;
+            o += 2;
+        } else {
+            let x = c1 * 44 + c0;
+            assert!(x <= 255, "base44 value overflow");
+            out[o] = x as u8;
+            i += 2;
+            o += 1;
+        }
+    }
+    out
+}
+
+/// Decode an optimal-length string holding `bits` bits (at most 128) in a `const`
+/// context, panicking on invalid input. Counterpart of [`decode_bits`].
+///
+/// `N` must equal `bits.div_ceil(8)`; the [`base44!`] macro computes it for you.
+pub const fn decode_bits_const<const N: usize>(bits: usize, s: &str) -> [u8; N] {
+    assert!(bits > 0 && bits <= 128, "bits must be in 1..=128");
+    assert!(
+        N == bits.div_ceil(8),
+        "output length must be bits.div_ceil(8)"
+    );
+    let bytes = s.as_bytes();
+    let mut value = 0u128;
+    let mut i = 0;
+    while i < bytes.len() {
+        let digit = match b44_val(bytes[i]) {
+            Some(v) => v as u128,
+            None => panic!("invalid base44 character"),
+        };
+        value = match value.checked_mul(44) {
+            Some(v) => match v.checked_add(digit) {
+                Some(v) => v,
+                None => panic!("base44 value overflow"),
+            },
+            None => panic!("base44 value overflow"),
+        };
+        i += 1;
+    }
+    assert!(bits == 128 || value >> bits == 0, "base44 value overflow");
+    let mut out = [0u8; N];
+    let mut b = 0;
+    while b < N {
+        out[b] = (value >> (b * 8)) as u8;
+        b += 1;
+    }
+    out
+}
+
+/// Decode a Base44 string literal into a byte array at compile time.
+///
+/// `base44!("...")` uses the byte-pair scheme of [`decode`];
+/// `base44!("...", bits = N)` uses the optimal scheme of [`decode_bits`] (N ≤ 128).
+/// Invalid characters, dangling groups and overflowing values are compile errors.
+///
+/// ```
+/// use qr_base44::base44;
+///
+/// const PAIR: [u8; 3] = base44!("J%XL1");
+/// assert_eq!(PAIR, [0xFF, 0xFF, 0x41]);
+///
+/// let key: [u8; 13] = base44!("0123456789ABCDEFGHI", bits = 103);
+/// assert_eq!(key.to_vec(), qr_base44::decode_bits(103, "0123456789ABCDEFGHI").unwrap());
+/// ```
+///
+/// ```compile_fail
+/// let _ = qr_base44::base44!("AB C");
+/// ```
+#[macro_export]
+macro_rules! base44 {
+    ($s:expr) => {{
+        const OUT: [u8; match $crate::decoded_len($s.len()) {
+            Some(n) => n,
+            None => panic!("dangling base44 character group"),
+        }] = $crate::decode_const($s);
+        OUT
+    }};
+    ($s:expr, bits = $bits:expr) => {{
+        const OUT: [u8; ($bits as usize).div_ceil(8)] = $crate::decode_bits_const($bits, $s);
+        OUT
+    }};
+}
+
 /// Decode a Base44 string back to raw bytes.
 /// Accepts only the Base44 alphabet; returns errors for invalid chars, dangling final char, or overflow.
 pub fn decode(s: &str) -> Result<Vec<u8>, Base44Error> {
-    let bytes = s.as_bytes();
-    let mut out: Vec<u8> = Vec::with_capacity(bytes.len());
-    let mut i = 0;
-    while i + 2 < bytes.len() {
+    decode_bytes(s.as_bytes())
+}
+
+/// Decode Base44 text given as raw bytes, e.g. straight from a QR scanner.
+///
+/// Behaves exactly like [`decode`], but the input need not be valid UTF-8: any byte
+/// outside the alphabet (including non-ASCII bytes) is reported as
+/// [`Base44Error::InvalidChar`], so no lossy `str` conversion is needed first.
+///
+/// ```
+/// assert_eq!(qr_base44::decode_bytes(b"J%X").unwrap(), [0xFF, 0xFF]);
+/// assert!(qr_base44::decode_bytes(b"J%\xFF").is_err());
+/// ```
+pub fn decode_bytes(bytes: &[u8]) -> Result<Vec<u8>, Base44Error> {
+    let mut out = vec![0; bytes.len() / 3 * 2 + bytes.len() % 3 / 2];
+    decode_into(bytes, &mut out)?;
+    Ok(out)
+}
+
+/// [`decode`] that returns [`Base44Error::TooLarge`] instead of aborting when
+/// the output cannot be allocated; see [`try_encode`].
+pub fn try_decode(s: &str) -> Result<Vec<u8>, Base44Error> {
+    try_decode_bytes(s.as_bytes())
+}
+
+/// [`decode_bytes`] that returns [`Base44Error::TooLarge`] instead of aborting
+/// when the output cannot be allocated.
+pub fn try_decode_bytes(bytes: &[u8]) -> Result<Vec<u8>, Base44Error> {
+    let mut out = try_zeroed(bytes.len() / 3 * 2 + bytes.len() % 3 / 2)?;
+    decode_into(bytes, &mut out)?;
+    Ok(out)
+}
+
+/// Decode `bytes` into `out`, which must be exactly as long as the decoded output.
+///
+/// The whole input is checked against the alphabet first, so the arithmetic
+/// loop reads digits straight from the table. Errors are still reported in
+/// input order: an overflowing group before the first bad character wins.
+fn decode_into(bytes: &[u8], out: &mut [u8]) -> Result<(), Base44Error> {
+    let bad = alphabet::first_invalid(bytes);
+    // Full groups before the one holding the bad character.
+    let checked = bad.map_or(bytes.len(), |i| i / 3 * 3);
+    let digit = |ch: u8| u32::from(alphabet::DECODE_TABLE[ch as usize]);
+    let mut groups = bytes[..checked].chunks_exact(3);
+    let mut pairs = out.chunks_exact_mut(2);
+    for (group, pair) in (&mut groups).zip(&mut pairs) {
         // Input is least-significant digit first: c (lsd), b, a (msd)
-        let c0 = b44_val(bytes[i]).ok_or(Base44Error::InvalidChar)? as u32;
-        let c1 = b44_val(bytes[i + 1]).ok_or(Base44Error::InvalidChar)? as u32;
-        let c2 = b44_val(bytes[i + 2]).ok_or(Base44Error::InvalidChar)? as u32;
-        let x: u32 = c2 * 44 * 44 + c1 * 44 + c0; // 0..(44^3 - 1)
-        if x > 65535 {
-            return Err(Base44Error::Overflow);
-        }
-        out.push((x / 256) as u8);
-        out.push((x % 256) as u8);
-        i += 3;
-    }
-    if i < bytes.len() {
-        if i + 1 >= bytes.len() {
-            // Single trailing character: report InvalidChar if it's not in alphabet, otherwise Dangling
-            if b44_val(bytes[i]).is_none() {
-                return Err(Base44Error::InvalidChar);
-            }
-            return Err(Base44Error::Dangling);
-        }
-        let c0 = b44_val(bytes[i]).ok_or(Base44Error::InvalidChar)? as u32;
-        let c1 = b44_val(bytes[i + 1]).ok_or(Base44Error::InvalidChar)? as u32;
-        let x: u32 = c1 * 44 + c0; // 0..(44^2 - 1)
-        if x > 255 {
-            return Err(Base44Error::Overflow);
+        let (c0, c1, c2) = (digit(group[0]), digit(group[1]), digit(group[2]));
+        let x = u16::try_from(c2 * 44 * 44 + c1 * 44 + c0).map_err(|_| Base44Error::Overflow)?;
+        pair.copy_from_slice(&x.to_be_bytes());
+    }
+    if bad.is_some() {
+        return Err(Base44Error::InvalidChar);
+    }
+    match (groups.remainder(), pairs.into_remainder()) {
+        ([], []) => {}
+        (&[_], []) => return Err(Base44Error::Dangling),
+        (&[c0, c1], [byte]) => {
+            let x = digit(c1) * 44 + digit(c0);
+            *byte = u8::try_from(x).map_err(|_| Base44Error::Overflow)?;
         }
-        out.push(x as u8);
+        _ => unreachable!("output is sized from the input remainder"),
     }
-    Ok(out)
+    Ok(())
 }
 
 /// Encode a fixed number of bits (arbitrary length) as a Base44 string with optimal length.
@@ -128,6 +585,13 @@ pub fn decode(s: &str) -> Result<Vec<u8>, Base44Error> {
 ///
 /// * `bits` - Number of significant bits (must be > 0). Bytes are read in little-endian order.
 /// * `bytes` - Input bytes in LSB-first order (matching typical bit-packing schemes).
+///   Only the first `ceil(bits / 8)` bytes are read.
+///
+/// # Panics
+///
+/// Panics if `bits` is zero, `bytes` is too short, or any padding bit above `bits`
+/// in the last byte is set (those bits could not be represented and would be
+/// silently lost). Use [`try_encode_bits`] to get an error instead.
 ///
 /// # Example
 ///
@@ -147,6 +611,10 @@ pub fn encode_bits(bits: usize, bytes: &[u8]) -> String {
         bits,
         bytes.len()
     );
+    assert!(
+        padding_is_clear(bits, bytes),
+        "padding bits above bit {bits} must be zero"
+    );
 
     // Use optimized paths for common bit sizes
     if bits <= 64 {
@@ -158,6 +626,38 @@ pub fn encode_bits(bits: usize, bytes: &[u8]) -> String {
     }
 }
 
+/// Fallible version of [`encode_bits`].
+///
+/// Returns [`Base44Error::InvalidLength`] if `bits` is zero or `bytes` holds fewer
+/// than `ceil(bits / 8)` bytes, and [`Base44Error::NonZeroPadding`] if any unused
+/// high bit of the last byte is set, instead of panicking.
+///
+/// ```
+/// use qr_base44::{Base44Error, try_encode_bits};
+///
+/// assert_eq!(try_encode_bits(7, &[0x7F]).unwrap().len(), 2);
+/// assert!(matches!(try_encode_bits(7, &[0x80]), Err(Base44Error::NonZeroPadding)));
+/// ```
+pub fn try_encode_bits(bits: usize, bytes: &[u8]) -> Result<String, Base44Error> {
+    if bits == 0 || bytes.len() < bits.div_ceil(8) {
+        return Err(Base44Error::InvalidLength);
+    }
+    if bits > bits_capacity(MAX_CHARS) {
+        return Err(Base44Error::TooLarge);
+    }
+    if !padding_is_clear(bits, bytes) {
+        return Err(Base44Error::NonZeroPadding);
+    }
+    Ok(encode_bits(bits, bytes))
+}
+
+/// Whether the bits of `bytes[bits / 8]` above `bits % 8` are all zero.
+/// `bytes` must hold at least `ceil(bits / 8)` bytes.
+#[inline]
+fn padding_is_clear(bits: usize, bytes: &[u8]) -> bool {
+    bits % 8 == 0 || bytes[bits / 8] >> (bits % 8) == 0
+}
+
 /// Fast path for bits <= 64 using u64
 #[inline]
 fn encode_bits_u64(bits: usize, bytes: &[u8]) -> String {
@@ -167,7 +667,7 @@ fn encode_bits_u64(bits: usize, bytes: &[u8]) -> String {
         value |= (b as u64) << (i * 8);
     }
 
-    let chars_needed = ((bits as f64) * 2f64.ln() / 44f64.ln()).ceil() as usize;
+    let chars_needed = chars_needed(bits);
     let mut result = Vec::with_capacity(chars_needed);
 
     for _ in 0..chars_needed {
@@ -177,7 +677,7 @@ fn encode_bits_u64(bits: usize, bytes: &[u8]) -> String {
     }
 
     result.reverse();
-    String::from_utf8(result).unwrap()
+    ascii_to_string(result)
 }
 
 /// Fast path for bits <= 128 using u128
@@ -189,7 +689,7 @@ fn encode_bits_u128(bits: usize, bytes: &[u8]) -> String {
         value |= (b as u128) << (i * 8);
     }
 
-    let chars_needed = ((bits as f64) * 2f64.ln() / 44f64.ln()).ceil() as usize;
+    let chars_needed = chars_needed(bits);
     let mut result = Vec::with_capacity(chars_needed);
 
     for _ in 0..chars_needed {
@@ -199,7 +699,7 @@ fn encode_bits_u128(bits: usize, bytes: &[u8]) -> String {
     }
 
     result.reverse();
-    String::from_utf8(result).unwrap()
+    ascii_to_string(result)
 }
 
 /// Fallback path for bits > 128 using BigUint
@@ -212,8 +712,7 @@ fn encode_bits_bigint(bits: usize, bytes: &[u8]) -> String {
         value += BigUint::from(b) << (i * 8);
     }
 
-    // Calculate optimal character count: ceil(bits * log(2) / log(44))
-    let chars_needed = ((bits as f64) * 2f64.ln() / 44f64.ln()).ceil() as usize;
+    let chars_needed = chars_needed(bits);
 
     // Convert to base44
     let mut result = Vec::with_capacity(chars_needed);
@@ -233,7 +732,7 @@ fn encode_bits_bigint(bits: usize, bytes: &[u8]) -> String {
 
     // Reverse to get most significant digit first
     result.reverse();
-    String::from_utf8(result).unwrap()
+    ascii_to_string(result)
 }
 
 /// Decode a Base44 string back to bytes, expecting a specific bit count.
@@ -259,6 +758,10 @@ fn encode_bits_bigint(bits: usize, bytes: &[u8]) -> String {
 /// Returns an error if the string contains invalid characters or the decoded value
 /// exceeds the specified bit count.
 ///
+/// # Panics
+///
+/// Panics if `bits` is zero. Use [`try_decode_bits`] to get an error instead.
+///
 /// # Example
 ///
 /// ```
@@ -279,13 +782,41 @@ pub fn decode_bits(bits: usize, s: &str) -> Result<Vec<u8>, Base44Error> {
     }
 }
 
+/// [`decode_bits`] that returns [`Base44Error::InvalidLength`] for zero `bits`
+/// and [`Base44Error::TooLarge`] for more bits than [`MAX_CHARS`] characters
+/// hold, instead of panicking.
+///
+/// Strings longer than [`chars_needed`]`(bits)` are rejected as
+/// [`Base44Error::InvalidLength`] before decoding, so untrusted input costs at
+/// most what a valid string of that size would. Bindings route through this.
+///
+/// ```
+/// use qr_base44::{Base44Error, try_decode_bits};
+///
+/// assert_eq!(try_decode_bits(7, "2+").unwrap(), [0x7F]);
+/// assert_eq!(try_decode_bits(0, "2+"), Err(Base44Error::InvalidLength));
+/// assert_eq!(try_decode_bits(7, "02+"), Err(Base44Error::InvalidLength));
+/// ```
+pub fn try_decode_bits(bits: usize, s: &str) -> Result<Vec<u8>, Base44Error> {
+    if bits == 0 {
+        return Err(Base44Error::InvalidLength);
+    }
+    if bits > bits_capacity(MAX_CHARS) {
+        return Err(Base44Error::TooLarge);
+    }
+    if s.len() > chars_needed(bits) {
+        return Err(Base44Error::InvalidLength);
+    }
+    decode_bits(bits, s)
+}
+
 /// Fast path for bits <= 64 using u64
 #[inline]
 fn decode_bits_u64(bits: usize, s: &str) -> Result<Vec<u8>, Base44Error> {
     let mut value = 0u64;
 
-    for ch in s.chars() {
-        let digit = b44_val(ch as u8).ok_or(Base44Error::InvalidChar)?;
+    for &ch in s.as_bytes() {
+        let digit = b44_val(ch).ok_or(Base44Error::InvalidChar)?;
         value = value
             .checked_mul(44)
             .and_then(|v| v.checked_add(digit as u64))
@@ -315,8 +846,8 @@ fn decode_bits_u64(bits: usize, s: &str) -> Result<Vec<u8>, Base44Error> {
 fn decode_bits_u128(bits: usize, s: &str) -> Result<Vec<u8>, Base44Error> {
     let mut value = 0u128;
 
-    for ch in s.chars() {
-        let digit = b44_val(ch as u8).ok_or(Base44Error::InvalidChar)?;
+    for &ch in s.as_bytes() {
+        let digit = b44_val(ch).ok_or(Base44Error::InvalidChar)?;
         value = value
             .checked_mul(44)
             .and_then(|v| v.checked_add(digit as u128))
@@ -347,20 +878,13 @@ fn decode_bits_bigint(bits: usize, s: &str) -> Result<Vec<u8>, Base44Error> {
     let mut value = BigUint::zero();
     let forty_four = BigUint::from(44u32);
 
-    for ch in s.chars() {
-        let digit = b44_val(ch as u8).ok_or(Base44Error::InvalidChar)?;
+    for &ch in s.as_bytes() {
+        let digit = b44_val(ch).ok_or(Base44Error::InvalidChar)?;
         value = value * &forty_four + BigUint::from(digit as u32);
     }
 
-    // Verify value fits in specified bits
-    let max_value = if bits < usize::MAX {
-        (BigUint::one() << bits) - BigUint::one()
-    } else {
-        // For very large bit counts, skip overflow check
-        value.clone()
-    };
-
-    if value > max_value {
+    // Verify value fits in specified bits (without materialising 2^bits)
+    if value.bits() > bits as u64 {
         return Err(Base44Error::Overflow);
     }
 
@@ -399,6 +923,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn every_length_through_the_remainders() {
+        let data: Vec<u8> = (0..=255).rev().collect();
+        for len in 0..data.len() {
+            let s = encode(&data[..len]);
+            assert_eq!(s.len(), encoded_len(len));
+            assert_eq!(decode(&s).unwrap(), &data[..len]);
+        }
+        // Errors in the remainder are reported after whole groups decode cleanly.
+        assert_eq!(decode("LZDL"), Err(Base44Error::Dangling));
+        assert_eq!(decode("LZD\t"), Err(Base44Error::InvalidChar));
+        assert_eq!(decode("LZD//"), Err(Base44Error::Overflow));
+        assert_eq!(decode("LZ\tLZ"), Err(Base44Error::InvalidChar));
+    }
+
     #[test]
     fn known_vectors() {
         // Base44 uses least-significant digit first (lsd-first): output order is c, b, a.
@@ -441,6 +980,13 @@ mod tests {
 
         assert!(matches!(decode("A"), Err(Base44Error::Dangling))); // single valid char -> incomplete group
         assert!(matches!(decode("😀"), Err(Base44Error::InvalidChar))); // not in Base44 alphabet
+
+        // The alphabet is checked up front, but the first error in input order wins.
+        assert_eq!(decode(":::00a"), Err(Base44Error::Overflow));
+        assert_eq!(decode("000:a:"), Err(Base44Error::InvalidChar));
+        assert_eq!(decode("000:::a"), Err(Base44Error::Overflow));
+        assert_eq!(decode("0000a"), Err(Base44Error::InvalidChar));
+        assert_eq!(decode("0000"), Err(Base44Error::Dangling));
     }
 
     #[test]
@@ -711,6 +1257,63 @@ mod tests {
         assert!(matches!(result, Err(Base44Error::Overflow)));
     }
 
+    #[test]
+    fn byte_input_and_non_ascii_rejection() {
+        assert_eq!(decode_bytes(b"").unwrap(), Vec::<u8>::new());
+        assert_eq!(decode_bytes(b"J%XL1").unwrap(), [0xFF, 0xFF, 0x41]);
+        assert!(matches!(
+            decode_bytes(b"J%X\x80\x81"),
+            Err(Base44Error::InvalidChar)
+        ));
+        // U+0130 truncates to b'0' when cast to u8; it must still be rejected.
+        for bits in [8, 100, 200] {
+            assert!(matches!(
+                decode_bits(bits, "0\u{130}"),
+                Err(Base44Error::InvalidChar)
+            ));
+        }
+    }
+
+    #[test]
+    fn dirty_padding_bits_are_rejected() {
+        let mut data = [0xFFu8; 13];
+        assert!(matches!(
+            try_encode_bits(103, &data),
+            Err(Base44Error::NonZeroPadding)
+        ));
+        data[12] = 0x7F;
+        assert_eq!(
+            try_encode_bits(103, &data).unwrap(),
+            encode_bits(103, &data)
+        );
+        // Byte-aligned bit counts have no padding
+        assert!(try_encode_bits(16, &[0xFF, 0xFF]).is_ok());
+        assert!(matches!(
+            try_encode_bits(1, &[0x02]),
+            Err(Base44Error::NonZeroPadding)
+        ));
+        let mut wide = [0u8; 17];
+        wide[16] = 0x02; // bit 129 set while encoding 129 bits
+        assert!(matches!(
+            try_encode_bits(129, &wide),
+            Err(Base44Error::NonZeroPadding)
+        ));
+        assert!(matches!(
+            try_encode_bits(0, &[]),
+            Err(Base44Error::InvalidLength)
+        ));
+        assert!(matches!(
+            try_encode_bits(9, &[0]),
+            Err(Base44Error::InvalidLength)
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "padding bits")]
+    fn encode_bits_panics_on_dirty_padding() {
+        encode_bits(103, &[0xFF; 13]);
+    }
+
     #[test]
     fn encode_bits_various_patterns() {
         // Test alternating bit patterns
@@ -759,6 +1362,102 @@ mod tests {
         assert_eq!(decode_bits(16, &encoded_bits).unwrap(), data);
     }
 
+    #[test]
+    fn const_encoding_matches_runtime() {
+        const EMPTY: [u8; 0] = encode_const(b"");
+        const ODD: [u8; 5] = encode_const(&[0xFF, 0xFF, 0x41]);
+        const EVEN: [u8; 6] = encode_const(b"Hell");
+        assert!(EMPTY.is_empty());
+        assert_eq!(core::str::from_utf8(&ODD).unwrap(), "J%XL1");
+        assert_eq!(core::str::from_utf8(&EVEN).unwrap(), encode(b"Hell"));
+        for n in 0..10 {
+            assert_eq!(encoded_len(n), encode(&vec![0u8; n]).len());
+        }
+    }
+
+    #[test]
+    fn encode_macro_matches_runtime() {
+        const EMPTY: &str = base44_encode!(b"");
+        const HELLO: &str = base44_encode!(b"Hello, world!");
+        assert_eq!(EMPTY, "");
+        assert_eq!(HELLO, encode(b"Hello, world!"));
+        assert_eq!(base44!("J%X"), *b"\xFF\xFF");
+        let s: &'static str = base44_encode!(&[0xFF, 0xFF]);
+        assert_eq!(s, "J%X");
+    }
+
+    #[test]
+    fn const_decoding_matches_runtime() {
+        const BYTES: [u8; 13] = base44!("0123456789ABCDEFGHI", bits = 103);
+        assert_eq!(
+            BYTES.to_vec(),
+            decode_bits(103, "0123456789ABCDEFGHI").unwrap()
+        );
+        const PAIRS: [u8; 5] = base44!("000100L1");
+        assert_eq!(PAIRS, [0x00, 0x00, 0x00, 0x01, 0x41]);
+        assert_eq!(base44!(""), [0u8; 0]);
+        assert_eq!(base44!("J%X"), [0xFF, 0xFF]);
+        assert_eq!(decoded_len(1), None);
+        assert_eq!(decoded_len(5), Some(3));
+    }
+
+    #[test]
+    fn try_variants_never_panic() {
+        use std::panic::catch_unwind;
+
+        let garbage: [&[u8]; 6] = [b"", b"L", b"\xFF\xFE", b":::", b"LZD//", &[b'Z'; 301]];
+        for bytes in garbage {
+            let text = String::from_utf8_lossy(bytes);
+            for bits in [0, 1, 7, 64, 65, 129, usize::MAX / 8, usize::MAX] {
+                assert!(catch_unwind(|| try_decode_bits(bits, &text)).is_ok());
+                assert!(catch_unwind(|| try_encode_bits(bits, bytes)).is_ok());
+                assert!(catch_unwind(|| try_chars_needed(bits)).is_ok());
+                assert!(catch_unwind(|| try_bits_capacity(bits)).is_ok());
+                assert!(catch_unwind(|| try_format_grouped(&text, bits)).is_ok());
+            }
+            assert!(catch_unwind(|| try_decode_bytes(bytes)).is_ok());
+            assert!(catch_unwind(|| try_encode(bytes)).is_ok());
+            assert!(catch_unwind(|| try_encode_wrapped(bytes, 0)).is_ok());
+        }
+        for ch in ['\0', ' ', 'L', ':', 'é'] {
+            assert!(catch_unwind(|| Framer::try_new(ch)).is_ok());
+        }
+
+        assert_eq!(try_encoded_len(usize::MAX), None);
+        assert_eq!(try_encoded_len(7), Some(encoded_len(7)));
+        assert_eq!(try_encode(b"hi").unwrap(), "LZD");
+        assert_eq!(try_decode("LZD").unwrap(), b"hi");
+        assert_eq!(try_decode("LZD//"), Err(Base44Error::Overflow));
+        assert_eq!(try_decode_bits(usize::MAX, "0"), Err(Base44Error::TooLarge));
+        assert_eq!(try_decode_bits(0, ""), Err(Base44Error::InvalidLength));
+        assert_eq!(
+            try_decode_bits(65, &"0".repeat(100_000)),
+            Err(Base44Error::InvalidLength)
+        );
+        assert_eq!(
+            try_format_grouped("LZD", 0),
+            Err(Base44Error::InvalidLength)
+        );
+        assert!(Framer::try_new(' ').is_none());
+        // Decoding a huge bit count no longer builds 2^bits to range-check.
+        assert_eq!(decode_bits(1 << 20, "1").unwrap().len(), 1 << 17);
+    }
+
+    #[test]
+    fn error_messages() {
+        assert_eq!(
+            Base44Error::InvalidChar.to_string(),
+            "invalid base44 character"
+        );
+        assert_eq!(
+            Base44Error::Dangling.to_string(),
+            "dangling character group"
+        );
+        assert_eq!(Base44Error::Overflow.to_string(), "value overflow");
+        let boxed: Box<dyn core::error::Error> = Box::new(Base44Error::Overflow);
+        assert!(boxed.source().is_none());
+    }
+
     #[test]
     fn alphabet_completeness() {
         // Verify all 44 characters in alphabet are unique
@@ -0,0 +1,121 @@
+//! Exact radix arithmetic between bits and Base44 characters.
+//!
+//! These replace magic numbers like 19 (chars for 103 bits) with `const`
+//! computations, so frame layouts can be derived at compile time:
+//!
+//! ```
+//! use qr_base44::{bits_capacity, chars_needed};
+//!
+//! const ID_CHARS: usize = chars_needed(103);
+//! const _: () = assert!(ID_CHARS == 19);
+//! assert_eq!(bits_capacity(19), 103);
+//! ```
+//!
+//! Both are computed with integer fixed-point arithmetic, not floats. The 64-bit
+//! fraction of `log2(44)` makes them exact for every input up to [`MAX_CHARS`]
+//! characters (checked against the continued-fraction expansion of `log2(44)`);
+//! larger inputs panic.
+
+/// `floor(log2(44) * 2^64)`.
+const LOG2_44_Q64: u128 = 0x5_759d_4f80_cba8_3bf8;
+/// `floor(2^64 / log2(44))`.
+const INV_LOG2_44_Q64: u128 = 0x2ee4_2e16_4e8f_53a4;
+
+/// Largest character count accepted by [`bits_capacity`] (`2^31 - 1`).
+pub const MAX_CHARS: usize = (1 << 31) - 1;
+
+/// Number of Base44 characters needed to hold any `bits`-bit value:
+/// `ceil(bits * ln 2 / ln 44)`, the length produced by [`encode_bits`](crate::encode_bits).
+///
+/// # Panics
+///
+/// Panics if the result would exceed [`MAX_CHARS`].
+pub const fn chars_needed(bits: usize) -> usize {
+    // Underestimate, then step up to the exact answer (at most two steps).
+    let mut chars = ((bits as u128 * INV_LOG2_44_Q64) >> 64) as usize;
+    while bits_capacity(chars) < bits {
+        chars += 1;
+    }
+    chars
+}
+
+/// Number of bits that always fit in `chars` Base44 characters:
+/// `floor(chars * ln 44 / ln 2)`, i.e. the largest `b` with `2^b <= 44^chars`.
+///
+/// # Panics
+///
+/// Panics if `chars > MAX_CHARS`.
+pub const fn bits_capacity(chars: usize) -> usize {
+    assert!(chars <= MAX_CHARS, "chars exceeds MAX_CHARS");
+    ((chars as u128 * LOG2_44_Q64) >> 64) as usize
+}
+
+/// [`chars_needed`], or `None` if the result would exceed [`MAX_CHARS`].
+pub const fn try_chars_needed(bits: usize) -> Option<usize> {
+    if bits > bits_capacity(MAX_CHARS) {
+        return None;
+    }
+    Some(chars_needed(bits))
+}
+
+/// [`bits_capacity`], or `None` if `chars > MAX_CHARS`.
+pub const fn try_bits_capacity(chars: usize) -> Option<usize> {
+    if chars > MAX_CHARS {
+        return None;
+    }
+    Some(bits_capacity(chars))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigUint;
+
+    #[test]
+    fn matches_big_integer_definition() {
+        let forty_four = BigUint::from(44u32);
+        let mut pow = BigUint::from(1u32); // 44^chars
+        for chars in 0..600usize {
+            let b = bits_capacity(chars);
+            assert!(BigUint::from(1u32) << b <= pow, "chars={chars}");
+            assert!(BigUint::from(1u32) << (b + 1) > pow, "chars={chars}");
+            pow *= &forty_four;
+        }
+        for bits in 0..3000 {
+            let c = chars_needed(bits);
+            let two_pow = BigUint::from(1u32) << bits;
+            assert!(forty_four.pow(c as u32) >= two_pow, "bits={bits}");
+            assert!(
+                c == 0 || forty_four.pow(c as u32 - 1) < two_pow,
+                "bits={bits}"
+            );
+        }
+    }
+
+    #[test]
+    fn checked_variants_stop_at_max_chars() {
+        let max_bits = bits_capacity(MAX_CHARS);
+        assert_eq!(try_chars_needed(max_bits), Some(MAX_CHARS));
+        assert_eq!(try_chars_needed(max_bits + 1), None);
+        assert_eq!(try_chars_needed(usize::MAX), None);
+        assert_eq!(try_bits_capacity(MAX_CHARS), Some(max_bits));
+        assert_eq!(try_bits_capacity(MAX_CHARS + 1), None);
+    }
+
+    #[test]
+    fn known_values_and_near_integer_cases() {
+        assert_eq!(chars_needed(103), 19);
+        assert_eq!(chars_needed(104), 20);
+        assert_eq!(chars_needed(256), 47);
+        assert_eq!(chars_needed(512), 94);
+        assert_eq!(bits_capacity(19), 103);
+        // Convergents of log2(44), where chars * log2(44) is closest to an integer
+        // (reference values computed with 200-digit arithmetic).
+        assert_eq!(bits_capacity(266_940_315), 1_457_342_396);
+        assert_eq!(chars_needed(1_457_342_396), 266_940_315);
+        assert_eq!(chars_needed(1_457_342_397), 266_940_316);
+        assert_eq!(bits_capacity(174_164_824), 950_840_946);
+        assert_eq!(chars_needed(950_840_947), 174_164_825);
+        assert_eq!(bits_capacity(MAX_CHARS), 11_724_040_122);
+    }
+}
@@ -0,0 +1,124 @@
+//! Multihash (`<code><length><digest>`) references as Base44 text.
+//!
+//! The code and length are unsigned LEB128 varints as specified by multiformats,
+//! so the decoded bytes are exactly the binary multihash used inside CIDs.
+
+use crate::{Base44Error, decode, encode};
+
+/// Multiformats caps varints at 9 bytes (63 bits).
+const MAX_VARINT_LEN: usize = 9;
+
+fn push_varint(mut n: u64, out: &mut Vec<u8>) {
+    while n >= 0x80 {
+        out.push(n as u8 | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+}
+
+/// Read a minimally encoded varint, returning it and the rest of `bytes`.
+fn read_varint(bytes: &[u8]) -> Result<(u64, &[u8]), Base44Error> {
+    let mut n = 0u64;
+    for (i, &byte) in bytes.iter().enumerate().take(MAX_VARINT_LEN) {
+        n |= u64::from(byte & 0x7F) << (7 * i);
+        if byte & 0x80 == 0 {
+            if byte == 0 && i > 0 {
+                return Err(Base44Error::InvalidLength);
+            }
+            return Ok((n, &bytes[i + 1..]));
+        }
+    }
+    match bytes.len() >= MAX_VARINT_LEN {
+        true => Err(Base44Error::Overflow),
+        false => Err(Base44Error::InvalidLength),
+    }
+}
+
+/// Encode a multihash: the hash function `code` (e.g. `0x12` for SHA2-256),
+/// the digest length and the digest.
+///
+/// # Panics
+///
+/// Panics if `code` does not fit in 63 bits, the most a multiformats varint
+/// holds; [`decode_multihash`] would reject the result.
+///
+/// ```
+/// use qr_base44::{decode_multihash, encode_multihash};
+///
+/// let digest = [0xAB; 32];
+/// let s = encode_multihash(0x12, &digest);
+/// let (code, back) = decode_multihash(&s).unwrap();
+/// assert_eq!((code, &back[..]), (0x12, &digest[..]));
+/// ```
+pub fn encode_multihash(code: u64, digest: &[u8]) -> String {
+    assert!(code < 1 << 63, "multihash code must fit in 63 bits");
+    let mut bytes = Vec::with_capacity(digest.len() + 2 * MAX_VARINT_LEN);
+    push_varint(code, &mut bytes);
+    push_varint(digest.len() as u64, &mut bytes);
+    bytes.extend_from_slice(digest);
+    encode(&bytes)
+}
+
+/// Decode the output of [`encode_multihash`] into `(code, digest)`.
+///
+/// A truncated or non-minimal varint, or a length that does not match the
+/// digest, is [`Base44Error::InvalidLength`]; a varint over 63 bits is
+/// [`Base44Error::Overflow`].
+pub fn decode_multihash(s: &str) -> Result<(u64, Vec<u8>), Base44Error> {
+    let bytes = decode(s)?;
+    let (code, rest) = read_varint(&bytes)?;
+    let (len, digest) = read_varint(rest)?;
+    if digest.len() as u64 != len {
+        return Err(Base44Error::InvalidLength);
+    }
+    Ok((code, digest.to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_multiformats_layout() {
+        let digest: Vec<u8> = (0..32).collect();
+        let s = encode_multihash(0x12, &digest);
+        let bytes = decode(&s).unwrap();
+        assert_eq!(&bytes[..2], [0x12, 0x20]);
+        assert_eq!(&bytes[2..], digest);
+
+        // Two-byte code (blake2b-256 is 0xb220).
+        let s = encode_multihash(0xB220, &digest);
+        assert_eq!(&decode(&s).unwrap()[..4], [0xA0, 0xE4, 0x02, 0x20]);
+        assert_eq!(decode_multihash(&s).unwrap(), (0xB220, digest));
+        assert_eq!(
+            decode_multihash(&encode_multihash(0, &[])).unwrap(),
+            (0, vec![])
+        );
+    }
+
+    #[test]
+    fn malformed_multihashes() {
+        let truncated = encode(&[0x12, 0x20, 0xAB]);
+        assert_eq!(
+            decode_multihash(&truncated),
+            Err(Base44Error::InvalidLength)
+        );
+        assert_eq!(decode_multihash(""), Err(Base44Error::InvalidLength));
+        let non_minimal = encode(&[0x92, 0x00, 0x00]);
+        assert_eq!(
+            decode_multihash(&non_minimal),
+            Err(Base44Error::InvalidLength)
+        );
+        let long = encode(&[0xFF; 10]);
+        assert_eq!(decode_multihash(&long), Err(Base44Error::Overflow));
+        assert_eq!(decode_multihash("ZZZ"), Err(Base44Error::Overflow));
+    }
+
+    #[test]
+    #[should_panic(expected = "63 bits")]
+    fn code_fits_a_varint() {
+        let largest = encode_multihash((1 << 63) - 1, &[]);
+        assert_eq!(decode_multihash(&largest).unwrap().0, (1 << 63) - 1);
+        let _ = encode_multihash(1 << 63, &[]);
+    }
+}
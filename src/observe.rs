@@ -0,0 +1,326 @@
+//! [`Observer`]: throughput and cancellation hooks for the streaming types, and
+//! [`ProgressReporter`] built on them.
+
+use core::fmt;
+use std::cell::{Cell, RefCell};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::Base44Error;
+
+/// Receives counters from [`EncoderWriter`](crate::EncoderWriter),
+/// [`DecoderWriter`](crate::DecoderWriter) and
+/// [`Framer::frames_with`](crate::Framer::frames_with).
+///
+/// Every method defaults to doing nothing, so implement only what you export.
+/// Methods take `&self`, so an observer holding atomic counters (or a metrics
+/// registry handle) can be shared through `&T` or `Arc<T>`. `()` is the no-op
+/// observer the plain constructors use.
+///
+/// ```
+/// use std::io::Write;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use qr_base44::{EncoderWriter, Observer};
+///
+/// #[derive(Default)]
+/// struct Counters {
+///     bytes_in: AtomicUsize,
+///     bytes_out: AtomicUsize,
+/// }
+///
+/// impl Observer for Counters {
+///     fn bytes_in(&self, n: usize) {
+///         self.bytes_in.fetch_add(n, Ordering::Relaxed);
+///     }
+///     fn bytes_out(&self, n: usize) {
+///         self.bytes_out.fetch_add(n, Ordering::Relaxed);
+///     }
+/// }
+///
+/// let counters = Counters::default();
+/// let mut enc = EncoderWriter::with_observer(Vec::new(), &counters);
+/// enc.write_all(b"hello").unwrap();
+/// enc.finish().unwrap();
+/// assert_eq!(counters.bytes_in.load(Ordering::Relaxed), 5);
+/// assert_eq!(counters.bytes_out.load(Ordering::Relaxed), 8);
+/// ```
+pub trait Observer {
+    /// `n` input bytes (or characters, when decoding) were accepted.
+    fn bytes_in(&self, n: usize) {
+        let _ = n;
+    }
+
+    /// `n` output bytes were written to the inner writer.
+    fn bytes_out(&self, n: usize) {
+        let _ = n;
+    }
+
+    /// A character or group was rejected with `err`.
+    fn group_rejected(&self, err: Base44Error) {
+        let _ = err;
+    }
+
+    /// A frame decoding to `len` bytes was received.
+    fn frame_completed(&self, len: usize) {
+        let _ = len;
+    }
+
+    /// The stream was finished successfully.
+    fn finished(&self) {}
+
+    /// Polled before each chunk is processed; returning `true` fails the `write`
+    /// (or `finish`) call with a [`Cancelled`] error.
+    fn should_cancel(&self) -> bool {
+        false
+    }
+}
+
+impl Observer for () {}
+
+/// An `AtomicBool` is a cancellation token: store `true` (e.g. when the client
+/// disconnects) and the stream aborts at its next chunk.
+///
+/// ```
+/// use std::io::{ErrorKind, Write};
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use qr_base44::{Cancelled, EncoderWriter};
+///
+/// let cancel = AtomicBool::new(false);
+/// let mut enc = EncoderWriter::with_observer(Vec::new(), &cancel);
+/// enc.write_all(b"first chunk").unwrap();
+/// cancel.store(true, Ordering::Relaxed);
+/// let err = enc.write_all(b"second chunk").unwrap_err();
+/// assert!(err.get_ref().unwrap().is::<Cancelled>());
+/// ```
+impl Observer for AtomicBool {
+    fn should_cancel(&self) -> bool {
+        self.load(Ordering::Relaxed)
+    }
+}
+
+/// Both observers see every event; the stream is cancelled if either asks.
+impl<A: Observer, B: Observer> Observer for (A, B) {
+    fn bytes_in(&self, n: usize) {
+        self.0.bytes_in(n);
+        self.1.bytes_in(n);
+    }
+
+    fn bytes_out(&self, n: usize) {
+        self.0.bytes_out(n);
+        self.1.bytes_out(n);
+    }
+
+    fn group_rejected(&self, err: Base44Error) {
+        self.0.group_rejected(err);
+        self.1.group_rejected(err);
+    }
+
+    fn frame_completed(&self, len: usize) {
+        self.0.frame_completed(len);
+        self.1.frame_completed(len);
+    }
+
+    fn finished(&self) {
+        self.0.finished();
+        self.1.finished();
+    }
+
+    fn should_cancel(&self) -> bool {
+        self.0.should_cancel() || self.1.should_cancel()
+    }
+}
+
+/// The error inside the [`std::io::Error`] returned when an [`Observer`]
+/// cancels a stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("base44 stream cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+impl<T: Observer + ?Sized> Observer for &T {
+    fn bytes_in(&self, n: usize) {
+        (**self).bytes_in(n)
+    }
+
+    fn bytes_out(&self, n: usize) {
+        (**self).bytes_out(n)
+    }
+
+    fn group_rejected(&self, err: Base44Error) {
+        (**self).group_rejected(err)
+    }
+
+    fn frame_completed(&self, len: usize) {
+        (**self).frame_completed(len)
+    }
+
+    fn finished(&self) {
+        (**self).finished()
+    }
+
+    fn should_cancel(&self) -> bool {
+        (**self).should_cancel()
+    }
+}
+
+impl<T: Observer + ?Sized> Observer for Arc<T> {
+    fn bytes_in(&self, n: usize) {
+        (**self).bytes_in(n)
+    }
+
+    fn bytes_out(&self, n: usize) {
+        (**self).bytes_out(n)
+    }
+
+    fn group_rejected(&self, err: Base44Error) {
+        (**self).group_rejected(err)
+    }
+
+    fn frame_completed(&self, len: usize) {
+        (**self).frame_completed(len)
+    }
+
+    fn finished(&self) {
+        (**self).finished()
+    }
+
+    fn should_cancel(&self) -> bool {
+        (**self).should_cancel()
+    }
+}
+
+/// A progress report from [`ProgressReporter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// Input bytes (or characters, when decoding) processed so far.
+    pub processed: u64,
+    /// Total input size, if known up front.
+    pub total: Option<u64>,
+}
+
+impl Progress {
+    /// `processed / total` in `0.0..=1.0`, if the total is known and non-zero.
+    pub fn fraction(&self) -> Option<f64> {
+        match self.total {
+            Some(total) if total > 0 => Some((self.processed as f64 / total as f64).min(1.0)),
+            _ => None,
+        }
+    }
+}
+
+/// An [`Observer`] that calls `F` every `interval` input bytes and once more
+/// when the stream finishes, for progress bars during large transcodes.
+///
+/// ```
+/// use std::io::Write;
+/// use qr_base44::{EncoderWriter, ProgressReporter};
+///
+/// let data = vec![0u8; 10_000];
+/// let mut seen = Vec::new();
+/// let progress = ProgressReporter::new(Some(data.len() as u64), 4096, |p| seen.push(p.processed));
+/// let mut enc = EncoderWriter::with_observer(Vec::new(), &progress);
+/// enc.write_all(&data).unwrap();
+/// enc.finish().unwrap();
+/// assert_eq!(seen, [4096, 8192, 10_000]);
+/// ```
+pub struct ProgressReporter<F> {
+    total: Option<u64>,
+    interval: u64,
+    processed: Cell<u64>,
+    reported: Cell<u64>,
+    callback: RefCell<F>,
+}
+
+impl<F: FnMut(Progress)> ProgressReporter<F> {
+    /// Report every `interval` bytes (at least 1) towards `total`.
+    pub fn new(total: Option<u64>, interval: u64, callback: F) -> Self {
+        Self {
+            total,
+            interval: interval.max(1),
+            processed: Cell::new(0),
+            reported: Cell::new(0),
+            callback: RefCell::new(callback),
+        }
+    }
+
+    /// Bytes processed so far.
+    pub fn processed(&self) -> u64 {
+        self.processed.get()
+    }
+
+    fn report(&self) {
+        let processed = self.processed.get();
+        self.reported.set(processed);
+        (self.callback.borrow_mut())(Progress {
+            processed,
+            total: self.total,
+        });
+    }
+}
+
+impl<F> fmt::Debug for ProgressReporter<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProgressReporter")
+            .field("total", &self.total)
+            .field("interval", &self.interval)
+            .field("processed", &self.processed.get())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F: FnMut(Progress)> Observer for ProgressReporter<F> {
+    fn bytes_in(&self, n: usize) {
+        let processed = self.processed.get() + n as u64;
+        self.processed.set(processed);
+        if processed / self.interval > self.reported.get() / self.interval {
+            self.report();
+        }
+    }
+
+    fn finished(&self) {
+        if self.reported.get() != self.processed.get() {
+            self.report();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::DecoderWriter;
+
+    #[test]
+    fn reports_on_interval_and_at_finish() {
+        let text = crate::encode(&[7; 3000]);
+        let mut seen = Vec::new();
+        let progress = ProgressReporter::new(Some(text.len() as u64), 1000, |p: Progress| {
+            seen.push((p.processed, p.fraction()))
+        });
+        let mut dec = DecoderWriter::with_observer(Vec::new(), &progress);
+        for chunk in text.as_bytes().chunks(300) {
+            dec.write_all(chunk).unwrap();
+        }
+        assert_eq!(dec.finish().unwrap().len(), 3000);
+        assert_eq!(progress.processed(), 4500);
+        let processed: Vec<u64> = seen.iter().map(|&(p, _)| p).collect();
+        assert_eq!(processed, [1200, 2100, 3000, 4200, 4500]);
+        assert_eq!(seen.last().unwrap().1, Some(1.0));
+        assert_eq!(
+            Progress {
+                processed: 5,
+                total: None
+            }
+            .fraction(),
+            None
+        );
+    }
+}
@@ -0,0 +1,57 @@
+//! utoipa OpenAPI schemas for the wrapper types ([`utoipa`] feature).
+//!
+//! [`Base44String`] and [`Base44Bytes`] both travel as Base44 text, so both
+//! document as a `string` constrained by [`Base44String::PATTERN`].
+
+use utoipa::openapi::RefOr;
+use utoipa::openapi::schema::{ObjectBuilder, Schema, Type};
+use utoipa::{PartialSchema, ToSchema};
+
+use crate::{Base44Bytes, Base44String};
+
+fn base44_text(description: &str) -> RefOr<Schema> {
+    ObjectBuilder::new()
+        .schema_type(Type::String)
+        .pattern(Some(Base44String::PATTERN))
+        .description(Some(description))
+        .into()
+}
+
+impl PartialSchema for Base44String {
+    fn schema() -> RefOr<Schema> {
+        base44_text("Byte-pair Base44 text over the QR alphanumeric alphabet.")
+    }
+}
+
+impl ToSchema for Base44String {}
+
+impl PartialSchema for Base44Bytes {
+    fn schema() -> RefOr<Schema> {
+        base44_text("Binary data, transmitted as byte-pair Base44 text.")
+    }
+}
+
+impl ToSchema for Base44Bytes {}
+
+#[cfg(test)]
+mod tests {
+    use utoipa::openapi::schema::SchemaType;
+
+    use super::*;
+
+    #[test]
+    fn wrappers_document_as_patterned_strings() {
+        for (name, schema) in [
+            (Base44String::name(), Base44String::schema()),
+            (Base44Bytes::name(), Base44Bytes::schema()),
+        ] {
+            let RefOr::T(Schema::Object(object)) = schema else {
+                panic!("{name} is not an inline object schema");
+            };
+            assert_eq!(object.pattern.as_deref(), Some(Base44String::PATTERN));
+            assert!(object.schema_type == SchemaType::Type(Type::String));
+        }
+        assert_eq!(Base44String::name(), "Base44String");
+        assert_eq!(Base44Bytes::name(), "Base44Bytes");
+    }
+}
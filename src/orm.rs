@@ -0,0 +1,55 @@
+//! Diesel column mapping for [`Base44String`] and [`Base44Bytes`] ([`diesel`]
+//! feature).
+//!
+//! Both types are `Text` columns holding byte-pair Base44. Loading validates the
+//! text, so a corrupted row fails to deserialize.
+
+use std::io::Write;
+
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql};
+use diesel::query_builder::bind_collector::RawBytesBindCollector;
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::Text;
+
+use crate::{Base44Bytes, Base44String, decode, encode};
+
+impl<DB: Backend> ToSql<Text, DB> for Base44String
+where
+    str: ToSql<Text, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        self.as_str().to_sql(out)
+    }
+}
+
+impl<DB: Backend> FromSql<Text, DB> for Base44String
+where
+    String: FromSql<Text, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        Ok(Base44String::new(String::from_sql(bytes)?)?)
+    }
+}
+
+/// The text is encoded while binding, so this needs a backend that copies bind
+/// values into a byte buffer (PostgreSQL, MySQL). On SQLite, bind a
+/// [`Base44String`] instead.
+impl<DB> ToSql<Text, DB> for Base44Bytes
+where
+    for<'c> DB: Backend<BindCollector<'c> = RawBytesBindCollector<DB>>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        out.write_all(encode(&self.0).as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl<DB: Backend> FromSql<Text, DB> for Base44Bytes
+where
+    String: FromSql<Text, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        Ok(Base44Bytes(decode(&String::from_sql(bytes)?)?))
+    }
+}
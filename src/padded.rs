@@ -0,0 +1,97 @@
+//! Constant-width output for fixed-width label templates and database columns.
+
+use crate::{Base44Error, b44_val, decode, encode, try_encode_bits};
+
+/// Byte-pair encode `input` and right-pad it with `pad` to exactly `width`
+/// characters.
+///
+/// `pad` must lie outside the alphabet (a space, as in QR alphanumeric mode, or
+/// e.g. `_`) so [`decode_padded`] can strip it unambiguously; otherwise this
+/// returns [`Base44Error::InvalidChar`]. Returns [`Base44Error::TooLarge`] if the
+/// encoded text is longer than `width`.
+///
+/// ```
+/// use qr_base44::{decode_padded, encode_padded};
+///
+/// let s = encode_padded(b"hi", 6, ' ').unwrap();
+/// assert_eq!(s, "LZD   ");
+/// assert_eq!(decode_padded(&s, ' ').unwrap(), b"hi");
+/// ```
+pub fn encode_padded(input: &[u8], width: usize, pad: char) -> Result<String, Base44Error> {
+    if pad.is_ascii() && b44_val(pad as u8).is_some() {
+        return Err(Base44Error::InvalidChar);
+    }
+    let mut out = encode(input);
+    let fill = width.checked_sub(out.len()).ok_or(Base44Error::TooLarge)?;
+    out.extend(core::iter::repeat_n(pad, fill));
+    Ok(out)
+}
+
+/// Decode the output of [`encode_padded`], removing trailing `pad` characters.
+pub fn decode_padded(s: &str, pad: char) -> Result<Vec<u8>, Base44Error> {
+    decode(s.trim_end_matches(pad))
+}
+
+/// Optimally encode `bits` bits of `bytes` (as [`encode_bits`](crate::encode_bits))
+/// and left-pad with `0` to exactly `width` characters.
+///
+/// `0` is the zero digit and the output is most-significant digit first, so the
+/// padding does not change the value: [`decode_bits`](crate::decode_bits) reads
+/// the padded text as is. Errors are those of [`try_encode_bits`], plus
+/// [`Base44Error::TooLarge`] if the minimal encoding is longer than `width`.
+///
+/// ```
+/// use qr_base44::{decode_bits, encode_bits_padded};
+///
+/// let s = encode_bits_padded(7, &[0x7F], 5).unwrap();
+/// assert_eq!(s, "0002+");
+/// assert_eq!(decode_bits(7, &s).unwrap(), [0x7F]);
+/// ```
+pub fn encode_bits_padded(bits: usize, bytes: &[u8], width: usize) -> Result<String, Base44Error> {
+    let text = try_encode_bits(bits, bytes)?;
+    let fill = width.checked_sub(text.len()).ok_or(Base44Error::TooLarge)?;
+    let mut out = String::with_capacity(width);
+    out.extend(core::iter::repeat_n('0', fill));
+    out.push_str(&text);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode_bits;
+
+    #[test]
+    fn byte_pair_padding_roundtrips() {
+        for len in 0..12 {
+            let data: Vec<u8> = (0..len).map(|i| 0xF0 ^ i).collect();
+            for pad in [' ', '_', '·'] {
+                let s = encode_padded(&data, 18, pad).unwrap();
+                assert_eq!(s.chars().count(), 18);
+                assert_eq!(decode_padded(&s, pad).unwrap(), data);
+            }
+        }
+        assert_eq!(encode_padded(b"hi", 2, ' '), Err(Base44Error::TooLarge));
+        assert_eq!(encode_padded(b"hi", 3, ' ').unwrap(), "LZD");
+        assert_eq!(encode_padded(b"hi", 6, '0'), Err(Base44Error::InvalidChar));
+    }
+
+    #[test]
+    fn optimal_padding_keeps_the_value() {
+        for bits in [1usize, 7, 64, 65, 103, 129, 256] {
+            let mut bytes = vec![0xA5; bits.div_ceil(8)];
+            *bytes.last_mut().unwrap() &= 0xFF >> ((8 - bits % 8) % 8);
+            let s = encode_bits_padded(bits, &bytes, 60).unwrap();
+            assert_eq!(s.len(), 60);
+            assert_eq!(decode_bits(bits, &s).unwrap(), bytes, "bits {bits}");
+        }
+        assert_eq!(
+            encode_bits_padded(103, &[0; 13], 18),
+            Err(Base44Error::TooLarge)
+        );
+        assert_eq!(
+            encode_bits_padded(0, &[], 4),
+            Err(Base44Error::InvalidLength)
+        );
+    }
+}
@@ -0,0 +1,138 @@
+//! Multi-threaded byte-pair encoding for large in-memory inputs.
+
+use std::io::{self, Write};
+use std::thread;
+
+use crate::sink::{IoSink, Sink};
+use crate::{ascii_to_string, encode_into, encoded_len};
+
+/// Smallest piece handed to a thread; below this, spawning costs more than it saves.
+const MIN_PIECE: usize = 64 * 1024;
+/// Input bytes each thread encodes per round when streaming; even, so pieces
+/// stay group-aligned.
+const STREAM_PIECE: usize = 1024 * 1024;
+
+/// [`encode`](crate::encode) split across up to `threads` scoped threads.
+///
+/// The input is cut at even offsets, so every piece encodes to whole groups that
+/// concatenate to exactly the single-threaded output. Each thread writes
+/// directly into its slice of the result; nothing else is allocated. Pieces are
+/// at least 64 KiB, so small inputs use fewer threads (or just one).
+///
+/// The whole encoding is held in the returned `String`; to encode a
+/// memory-mapped file with bounded memory, use [`encode_parallel_to_writer`].
+///
+/// ```
+/// let data = vec![0x5A; 100_001];
+/// assert_eq!(qr_base44::encode_parallel(&data, 4), qr_base44::encode(&data));
+/// ```
+pub fn encode_parallel(input: &[u8], threads: usize) -> String {
+    let mut out = vec![0u8; encoded_len(input.len())];
+    let piece = input
+        .len()
+        .div_ceil(threads.max(1))
+        .max(MIN_PIECE)
+        .next_multiple_of(2);
+    thread::scope(|scope| {
+        let mut rest = out.as_mut_slice();
+        for chunk in input.chunks(piece) {
+            let (head, tail) = rest.split_at_mut(encoded_len(chunk.len()));
+            rest = tail;
+            scope.spawn(move || encode_into(chunk, head));
+        }
+    });
+    ascii_to_string(out)
+}
+
+/// [`encode_parallel`] streamed into `writer`, returning the number of
+/// characters written.
+///
+/// The input is encoded in rounds of up to `threads` group-aligned 1 MiB
+/// pieces, and each round is written out in order before the next starts, so
+/// memory stays at `threads` piece buffers however large `input` is. Pass a
+/// memory-mapped file's bytes to encode it without reading it into memory
+/// first. The writer is flushed at the end.
+///
+/// ```
+/// let data = vec![0x5A; 100_001];
+/// let mut out = Vec::new();
+/// let n = qr_base44::encode_parallel_to_writer(&data, 4, &mut out).unwrap();
+/// assert_eq!(n, out.len());
+/// assert_eq!(out, qr_base44::encode(&data).into_bytes());
+/// ```
+pub fn encode_parallel_to_writer<W: Write + ?Sized>(
+    input: &[u8],
+    threads: usize,
+    writer: &mut W,
+) -> io::Result<usize> {
+    encode_parallel_to_sink(input, threads, STREAM_PIECE, &mut IoSink(&mut *writer))?;
+    writer.flush()?;
+    Ok(encoded_len(input.len()))
+}
+
+/// Encode `input` into `sink` in rounds of up to `threads` pieces of `piece`
+/// bytes, each encoded on its own thread into a reused buffer.
+fn encode_parallel_to_sink<S: Sink + ?Sized>(
+    input: &[u8],
+    threads: usize,
+    piece: usize,
+    sink: &mut S,
+) -> Result<(), S::Error> {
+    debug_assert!(piece % 2 == 0);
+    let threads = threads.max(1);
+    let mut bufs = vec![Vec::new(); threads.min(input.len().div_ceil(piece))];
+    for round in input.chunks(piece * threads) {
+        thread::scope(|scope| {
+            for (chunk, buf) in round.chunks(piece).zip(&mut bufs) {
+                buf.resize(encoded_len(chunk.len()), 0);
+                scope.spawn(move || encode_into(chunk, buf));
+            }
+        });
+        for (chunk, buf) in round.chunks(piece).zip(&bufs) {
+            sink.put(&buf[..encoded_len(chunk.len())])?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encode;
+
+    #[test]
+    fn matches_encode_for_any_thread_count() {
+        let data: Vec<u8> = (0..300_001u32).map(|i| (i * 97 + 5) as u8).collect();
+        for len in [0, 1, 2, 3, MIN_PIECE + 1, 300_000, 300_001] {
+            for threads in [0, 1, 2, 3, 8] {
+                assert_eq!(
+                    encode_parallel(&data[..len], threads),
+                    encode(&data[..len]),
+                    "len {len} threads {threads}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn streaming_matches_encode_across_rounds() {
+        let data: Vec<u8> = (0..10_001u32).map(|i| (i * 97 + 5) as u8).collect();
+        for len in [0, 1, 2, 999, 1000, 1001, 4000, 10_001] {
+            for threads in [0, 1, 3, 8] {
+                let mut out = Vec::new();
+                let Ok(()) = encode_parallel_to_sink(&data[..len], threads, 1000, &mut out);
+                assert_eq!(
+                    out,
+                    encode(&data[..len]).as_bytes(),
+                    "len {len} threads {threads}"
+                );
+            }
+        }
+        let mut out = Vec::new();
+        let n = encode_parallel_to_writer(&data, 2, &mut out).unwrap();
+        assert_eq!(
+            (n, out),
+            (encoded_len(data.len()), encode(&data).into_bytes())
+        );
+    }
+}
@@ -0,0 +1,258 @@
+//! Application payloads: type tag, version, optional compression and checksum in
+//! one encoded string.
+//!
+//! Layout, before byte-pair encoding: the tag byte, a byte holding
+//! `version << 1 | compressed`, the (possibly DEFLATE-compressed) data, and a
+//! big-endian CRC-32 of everything before it. The first group therefore holds
+//! the tag, as in [`tagged`](crate::tagged), so [`tagged::peek_tag`](crate::tagged::peek_tag)
+//! works on these strings too.
+
+use core::fmt;
+
+#[cfg(feature = "compression")]
+use crate::compress::{self, Framing};
+use crate::crc::crc32;
+use crate::{Base44Error, decode, encode};
+
+/// Largest decompressed body [`QrPayload::parse`] accepts, so a small code
+/// cannot expand into an arbitrarily large allocation.
+pub const MAX_INFLATED_LEN: usize = 1 << 20;
+
+/// Why [`QrPayload::parse`] failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum QrPayloadError {
+    /// The text is not valid Base44, is too short for the header and checksum,
+    /// or fails the checksum.
+    Decode(Base44Error),
+    /// The body is compressed but the `compression` feature is disabled.
+    CompressionUnsupported,
+    /// The compressed body is corrupt or inflates past [`MAX_INFLATED_LEN`].
+    Decompress,
+}
+
+impl fmt::Display for QrPayloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QrPayloadError::Decode(err) => err.fmt(f),
+            QrPayloadError::CompressionUnsupported => {
+                f.write_str("payload is compressed but the `compression` feature is disabled")
+            }
+            QrPayloadError::Decompress => f.write_str("compressed payload is corrupt or too large"),
+        }
+    }
+}
+
+impl core::error::Error for QrPayloadError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            QrPayloadError::Decode(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<Base44Error> for QrPayloadError {
+    fn from(err: Base44Error) -> Self {
+        QrPayloadError::Decode(err)
+    }
+}
+
+/// A parsed application payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QrPayload {
+    /// Application-defined type tag.
+    pub tag: u8,
+    /// Application-defined format version, `0..=127`.
+    pub version: u8,
+    /// Whether the body was stored compressed.
+    pub compressed: bool,
+    /// The (decompressed) data.
+    pub data: Vec<u8>,
+}
+
+impl QrPayload {
+    /// Verify and unpack a string produced by [`QrPayloadBuilder::encode`].
+    ///
+    /// ```
+    /// use qr_base44::{QrPayload, QrPayloadBuilder};
+    ///
+    /// let s = QrPayloadBuilder::new().tag(0x07).version(2).encode(b"ticket 42");
+    /// let p = QrPayload::parse(&s).unwrap();
+    /// assert_eq!((p.tag, p.version, &p.data[..]), (0x07, 2, &b"ticket 42"[..]));
+    /// ```
+    pub fn parse(s: &str) -> Result<Self, QrPayloadError> {
+        let mut bytes = decode(s)?;
+        let body_len = bytes
+            .len()
+            .checked_sub(6)
+            .ok_or(Base44Error::InvalidLength)?;
+        let check = bytes.split_off(body_len + 2);
+        if check != crc32(&bytes).to_be_bytes() {
+            return Err(Base44Error::ChecksumMismatch.into());
+        }
+        let (tag, flags) = (bytes[0], bytes[1]);
+        let compressed = flags & 1 == 1;
+        bytes.drain(..2);
+        let data = match compressed {
+            true => inflate(&bytes)?,
+            false => bytes,
+        };
+        Ok(Self {
+            tag,
+            version: flags >> 1,
+            compressed,
+            data,
+        })
+    }
+}
+
+#[cfg(feature = "compression")]
+fn inflate(body: &[u8]) -> Result<Vec<u8>, QrPayloadError> {
+    compress::inflate(body, Framing::Raw).ok_or(QrPayloadError::Decompress)
+}
+
+#[cfg(not(feature = "compression"))]
+fn inflate(_: &[u8]) -> Result<Vec<u8>, QrPayloadError> {
+    Err(QrPayloadError::CompressionUnsupported)
+}
+
+/// Builder for [`QrPayload`] strings.
+///
+/// Defaults: tag 0, version 0, no compression.
+///
+/// ```
+/// use qr_base44::{QrPayload, QrPayloadBuilder};
+///
+/// let builder = QrPayloadBuilder::new().tag(0x01).version(1);
+/// let s = builder.encode(b"ssid=home;psk=secret");
+/// assert_eq!(QrPayload::parse(&s).unwrap().data, b"ssid=home;psk=secret");
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QrPayloadBuilder {
+    tag: u8,
+    version: u8,
+    #[cfg(feature = "compression")]
+    compress: bool,
+}
+
+impl QrPayloadBuilder {
+    /// Start from the defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the type tag.
+    pub fn tag(mut self, tag: u8) -> Self {
+        self.tag = tag;
+        self
+    }
+
+    /// Set the format version.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `version` is over 127.
+    pub fn version(mut self, version: u8) -> Self {
+        assert!(version <= 127, "payload version must be <= 127");
+        self.version = version;
+        self
+    }
+
+    /// DEFLATE-compress the data when that makes the output shorter.
+    #[cfg(feature = "compression")]
+    pub fn compress(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
+    }
+
+    /// Assemble and encode `data`.
+    pub fn encode(&self, data: &[u8]) -> String {
+        let deflated = self.deflate(data);
+        let compressed = deflated.is_some();
+        let body = deflated.as_deref().unwrap_or(data);
+        let mut bytes = Vec::with_capacity(body.len() + 6);
+        bytes.push(self.tag);
+        bytes.push(self.version << 1 | compressed as u8);
+        bytes.extend_from_slice(body);
+        let check = crc32(&bytes).to_be_bytes();
+        bytes.extend_from_slice(&check);
+        encode(&bytes)
+    }
+
+    /// The compressed body, if compression is on and pays off.
+    #[cfg(feature = "compression")]
+    fn deflate(&self, data: &[u8]) -> Option<Vec<u8>> {
+        if !self.compress {
+            return None;
+        }
+        let deflated = compress::deflate(data, Framing::Raw);
+        (deflated.len() < data.len()).then_some(deflated)
+    }
+
+    #[cfg(not(feature = "compression"))]
+    fn deflate(&self, _: &[u8]) -> Option<Vec<u8>> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_and_checksum() {
+        let builder = QrPayloadBuilder::new().tag(0xAB).version(127);
+        let s = builder.encode(b"");
+        assert_eq!(crate::tagged::peek_tag(&s), Ok(0xAB));
+        assert_eq!(
+            QrPayload::parse(&s).unwrap(),
+            QrPayload {
+                tag: 0xAB,
+                version: 127,
+                compressed: false,
+                data: vec![]
+            }
+        );
+        let mut bytes = decode(&builder.encode(b"data")).unwrap();
+        bytes[3] ^= 1;
+        assert_eq!(
+            QrPayload::parse(&encode(&bytes)),
+            Err(QrPayloadError::Decode(Base44Error::ChecksumMismatch))
+        );
+        assert_eq!(
+            QrPayload::parse("LZD"),
+            Err(QrPayloadError::Decode(Base44Error::InvalidLength))
+        );
+
+        // A compressed flag with a valid checksum but a garbage body.
+        let mut bytes = vec![0, 1, 0xFF, 0xFF];
+        bytes.extend_from_slice(&crc32(&bytes).to_be_bytes());
+        assert!(matches!(
+            QrPayload::parse(&encode(&bytes)),
+            Err(QrPayloadError::Decompress | QrPayloadError::CompressionUnsupported)
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "version")]
+    fn version_is_seven_bits() {
+        let _ = QrPayloadBuilder::new().version(128);
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn compresses_only_when_smaller() {
+        let builder = QrPayloadBuilder::new().tag(3).compress(true);
+        let text = b"repeat repeat repeat repeat repeat repeat repeat repeat".repeat(4);
+        let s = builder.encode(&text);
+        assert!(s.len() < QrPayloadBuilder::new().tag(3).encode(&text).len());
+        let p = QrPayload::parse(&s).unwrap();
+        assert!(p.compressed);
+        assert_eq!(p.data, text);
+
+        let p = QrPayload::parse(&builder.encode(b"xy")).unwrap();
+        assert!(!p.compressed);
+        assert_eq!(p.data, b"xy");
+    }
+}
@@ -0,0 +1,6 @@
+//! Convenience re-exports: `use qr_base44::prelude::*;`.
+
+pub use crate::{
+    Base44Error, Base44String, DecoderBuilder, DigitOrder, EncoderBuilder, Engine, FromBase44,
+    Mode, ToBase44,
+};
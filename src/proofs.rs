@@ -0,0 +1,67 @@
+//! Kani proof harnesses (`cargo kani`).
+//!
+//! Inputs are bounded so the model checker can explore every value exhaustively;
+//! the codec processes independent 3-character groups, so short inputs already
+//! cover every path through the group and tail logic.
+
+use crate::{decode_bytes, decoded_len, encode, encoded_len};
+
+const MAX_BYTES: usize = 4;
+const MAX_CHARS: usize = 7;
+
+/// `decode(encode(x)) == x` for every input of up to `MAX_BYTES` bytes.
+#[kani::proof]
+#[kani::unwind(8)]
+fn roundtrip() {
+    let data: [u8; MAX_BYTES] = kani::any();
+    let len: usize = kani::any();
+    kani::assume(len <= MAX_BYTES);
+    let input = &data[..len];
+
+    let encoded = encode(input);
+    assert_eq!(encoded.len(), encoded_len(len));
+    assert_eq!(decode_bytes(encoded.as_bytes()).unwrap(), input);
+}
+
+/// Decoding arbitrary bytes never panics, and accepted input has the predicted
+/// length and re-encodes to itself.
+#[kani::proof]
+#[kani::unwind(8)]
+fn decode_never_panics() {
+    let text: [u8; MAX_CHARS] = kani::any();
+    let len: usize = kani::any();
+    kani::assume(len <= MAX_CHARS);
+    let input = &text[..len];
+
+    if let Ok(bytes) = decode_bytes(input) {
+        assert_eq!(Some(bytes.len()), decoded_len(len));
+        assert_eq!(encode(&bytes).as_bytes(), input);
+    }
+}
+
+/// Every 3-character group above `0xFFFF` and every tail above `0xFF` is rejected.
+#[kani::proof]
+fn out_of_range_groups_overflow() {
+    let value: u32 = kani::any();
+    kani::assume(value > 0xFFFF && value < 44 * 44 * 44);
+    let group = [
+        crate::BASE44_ALPHABET[(value % 44) as usize],
+        crate::BASE44_ALPHABET[(value / 44 % 44) as usize],
+        crate::BASE44_ALPHABET[(value / (44 * 44)) as usize],
+    ];
+    assert!(matches!(
+        decode_bytes(&group),
+        Err(crate::Base44Error::Overflow)
+    ));
+
+    let tail: u32 = kani::any();
+    kani::assume(tail > 0xFF && tail < 44 * 44);
+    let group = [
+        crate::BASE44_ALPHABET[(tail % 44) as usize],
+        crate::BASE44_ALPHABET[(tail / 44) as usize],
+    ];
+    assert!(matches!(
+        decode_bytes(&group),
+        Err(crate::Base44Error::Overflow)
+    ));
+}
@@ -0,0 +1,72 @@
+//! `quickcheck::Arbitrary` for the validated string types ([`quickcheck`] feature).
+//!
+//! Values are generated and shrunk through their decoded bytes, so every
+//! candidate stays a valid encoding and a failing case shrinks towards the
+//! shortest input that still fails, down to the empty string.
+
+use quickcheck::{Arbitrary, Gen};
+
+use crate::{Base44Bytes, Base44String};
+
+impl Arbitrary for Base44String {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Self::from_bytes(&Vec::<u8>::arbitrary(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(self.decode().shrink().map(|bytes| Self::from_bytes(&bytes)))
+    }
+}
+
+impl Arbitrary for Base44Bytes {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Self::from(Vec::<u8>::arbitrary(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(self.0.shrink().map(Self::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode;
+
+    #[test]
+    fn generated_and_shrunk_values_are_valid() {
+        let mut g = Gen::new(64);
+        for _ in 0..100 {
+            let s = Base44String::arbitrary(&mut g);
+            assert_eq!(decode(s.as_str()).unwrap(), s.decode());
+            for smaller in s.shrink().take(20) {
+                assert!(decode(smaller.as_str()).is_ok());
+            }
+        }
+        let s = Base44String::from_bytes(b"abc");
+        assert_eq!(s.shrink().next().unwrap().as_str(), "");
+    }
+
+    #[test]
+    fn failing_property_shrinks_to_a_minimal_case() {
+        // "No encoding contains 'Z'": greedily shrink a large counterexample.
+        let fails = |s: &Base44String| s.contains('Z');
+        let mut s = Base44String::from_bytes(&(0..=255).collect::<Vec<u8>>());
+        assert!(fails(&s));
+        while let Some(smaller) = s.shrink().find(fails) {
+            s = smaller;
+        }
+        // One group: a single byte, or a pair neither of whose bytes fails alone.
+        assert!(s.len() <= 3, "{s}");
+    }
+
+    #[test]
+    fn quickcheck_runs_with_the_impls() {
+        fn roundtrip(s: Base44String, b: Base44Bytes) -> bool {
+            Base44String::from_bytes(&s.decode()) == s && b.to_string().parse() == Ok(b)
+        }
+        quickcheck::QuickCheck::new()
+            .tests(200)
+            .quickcheck(roundtrip as fn(Base44String, Base44Bytes) -> bool);
+    }
+}
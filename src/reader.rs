@@ -0,0 +1,181 @@
+//! [`Frame103Reader`]: 103-bit records from an [`io::Read`](std::io::Read).
+
+use std::io::{self, ErrorKind, Read};
+
+use crate::stream::invalid_data;
+use crate::{Base44Error, chars_needed, decode_103bits};
+
+/// Characters per record.
+const RECORD: usize = chars_needed(103);
+
+/// Reads concatenated 19-character [`encode_103bits`](crate::encode_103bits)
+/// records from `R`, yielding one `[u8; 13]` frame per record.
+///
+/// The streaming counterpart of [`iter_frames_103`](crate::iter_frames_103):
+/// only one record is held at a time, so a long-running ingestion loop can
+/// consume a sensor feed of any length.
+///
+/// Errors are per frame. A corrupt record yields an
+/// [`io::ErrorKind::InvalidData`] error wrapping its [`Base44Error`] and
+/// reading carries on with the next record; a trailing partial record is
+/// [`Base44Error::InvalidLength`]. An error from `R` itself ends the
+/// iteration. `R` is read 19 bytes at a time, so wrap unbuffered sources in a
+/// [`BufReader`](std::io::BufReader).
+///
+/// ```
+/// use qr_base44::{Frame103Reader, encode_103bits};
+///
+/// let feed = encode_103bits(&[1; 13]) + "not a valid record!" + &encode_103bits(&[2; 13]);
+/// let frames: Vec<_> = Frame103Reader::new(feed.as_bytes()).collect();
+/// assert_eq!(frames.len(), 3);
+/// assert_eq!(frames[0].as_ref().unwrap(), &[1; 13]);
+/// assert!(frames[1].is_err());
+/// assert_eq!(frames[2].as_ref().unwrap(), &[2; 13]);
+/// ```
+#[derive(Debug)]
+pub struct Frame103Reader<R> {
+    inner: R,
+    frames: u64,
+    done: bool,
+}
+
+impl<R: Read> Frame103Reader<R> {
+    /// Read records from `inner`.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            frames: 0,
+            done: false,
+        }
+    }
+
+    /// Records read so far, good or bad: the index of the next one.
+    pub fn frames_read(&self) -> u64 {
+        self.frames
+    }
+
+    /// The inner reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Unwrap the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Fill `record`, returning how many bytes arrived before end of input.
+    fn fill(&mut self, record: &mut [u8; RECORD]) -> io::Result<usize> {
+        let mut len = 0;
+        while len < RECORD {
+            match self.inner.read(&mut record[len..]) {
+                Ok(0) => break,
+                Ok(n) => len += n,
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(len)
+    }
+}
+
+impl<R: Read> Iterator for Frame103Reader<R> {
+    type Item = io::Result<[u8; 13]>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let mut record = [0u8; RECORD];
+        let len = match self.fill(&mut record) {
+            Ok(0) => {
+                self.done = true;
+                return None;
+            }
+            Ok(len) => len,
+            Err(err) => {
+                self.done = true;
+                return Some(Err(err));
+            }
+        };
+        self.frames += 1;
+        if len < RECORD {
+            self.done = true;
+            return Some(Err(invalid_data(Base44Error::InvalidLength)));
+        }
+        let frame = core::str::from_utf8(&record)
+            .map_err(|_| Base44Error::InvalidChar)
+            .and_then(decode_103bits);
+        Some(frame.map_err(invalid_data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{encode_103bits, iter_frames_103};
+
+    /// Hands out at most three bytes per `read`, interrupting every other call.
+    struct Trickle<'a> {
+        data: &'a [u8],
+        interrupt: bool,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(ErrorKind::Interrupted.into());
+            }
+            let n = buf.len().min(3).min(self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    fn base44_error(err: &io::Error) -> Option<Base44Error> {
+        err.get_ref()?.downcast_ref().copied()
+    }
+
+    #[test]
+    fn matches_iter_frames_103() {
+        let mut max = [0xFF; 13];
+        max[12] = 0x7F;
+        let feed = [
+            encode_103bits(&[0; 13]),
+            "a".repeat(19),
+            encode_103bits(&max),
+            "0".repeat(7),
+        ]
+        .concat();
+        let reader = Frame103Reader::new(Trickle {
+            data: feed.as_bytes(),
+            interrupt: false,
+        });
+        let got: Vec<_> = reader
+            .map(|frame| frame.map_err(|err| base44_error(&err).unwrap()))
+            .collect();
+        assert_eq!(got, iter_frames_103(&feed).collect::<Vec<_>>());
+        assert_eq!(got[3], Err(Base44Error::InvalidLength));
+
+        let mut empty = Frame103Reader::new(&b""[..]);
+        assert!(empty.next().is_none());
+        assert_eq!(empty.frames_read(), 0);
+    }
+
+    #[test]
+    fn io_errors_end_the_stream() {
+        struct Broken;
+        impl Read for Broken {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("unplugged"))
+            }
+        }
+        let mut reader = Frame103Reader::new(Broken);
+        let err = reader.next().unwrap().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Other);
+        assert_eq!(base44_error(&err), None);
+        assert!(reader.next().is_none());
+    }
+}
@@ -0,0 +1,135 @@
+//! Opt-in repair of mis-transcribed or mis-encoded input before decoding.
+
+use std::borrow::Cow;
+
+use crate::{Base44Error, decode};
+
+/// Default substitutions for codes typed from printed labels: letters that look
+/// like digits, plus the lower-case forms a phone keyboard produces.
+///
+/// `O`, `I` and `S` are themselves alphabet characters, so only use this table
+/// when the codes being read are known not to contain them (e.g. digit-heavy
+/// labels). Pass a custom table to [`decode_repaired`] otherwise.
+pub const DEFAULT_CONFUSABLES: &[(char, char)] = &[
+    ('O', '0'),
+    ('o', '0'),
+    ('I', '1'),
+    ('i', '1'),
+    ('l', '1'),
+    ('S', '5'),
+    ('s', '5'),
+];
+
+/// One substitution made by [`decode_repaired`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Repair {
+    /// Character index (not byte offset) in the input.
+    pub index: usize,
+    /// The character found.
+    pub from: char,
+    /// The character it was replaced with.
+    pub to: char,
+}
+
+/// Decode byte-pair text after applying `confusables` (`(from, to)` pairs),
+/// returning the bytes together with every substitution made.
+///
+/// ```
+/// use qr_base44::{DEFAULT_CONFUSABLES, Repair, decode_repaired, encode};
+///
+/// assert_eq!(encode(&[0x00, 0x01]), "100");
+/// let (bytes, repairs) = decode_repaired("10O", DEFAULT_CONFUSABLES).unwrap();
+/// assert_eq!(bytes, [0x00, 0x01]);
+/// assert_eq!(repairs, [Repair { index: 2, from: 'O', to: '0' }]);
+/// ```
+pub fn decode_repaired(
+    s: &str,
+    confusables: &[(char, char)],
+) -> Result<(Vec<u8>, Vec<Repair>), Base44Error> {
+    let mut repairs = Vec::new();
+    let mut repaired = String::with_capacity(s.len());
+    for (index, from) in s.chars().enumerate() {
+        match confusables.iter().find(|(c, _)| *c == from) {
+            Some(&(_, to)) => {
+                repairs.push(Repair { index, from, to });
+                repaired.push(to);
+            }
+            None => repaired.push(from),
+        }
+    }
+    Ok((decode(&repaired)?, repairs))
+}
+
+/// Map full-width ASCII variants (U+FF01..=U+FF5E, e.g. `Ａ`, `１`, `：`) to their
+/// ASCII counterparts, as emitted by Japanese OCR and some mobile keyboards.
+///
+/// This is the subset of NFKC that matters for the alphabet; all other characters
+/// are left alone. Borrows when there is nothing to map.
+pub fn normalize_width(s: &str) -> Cow<'_, str> {
+    const OFFSET: u32 = 0xFF01 - 0x21;
+    let is_wide = |c: char| ('\u{FF01}'..='\u{FF5E}').contains(&c);
+    if !s.contains(is_wide) {
+        return Cow::Borrowed(s);
+    }
+    s.chars()
+        .map(|c| match is_wide(c) {
+            true => char::from_u32(c as u32 - OFFSET).expect("maps into ASCII"),
+            false => c,
+        })
+        .collect()
+}
+
+/// Decode byte-pair text after [`normalize_width`].
+///
+/// ```
+/// use qr_base44::decode_normalized;
+///
+/// assert_eq!(decode_normalized("ＬＺＤ").unwrap(), b"hi");
+/// assert_eq!(decode_normalized("1０0").unwrap(), [0x00, 0x01]);
+/// ```
+pub fn decode_normalized(s: &str) -> Result<Vec<u8>, Base44Error> {
+    decode(&normalize_width(s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encode;
+
+    #[test]
+    fn repairs_are_reported_by_char_index() {
+        assert_eq!(encode(&[0x00, 0x00, 0x00, 0x01]), "000100");
+        let table = [('o', '0'), ('O', '0'), ('Ｏ', '0')];
+        let (bytes, repairs) = decode_repaired("Ｏ0o1O0", &table).unwrap();
+        assert_eq!(bytes, [0x00, 0x00, 0x00, 0x01]);
+        let indices: Vec<usize> = repairs.iter().map(|r| r.index).collect();
+        assert_eq!(indices, [0, 2, 4]);
+        assert_eq!(repairs[0].from, 'Ｏ');
+    }
+
+    #[test]
+    fn custom_table_and_errors() {
+        // An empty table is plain decoding.
+        assert_eq!(
+            decode_repaired("LZD", &[]).unwrap(),
+            (b"hi".to_vec(), vec![])
+        );
+        assert_eq!(decode_repaired("lzd", &[]), Err(Base44Error::InvalidChar));
+        let upper: Vec<(char, char)> = ('a'..='z').map(|c| (c, c.to_ascii_uppercase())).collect();
+        let (bytes, repairs) = decode_repaired("lzd", &upper).unwrap();
+        assert_eq!(bytes, b"hi");
+        assert_eq!(repairs.len(), 3);
+    }
+
+    #[test]
+    fn full_width_forms_are_normalized() {
+        let wide: String = crate::BASE44_ALPHABET
+            .iter()
+            .map(|&b| char::from_u32(b as u32 + 0xFF01 - 0x21).unwrap())
+            .collect();
+        assert_eq!(normalize_width(&wide).as_bytes(), crate::BASE44_ALPHABET);
+        assert!(matches!(normalize_width("LZD"), Cow::Borrowed("LZD")));
+        assert_eq!(normalize_width("ａ\u{3000}é"), "a\u{3000}é");
+        assert_eq!(decode_normalized("ｌｚｄ"), Err(Base44Error::InvalidChar));
+    }
+}
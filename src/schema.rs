@@ -0,0 +1,38 @@
+//! JSON Schema for [`Base44String`] ([`schemars`] feature).
+
+use std::borrow::Cow;
+
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+
+use crate::Base44String;
+
+impl JsonSchema for Base44String {
+    fn schema_name() -> Cow<'static, str> {
+        "Base44String".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        "qr_base44::Base44String".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "pattern": Base44String::PATTERN,
+            "description": "Byte-pair Base44 text (alphabet 0-9A-Z$%*+-./:); 3 characters per 2 bytes, 2 for a trailing odd byte.",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn documents_a_patterned_string() {
+        let schema = schemars::schema_for!(Base44String);
+        assert_eq!(schema.get("type").unwrap(), "string");
+        assert_eq!(schema.get("pattern").unwrap(), Base44String::PATTERN);
+        assert_eq!(schema.get("title").unwrap(), "Base44String");
+    }
+}
@@ -0,0 +1,228 @@
+//! Sealed payloads: compress, encrypt and encode in one call ([`secure`]
+//! feature).
+//!
+//! [`secure_seal`] optionally DEFLATE-compresses the data, encrypts it with
+//! XChaCha20-Poly1305 under a fresh random 192-bit nonce, and byte-pair
+//! encodes the result. The nonce is long enough that random nonces never
+//! realistically repeat under one key, so callers have no nonce state to keep.
+//!
+//! Layout, before encoding: a format version byte, a flags byte (bit 0:
+//! compressed), the 24-byte nonce, then the ciphertext and its 16-byte tag.
+//! The version, flags and nonce are authenticated as associated data, so a
+//! flipped compression flag is rejected like any other tampering.
+
+use core::fmt;
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+
+use crate::compress::{self, Framing};
+use crate::{Base44Error, decode, encode};
+
+/// Format version written by [`secure_seal`].
+const VERSION: u8 = 1;
+/// Flags bit: the plaintext was compressed before encryption.
+const COMPRESSED: u8 = 1;
+/// Version, flags and nonce.
+const HEADER_LEN: usize = 2 + 24;
+/// Poly1305 tag bytes.
+const TAG_LEN: usize = 16;
+
+/// Why [`secure_open`] failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SecureError {
+    /// The text is not valid Base44, or is too short to be sealed.
+    Decode(Base44Error),
+    /// The format version or flags are not ones this crate writes.
+    UnsupportedFormat {
+        /// The version byte.
+        version: u8,
+        /// The flags byte.
+        flags: u8,
+    },
+    /// Wrong key, or the text was altered.
+    Unauthenticated,
+    /// The authenticated body does not inflate, or inflates past
+    /// [`MAX_INFLATED_LEN`](crate::MAX_INFLATED_LEN).
+    Decompress,
+}
+
+impl fmt::Display for SecureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SecureError::Decode(err) => err.fmt(f),
+            SecureError::UnsupportedFormat { version, flags } => write!(
+                f,
+                "unsupported sealed format (version {version}, flags 0x{flags:02x})"
+            ),
+            SecureError::Unauthenticated => f.write_str("wrong key or tampered sealed payload"),
+            SecureError::Decompress => f.write_str("sealed payload is corrupt or too large"),
+        }
+    }
+}
+
+impl core::error::Error for SecureError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            SecureError::Decode(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<Base44Error> for SecureError {
+    fn from(err: Base44Error) -> Self {
+        SecureError::Decode(err)
+    }
+}
+
+/// Options for [`secure_seal`].
+///
+/// Defaults: no compression.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SealOptions {
+    compress: bool,
+}
+
+impl SealOptions {
+    /// Start from the defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compress before encrypting when that makes the output shorter.
+    ///
+    /// Only turn this on when no part of the plaintext is controlled by an
+    /// attacker: if it mixes secrets with attacker-chosen text, the compressed
+    /// length can reveal the secret.
+    pub fn compress(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
+    }
+}
+
+/// Encrypt `data` under `key`, compressing it first if `options` ask for it,
+/// and encode the result.
+///
+/// # Panics
+///
+/// Panics if the operating system's random number generator fails.
+///
+/// ```
+/// use qr_base44::{SealOptions, SecureError, secure_open, secure_seal};
+///
+/// let key = [7u8; 32]; // derive real keys with a KDF
+/// let code = secure_seal(&key, b"door 4, code 1234", SealOptions::new());
+/// assert_eq!(secure_open(&key, &code).unwrap(), b"door 4, code 1234");
+/// assert_eq!(secure_open(&[8; 32], &code), Err(SecureError::Unauthenticated));
+/// ```
+pub fn secure_seal(key: &[u8; 32], data: &[u8], options: SealOptions) -> String {
+    let deflated = match options.compress {
+        true => Some(compress::deflate(data, Framing::Raw)).filter(|d| d.len() < data.len()),
+        false => None,
+    };
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let mut out = Vec::with_capacity(HEADER_LEN + data.len() + TAG_LEN);
+    out.push(VERSION);
+    out.push(if deflated.is_some() { COMPRESSED } else { 0 });
+    out.extend_from_slice(&nonce);
+    let sealed = XChaCha20Poly1305::new(key.into())
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: deflated.as_deref().unwrap_or(data),
+                aad: &out,
+            },
+        )
+        .expect("XChaCha20-Poly1305 accepts any message length that fits in memory");
+    out.extend_from_slice(&sealed);
+    encode(&out)
+}
+
+/// Decode, authenticate and decrypt a [`secure_seal`] string under `key`, then
+/// inflate it if it was compressed.
+pub fn secure_open(key: &[u8; 32], s: &str) -> Result<Vec<u8>, SecureError> {
+    let bytes = decode(s)?;
+    if bytes.len() < HEADER_LEN + TAG_LEN {
+        return Err(Base44Error::InvalidLength.into());
+    }
+    let (header, sealed) = bytes.split_at(HEADER_LEN);
+    let (version, flags) = (header[0], header[1]);
+    if version != VERSION || flags & !COMPRESSED != 0 {
+        return Err(SecureError::UnsupportedFormat { version, flags });
+    }
+    let body = XChaCha20Poly1305::new(key.into())
+        .decrypt(
+            XNonce::from_slice(&header[2..]),
+            Payload {
+                msg: sealed,
+                aad: header,
+            },
+        )
+        .map_err(|_| SecureError::Unauthenticated)?;
+    match flags & COMPRESSED {
+        0 => Ok(body),
+        _ => compress::inflate(&body, Framing::Raw).ok_or(SecureError::Decompress),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: [u8; 32] = [0x42; 32];
+
+    #[test]
+    fn roundtrips_with_and_without_compression() {
+        let text = b"the same words, the same words, the same words again".repeat(5);
+        let packed = secure_seal(&KEY, &text, SealOptions::new().compress(true));
+        let plain = secure_seal(&KEY, &text, SealOptions::new());
+        assert!(packed.len() < plain.len());
+        assert_eq!(
+            plain.len(),
+            crate::encoded_len(HEADER_LEN + text.len() + TAG_LEN)
+        );
+        for code in [&packed, &plain] {
+            assert_eq!(secure_open(&KEY, code).unwrap(), text);
+        }
+        assert_eq!(
+            secure_open(&KEY, &secure_seal(&KEY, b"", SealOptions::new())).unwrap(),
+            b""
+        );
+        // Fresh nonces: sealing twice never gives the same code.
+        assert_ne!(
+            secure_seal(&KEY, b"x", SealOptions::new()),
+            secure_seal(&KEY, b"x", SealOptions::new())
+        );
+    }
+
+    #[test]
+    fn rejects_tampering() {
+        let code = secure_seal(&KEY, b"attack at dawn", SealOptions::new());
+        let bytes = decode(&code).unwrap();
+        // Flipping the compression flag, the nonce or the ciphertext breaks authentication.
+        for at in [1, 2, HEADER_LEN, bytes.len() - 1] {
+            let mut bad = bytes.clone();
+            bad[at] ^= 1;
+            assert_eq!(
+                secure_open(&KEY, &encode(&bad)),
+                Err(SecureError::Unauthenticated),
+                "byte {at}"
+            );
+        }
+        let mut bad = bytes.clone();
+        bad[0] = 2;
+        assert_eq!(
+            secure_open(&KEY, &encode(&bad)),
+            Err(SecureError::UnsupportedFormat {
+                version: 2,
+                flags: 0
+            })
+        );
+        assert_eq!(
+            secure_open(&KEY, &encode(&bytes[..HEADER_LEN + TAG_LEN - 1])),
+            Err(SecureError::Decode(Base44Error::InvalidLength))
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! One byte-pair encode loop for every output target.
+//!
+//! [`encode_to_sink`] encodes into a stack buffer in fixed-size batches and
+//! hands each batch to a [`Sink`]. `String`, `fmt::Write`, `io::Write` and slice
+//! outputs differ only in their `Sink` impl, so the arithmetic lives in
+//! [`encode_into`] alone.
+
+use core::convert::Infallible;
+use core::fmt;
+use std::io;
+
+use crate::{encode_into, encoded_len};
+
+/// Input bytes per batch; even, so only the last batch can end in a lone byte.
+const BATCH: usize = 256;
+
+/// Somewhere encoded text can go.
+pub(crate) trait Sink {
+    type Error;
+
+    /// Append `ascii`, a run of alphabet characters.
+    fn put(&mut self, ascii: &[u8]) -> Result<(), Self::Error>;
+}
+
+/// Byte-pair encode `input` into `sink`, batch by batch.
+pub(crate) fn encode_to_sink<S: Sink + ?Sized>(input: &[u8], sink: &mut S) -> Result<(), S::Error> {
+    let mut buf = [0u8; BATCH / 2 * 3];
+    for chunk in input.chunks(BATCH) {
+        let out = &mut buf[..encoded_len(chunk.len())];
+        encode_into(chunk, out);
+        sink.put(out)?;
+    }
+    Ok(())
+}
+
+impl Sink for Vec<u8> {
+    type Error = Infallible;
+
+    fn put(&mut self, ascii: &[u8]) -> Result<(), Infallible> {
+        self.extend_from_slice(ascii);
+        Ok(())
+    }
+}
+
+/// A [`Sink`] over a `fmt::Write`.
+pub(crate) struct FmtSink<W>(pub W);
+
+impl<W: fmt::Write> Sink for FmtSink<W> {
+    type Error = fmt::Error;
+
+    fn put(&mut self, ascii: &[u8]) -> fmt::Result {
+        self.0
+            .write_str(core::str::from_utf8(ascii).expect("base44 alphabet is ASCII"))
+    }
+}
+
+/// A [`Sink`] over an `io::Write`.
+pub(crate) struct IoSink<W>(pub W);
+
+impl<W: io::Write> Sink for IoSink<W> {
+    type Error = io::Error;
+
+    fn put(&mut self, ascii: &[u8]) -> io::Result<()> {
+        self.0.write_all(ascii)
+    }
+}
+
+/// A [`Sink`] filling a slice from the front. The caller checks the length up
+/// front; writing past the end panics.
+pub(crate) struct SliceSink<'a> {
+    out: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> SliceSink<'a> {
+    pub(crate) fn new(out: &'a mut [u8]) -> Self {
+        Self { out, len: 0 }
+    }
+
+    /// Bytes written so far.
+    pub(crate) fn written(&self) -> usize {
+        self.len
+    }
+}
+
+impl Sink for SliceSink<'_> {
+    type Error = Infallible;
+
+    fn put(&mut self, ascii: &[u8]) -> Result<(), Infallible> {
+        self.out[self.len..self.len + ascii.len()].copy_from_slice(ascii);
+        self.len += ascii.len();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encode;
+
+    #[test]
+    fn every_sink_matches_encode() {
+        for len in [0, 1, 2, BATCH - 1, BATCH, BATCH + 1, 3 * BATCH + 7] {
+            let data: Vec<u8> = (0..len).map(|i| (i * 31 + 5) as u8).collect();
+            let expected = encode(&data);
+
+            let mut vec = Vec::new();
+            let Ok(()) = encode_to_sink(&data, &mut vec);
+            assert_eq!(vec, expected.as_bytes());
+
+            let mut text = FmtSink(String::new());
+            encode_to_sink(&data, &mut text).unwrap();
+            assert_eq!(text.0, expected);
+
+            let mut io = IoSink(Vec::new());
+            encode_to_sink(&data, &mut io).unwrap();
+            assert_eq!(io.0, expected.as_bytes());
+
+            let mut buf = vec![0; expected.len()];
+            let mut slice = SliceSink::new(&mut buf);
+            let Ok(()) = encode_to_sink(&data, &mut slice);
+            assert_eq!(slice.written(), expected.len());
+            assert_eq!(buf, expected.as_bytes());
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! Encoding into caller-provided buffers, without allocating.
+
+use core::fmt;
+
+use crate::encoded_len;
+use crate::sink::{SliceSink, encode_to_sink};
+
+/// The output buffer cannot hold the encoded text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BufferTooSmall {
+    /// Bytes the output needs, so the caller can retry with a larger buffer.
+    pub needed: usize,
+}
+
+impl fmt::Display for BufferTooSmall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "output buffer too small: {} bytes needed", self.needed)
+    }
+}
+
+impl core::error::Error for BufferTooSmall {}
+
+/// Byte-pair encode `input` into the start of `out` as ASCII, returning the
+/// number of bytes written ([`encoded_len`]`(input.len())`).
+///
+/// If `out` is shorter than that, nothing is written and the error carries the
+/// required size; bytes past the encoded text are left untouched.
+///
+/// ```
+/// use qr_base44::{BufferTooSmall, encode_to_slice};
+///
+/// let mut buf = [0u8; 4];
+/// assert_eq!(encode_to_slice(b"hi", &mut buf), Ok(3));
+/// assert_eq!(&buf[..3], b"LZD");
+/// assert_eq!(encode_to_slice(b"hi!", &mut buf), Err(BufferTooSmall { needed: 5 }));
+/// ```
+pub fn encode_to_slice(input: &[u8], out: &mut [u8]) -> Result<usize, BufferTooSmall> {
+    let needed = encoded_len(input.len());
+    let out = out.get_mut(..needed).ok_or(BufferTooSmall { needed })?;
+    let Ok(()) = encode_to_sink(input, &mut SliceSink::new(out));
+    Ok(needed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encode;
+
+    #[test]
+    fn exact_short_and_oversized_buffers() {
+        let data: Vec<u8> = (0..=20).collect();
+        for len in 0..data.len() {
+            let input = &data[..len];
+            let needed = encoded_len(len);
+            let mut buf = [b'#'; 40];
+            assert_eq!(encode_to_slice(input, &mut buf), Ok(needed));
+            assert_eq!(&buf[..needed], encode(input).as_bytes());
+            assert!(buf[needed..].iter().all(|&b| b == b'#'));
+            if needed > 0 {
+                let mut short = vec![b'#'; needed - 1];
+                assert_eq!(
+                    encode_to_slice(input, &mut short),
+                    Err(BufferTooSmall { needed })
+                );
+                assert!(short.iter().all(|&b| b == b'#'));
+            }
+        }
+    }
+}
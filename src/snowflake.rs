@@ -0,0 +1,224 @@
+//! Snowflake-style 64-bit IDs: `{timestamp, worker, sequence}` packed into one
+//! integer and encoded with the optimal packing of [`encode_bits`].
+//!
+//! A [`Layout`] fixes the custom epoch and the width of each field, most
+//! significant first. Twitter's layout ([`Layout::TWITTER`], 41 + 10 + 12 bits)
+//! needs 12 characters; 11 characters hold 60 bits, so a layout with a shorter
+//! timestamp such as [`Layout::COMPACT`] fits in 11.
+//!
+//! ```
+//! use qr_base44::snowflake::{Layout, Snowflake};
+//!
+//! let layout = Layout::TWITTER;
+//! let id = Snowflake { timestamp: 1_000, worker: 7, sequence: 42 };
+//! let s = layout.encode(id).unwrap();
+//! assert_eq!(s.len(), 12);
+//! assert_eq!(layout.decode(&s).unwrap(), id);
+//! ```
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{Base44Error, chars_needed, decode_bits, encode_bits};
+
+/// The fields of an ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Snowflake {
+    /// Milliseconds since the layout's epoch.
+    pub timestamp: u64,
+    /// Worker (machine or process) number.
+    pub worker: u64,
+    /// Per-millisecond sequence number.
+    pub sequence: u64,
+}
+
+/// Epoch and field widths of a Snowflake scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    epoch_ms: u64,
+    timestamp_bits: u32,
+    worker_bits: u32,
+    sequence_bits: u32,
+}
+
+impl Layout {
+    /// Twitter's layout: epoch 2010-11-04T01:42:54.657Z, 41-bit timestamp,
+    /// 10-bit worker, 12-bit sequence. 12 characters.
+    pub const TWITTER: Layout = Layout::new(1_288_834_974_657, 41, 10, 12);
+
+    /// 60 bits in 11 characters: epoch 2024-01-01T00:00:00Z, 38-bit timestamp
+    /// (about 8.7 years), 10-bit worker, 12-bit sequence.
+    pub const COMPACT: Layout = Layout::new(1_704_067_200_000, 38, 10, 12);
+
+    /// A layout with the given epoch (Unix milliseconds) and field widths.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the widths add up to zero or more than 64 bits.
+    pub const fn new(
+        epoch_ms: u64,
+        timestamp_bits: u32,
+        worker_bits: u32,
+        sequence_bits: u32,
+    ) -> Self {
+        let total = timestamp_bits + worker_bits + sequence_bits;
+        assert!(
+            total > 0 && total <= 64,
+            "snowflake fields must total 1..=64 bits"
+        );
+        Self {
+            epoch_ms,
+            timestamp_bits,
+            worker_bits,
+            sequence_bits,
+        }
+    }
+
+    /// Total bits of an ID.
+    pub const fn bits(&self) -> u32 {
+        self.timestamp_bits + self.worker_bits + self.sequence_bits
+    }
+
+    /// Characters of an encoded ID.
+    pub const fn encoded_len(&self) -> usize {
+        chars_needed(self.bits() as usize)
+    }
+
+    /// Pack `id` into an integer. A field too wide for its slot is
+    /// [`Base44Error::Overflow`].
+    pub fn pack(&self, id: Snowflake) -> Result<u64, Base44Error> {
+        let fits = |value: u64, bits: u32| bits >= 64 || value >> bits == 0;
+        if !fits(id.timestamp, self.timestamp_bits)
+            || !fits(id.worker, self.worker_bits)
+            || !fits(id.sequence, self.sequence_bits)
+        {
+            return Err(Base44Error::Overflow);
+        }
+        let worker_shift = self.sequence_bits;
+        let timestamp_shift = worker_shift + self.worker_bits;
+        Ok(id.timestamp.checked_shl(timestamp_shift).unwrap_or(0)
+            | id.worker.checked_shl(worker_shift).unwrap_or(0)
+            | id.sequence)
+    }
+
+    /// Split an integer produced by [`Layout::pack`]. Bits above
+    /// [`Layout::bits`] are ignored.
+    pub fn unpack(&self, raw: u64) -> Snowflake {
+        let field = |shift: u32, bits: u32| {
+            raw.checked_shr(shift).unwrap_or(0) & u64::MAX.checked_shr(64 - bits).unwrap_or(0)
+        };
+        Snowflake {
+            timestamp: field(self.sequence_bits + self.worker_bits, self.timestamp_bits),
+            worker: field(self.sequence_bits, self.worker_bits),
+            sequence: field(0, self.sequence_bits),
+        }
+    }
+
+    /// Pack and encode `id` into [`Layout::encoded_len`] characters.
+    pub fn encode(&self, id: Snowflake) -> Result<String, Base44Error> {
+        let raw = self.pack(id)?;
+        Ok(encode_bits(self.bits() as usize, &raw.to_le_bytes()))
+    }
+
+    /// Decode the output of [`Layout::encode`]. Any other length is
+    /// [`Base44Error::InvalidLength`].
+    pub fn decode(&self, s: &str) -> Result<Snowflake, Base44Error> {
+        if s.len() != self.encoded_len() {
+            return Err(Base44Error::InvalidLength);
+        }
+        let bytes = decode_bits(self.bits() as usize, s)?;
+        let mut raw = [0u8; 8];
+        raw[..bytes.len()].copy_from_slice(&bytes);
+        Ok(self.unpack(u64::from_le_bytes(raw)))
+    }
+
+    /// An ID for `time`. A time before the epoch or past the timestamp range is
+    /// [`Base44Error::Overflow`], as is a worker or sequence too wide.
+    pub fn at(
+        &self,
+        time: SystemTime,
+        worker: u64,
+        sequence: u64,
+    ) -> Result<Snowflake, Base44Error> {
+        let timestamp = time
+            .duration_since(UNIX_EPOCH)
+            .ok()
+            .and_then(|d| u64::try_from(d.as_millis()).ok())
+            .and_then(|ms| ms.checked_sub(self.epoch_ms))
+            .ok_or(Base44Error::Overflow)?;
+        let id = Snowflake {
+            timestamp,
+            worker,
+            sequence,
+        };
+        self.pack(id)?;
+        Ok(id)
+    }
+
+    /// The wall-clock time of `id`.
+    pub fn time(&self, id: Snowflake) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(self.epoch_ms.saturating_add(id.timestamp))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layouts_and_lengths() {
+        assert_eq!(Layout::TWITTER.encoded_len(), 12);
+        assert_eq!(Layout::COMPACT.encoded_len(), 11);
+        let max = Snowflake {
+            timestamp: (1 << 38) - 1,
+            worker: 1023,
+            sequence: 4095,
+        };
+        let s = Layout::COMPACT.encode(max).unwrap();
+        assert_eq!(s.len(), 11);
+        assert_eq!(Layout::COMPACT.decode(&s).unwrap(), max);
+        assert_eq!(
+            Layout::COMPACT.encode(Snowflake {
+                worker: 1024,
+                ..max
+            }),
+            Err(Base44Error::Overflow)
+        );
+        assert_eq!(Layout::TWITTER.decode(&s), Err(Base44Error::InvalidLength));
+
+        // A tweet ID from 2022-06-28T16:07:40.105Z.
+        let id = Layout::TWITTER.unpack(1_541_815_603_606_036_480);
+        let ms = Layout::TWITTER
+            .time(id)
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis();
+        assert_eq!(ms, 1_656_432_460_105);
+        assert_eq!(Layout::TWITTER.pack(id), Ok(1_541_815_603_606_036_480));
+    }
+
+    #[test]
+    fn epoch_handling() {
+        let layout = Layout::COMPACT;
+        let time = UNIX_EPOCH + Duration::from_millis(1_704_067_200_000 + 1234);
+        let id = layout.at(time, 3, 9).unwrap();
+        assert_eq!(id.timestamp, 1234);
+        assert_eq!(layout.time(id), time);
+        assert_eq!(layout.at(UNIX_EPOCH, 0, 0), Err(Base44Error::Overflow));
+        let far = UNIX_EPOCH + Duration::from_millis(1_704_067_200_000 + (1 << 38));
+        assert_eq!(layout.at(far, 0, 0), Err(Base44Error::Overflow));
+
+        let whole = Layout::new(0, 64, 0, 0);
+        let id = Snowflake {
+            timestamp: u64::MAX,
+            worker: 0,
+            sequence: 0,
+        };
+        assert_eq!(whole.decode(&whole.encode(id).unwrap()).unwrap(), id);
+    }
+
+    #[test]
+    #[should_panic(expected = "64 bits")]
+    fn oversized_layout_is_rejected() {
+        let _ = Layout::new(0, 42, 11, 12);
+    }
+}
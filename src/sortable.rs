@@ -0,0 +1,182 @@
+//! Order-preserving encoding: encoded strings sort like their input bytes.
+//!
+//! The default codec emits the least-significant digit first and its alphabet is
+//! not in ASCII order, so encoded strings sort arbitrarily. This mode fixes both:
+//!
+//! - Digits are written most-significant first using [`SORTABLE_ALPHABET`], the
+//!   same 44 characters rearranged into ASCII order.
+//! - Each 2-byte group `[u, v]` becomes the 3-digit number `u * 308 + v`, and a
+//!   final single byte `[u]` becomes the 2-digit number `7 * u`. Giving every
+//!   leading byte its own run of 7 two-digit prefixes (`7 * 44 = 308 >= 256`)
+//!   makes the encoding of a shorter input sort before the encodings of all its
+//!   extensions, exactly like byte strings do.
+//!
+//! For any byte strings `a` and `b`, `a.cmp(b) == encode_sortable(a).cmp(&encode_sortable(b))`,
+//! so encoded values can be used directly as sortable database keys. Output length
+//! is the same as [`encode`](crate::encode).
+
+use crate::Base44Error;
+
+/// The Base44 alphabet in ASCII order, used by the order-preserving mode.
+pub const SORTABLE_ALPHABET: &[u8; 44] = b"$%*+-./0123456789:ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Two-digit prefixes reserved per leading byte.
+const PREFIXES_PER_BYTE: u32 = 7;
+/// Three-digit values reserved per leading byte.
+const VALUES_PER_BYTE: u32 = PREFIXES_PER_BYTE * 44;
+
+#[inline]
+const fn sortable_val(ch: u8) -> Option<u32> {
+    match ch {
+        b'$' => Some(0),
+        b'%' => Some(1),
+        b'*' => Some(2),
+        b'+' => Some(3),
+        b'-' => Some(4),
+        b'.' => Some(5),
+        b'/' => Some(6),
+        b'0'..=b'9' => Some(7 + (ch - b'0') as u32),
+        b':' => Some(17),
+        b'A'..=b'Z' => Some(18 + (ch - b'A') as u32),
+        _ => None,
+    }
+}
+
+/// Encode bytes so that encoded strings sort in the same order as the inputs.
+///
+/// ```
+/// use qr_base44::encode_sortable;
+///
+/// let mut keys = [&b"b"[..], b"ab", b"a", b"", b"a\xFF"];
+/// let mut encoded: Vec<String> = keys.iter().map(|k| encode_sortable(k)).collect();
+/// keys.sort();
+/// encoded.sort();
+/// let expected: Vec<String> = keys.iter().map(|k| encode_sortable(k)).collect();
+/// assert_eq!(encoded, expected);
+/// ```
+pub fn encode_sortable(input: &[u8]) -> String {
+    let mut out = String::with_capacity(crate::encoded_len(input.len()));
+    let mut pairs = input.chunks_exact(2);
+    for pair in &mut pairs {
+        let y = pair[0] as u32 * VALUES_PER_BYTE + pair[1] as u32;
+        out.push(SORTABLE_ALPHABET[(y / (44 * 44)) as usize] as char);
+        out.push(SORTABLE_ALPHABET[(y / 44 % 44) as usize] as char);
+        out.push(SORTABLE_ALPHABET[(y % 44) as usize] as char);
+    }
+    if let [u] = pairs.remainder() {
+        let y = *u as u32 * PREFIXES_PER_BYTE;
+        out.push(SORTABLE_ALPHABET[(y / 44) as usize] as char);
+        out.push(SORTABLE_ALPHABET[(y % 44) as usize] as char);
+    }
+    out
+}
+
+/// Decode the output of [`encode_sortable`].
+///
+/// Returns [`Base44Error::Overflow`] for digit combinations the encoder never
+/// produces (unused values inside a leading byte's range).
+pub fn decode_sortable(s: &str) -> Result<Vec<u8>, Base44Error> {
+    let len = s.len();
+    let mut out = vec![0u8; len / 3 * 2 + usize::from(len % 3 == 2)];
+    decode_sortable_into(s.as_bytes(), &mut out)?;
+    Ok(out)
+}
+
+/// [`decode_sortable`] into `out`, which must hold exactly the decoded length
+/// (`s.len() / 3 * 2`, plus one for a 2-character tail).
+pub(crate) fn decode_sortable_into(s: &[u8], out: &mut [u8]) -> Result<(), Base44Error> {
+    let mut groups = s.chunks_exact(3);
+    for (group, dst) in (&mut groups).zip(out.chunks_exact_mut(2)) {
+        let mut y = 0;
+        for &ch in group {
+            y = y * 44 + sortable_val(ch).ok_or(Base44Error::InvalidChar)?;
+        }
+        let (u, v) = (y / VALUES_PER_BYTE, y % VALUES_PER_BYTE);
+        if u > 255 || v > 255 {
+            return Err(Base44Error::Overflow);
+        }
+        dst[0] = u as u8;
+        dst[1] = v as u8;
+    }
+    match groups.remainder() {
+        [] => {}
+        [ch] => {
+            sortable_val(*ch).ok_or(Base44Error::InvalidChar)?;
+            return Err(Base44Error::Dangling);
+        }
+        [hi, lo] => {
+            let hi = sortable_val(*hi).ok_or(Base44Error::InvalidChar)?;
+            let lo = sortable_val(*lo).ok_or(Base44Error::InvalidChar)?;
+            let y = hi * 44 + lo;
+            if y % PREFIXES_PER_BYTE != 0 || y / PREFIXES_PER_BYTE > 255 {
+                return Err(Base44Error::Overflow);
+            }
+            out[out.len() - 1] = (y / PREFIXES_PER_BYTE) as u8;
+        }
+        _ => unreachable!("chunks_exact(3) remainder has at most 2 elements"),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alphabet_is_sorted_permutation() {
+        assert!(SORTABLE_ALPHABET.windows(2).all(|w| w[0] < w[1]));
+        let mut a = SORTABLE_ALPHABET.to_vec();
+        let mut b = crate::BASE44_ALPHABET.to_vec();
+        a.sort();
+        b.sort();
+        assert_eq!(a, b);
+        for (i, &ch) in SORTABLE_ALPHABET.iter().enumerate() {
+            assert_eq!(sortable_val(ch), Some(i as u32));
+        }
+    }
+
+    #[test]
+    fn order_matches_byte_order_exhaustively_for_short_inputs() {
+        // Inputs of length 0..=3 built from edge-case byte values.
+        let vals = [0x00, 0x01, 0x2B, 0x2C, 0x7F, 0x80, 0xFE, 0xFF];
+        let mut inputs: Vec<Vec<u8>> = vec![vec![]];
+        for &a in &vals {
+            inputs.push(vec![a]);
+            for &b in &vals {
+                inputs.push(vec![a, b]);
+                inputs.push(vec![a, b, 0x00]);
+                inputs.push(vec![a, b, 0xFF]);
+            }
+        }
+        for a in &inputs {
+            let ea = encode_sortable(a);
+            assert_eq!(ea.len(), crate::encoded_len(a.len()));
+            assert_eq!(&decode_sortable(&ea).unwrap(), a);
+            for b in &inputs {
+                assert_eq!(a.cmp(b), ea.cmp(&encode_sortable(b)), "{a:?} vs {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn all_single_and_pair_values_roundtrip() {
+        for u in 0..=255u8 {
+            assert_eq!(decode_sortable(&encode_sortable(&[u])).unwrap(), [u]);
+            for v in [0u8, 1, 127, 255] {
+                assert_eq!(decode_sortable(&encode_sortable(&[u, v])).unwrap(), [u, v]);
+            }
+        }
+    }
+
+    #[test]
+    fn sortable_errors() {
+        assert!(matches!(decode_sortable("0"), Err(Base44Error::Dangling)));
+        assert!(matches!(
+            decode_sortable("a0"),
+            Err(Base44Error::InvalidChar)
+        ));
+        assert!(matches!(decode_sortable("ZZZ"), Err(Base44Error::Overflow)));
+        // 2-digit value that is not a multiple of 7
+        assert!(matches!(decode_sortable("$%"), Err(Base44Error::Overflow)));
+    }
+}
@@ -0,0 +1,90 @@
+//! SQL column mapping for [`Base44String`] and [`Base44Bytes`] ([`sqlx`] feature).
+//!
+//! The impls are generic over the database and delegate to `String`, so a
+//! `Base44String` binds and fetches wherever a TEXT column does. Values read back
+//! are validated, and a row holding invalid Base44 fails to decode instead of
+//! producing a `Base44String` that would panic later.
+
+use sqlx::database::Database;
+use sqlx::decode::Decode;
+use sqlx::encode::{Encode, IsNull};
+use sqlx::error::BoxDynError;
+use sqlx::types::Type;
+
+use crate::{Base44Bytes, Base44String, decode, encode};
+
+impl<DB: Database> Type<DB> for Base44String
+where
+    String: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <String as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <String as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, DB: Database> Encode<'q, DB> for Base44String
+where
+    String: Encode<'q, DB>,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        self.0.encode_by_ref(buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.0.size_hint()
+    }
+}
+
+impl<'r, DB: Database> Decode<'r, DB> for Base44String
+where
+    String: Decode<'r, DB>,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(Base44String::new(<String as Decode<'r, DB>>::decode(
+            value,
+        )?)?)
+    }
+}
+
+impl<DB: Database> Type<DB> for Base44Bytes
+where
+    String: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <String as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <String as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, DB: Database> Encode<'q, DB> for Base44Bytes
+where
+    String: Encode<'q, DB>,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        encode(&self.0).encode(buf)
+    }
+}
+
+impl<'r, DB: Database> Decode<'r, DB> for Base44Bytes
+where
+    String: Decode<'r, DB>,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(Base44Bytes(decode(&<String as Decode<'r, DB>>::decode(
+            value,
+        )?)?))
+    }
+}
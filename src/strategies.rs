@@ -0,0 +1,161 @@
+//! [`proptest`](mod@proptest) strategies for Base44 input.
+//!
+//! Downstream crates that embed the codec can reuse these generators in their own
+//! property tests:
+//!
+//! ```
+//! use proptest::prelude::*;
+//! use qr_base44::strategies;
+//!
+//! proptest!(|(m in strategies::malformed())| {
+//!     let err = qr_base44::decode(m.input()).unwrap_err();
+//!     prop_assert!(m.matches(&err));
+//! });
+//! ```
+
+use proptest::prelude::*;
+
+use crate::{BASE44_ALPHABET, Base44Error, encode};
+
+/// Any alphabet character.
+pub fn alphabet_char() -> impl Strategy<Value = char> {
+    proptest::sample::select(&BASE44_ALPHABET[..]).prop_map(|b| b as char)
+}
+
+/// A character outside the alphabet (lower-case letters, space, `=`, non-ASCII, ...).
+pub fn non_alphabet_char() -> impl Strategy<Value = char> {
+    any::<char>().prop_filter("in alphabet", |c| {
+        !c.is_ascii() || !BASE44_ALPHABET.contains(&(*c as u8))
+    })
+}
+
+/// Arbitrary payloads of up to 256 bytes together with their encoding.
+pub fn encoded_with_bytes() -> impl Strategy<Value = (Vec<u8>, String)> {
+    proptest::collection::vec(any::<u8>(), 0..=256).prop_map(|bytes| {
+        let s = encode(&bytes);
+        (bytes, s)
+    })
+}
+
+/// Valid byte-pair encoded strings.
+pub fn encoded() -> impl Strategy<Value = String> {
+    encoded_with_bytes().prop_map(|(_, s)| s)
+}
+
+/// Encoding of an even number of bytes: whole 3-character groups only.
+fn whole_groups() -> impl Strategy<Value = String> {
+    proptest::collection::vec(any::<[u8; 2]>(), 0..=64)
+        .prop_map(|pairs| encode(pairs.as_flattened()))
+}
+
+/// Encoded digits of `value`, least-significant first.
+fn digits(mut value: u32, len: usize) -> String {
+    (0..len)
+        .map(|_| {
+            let ch = BASE44_ALPHABET[(value % 44) as usize] as char;
+            value /= 44;
+            ch
+        })
+        .collect()
+}
+
+/// A string that [`decode`](crate::decode) must reject, with the expected error.
+#[derive(Debug, Clone)]
+pub enum Malformed {
+    /// Contains a character outside the alphabet.
+    InvalidChar(String),
+    /// Ends with a single character after whole groups.
+    Dangling(String),
+    /// Contains a group whose value exceeds its byte range.
+    Overflow(String),
+}
+
+impl Malformed {
+    /// The malformed input.
+    pub fn input(&self) -> &str {
+        match self {
+            Malformed::InvalidChar(s) | Malformed::Dangling(s) | Malformed::Overflow(s) => s,
+        }
+    }
+
+    /// Whether `err` is the error this input should produce.
+    pub fn matches(&self, err: &Base44Error) -> bool {
+        matches!(
+            (self, err),
+            (Malformed::InvalidChar(_), Base44Error::InvalidChar)
+                | (Malformed::Dangling(_), Base44Error::Dangling)
+                | (Malformed::Overflow(_), Base44Error::Overflow)
+        )
+    }
+}
+
+/// Valid strings with one character replaced by a non-alphabet character.
+pub fn invalid_char() -> impl Strategy<Value = Malformed> {
+    (encoded(), any::<prop::sample::Index>(), non_alphabet_char()).prop_map(|(s, at, bad)| {
+        let mut chars: Vec<char> = s.chars().collect();
+        if chars.is_empty() {
+            chars.push(bad);
+        } else {
+            let i = at.index(chars.len());
+            chars[i] = bad;
+        }
+        Malformed::InvalidChar(chars.into_iter().collect())
+    })
+}
+
+/// Whole groups followed by a single alphabet character.
+pub fn dangling() -> impl Strategy<Value = Malformed> {
+    (whole_groups(), alphabet_char()).prop_map(|(mut s, ch)| {
+        s.push(ch);
+        Malformed::Dangling(s)
+    })
+}
+
+/// Whole groups followed by an out-of-range group and then arbitrary valid input.
+pub fn overflow() -> impl Strategy<Value = Malformed> {
+    let bad_group = prop_oneof![
+        (65_536u32..44 * 44 * 44).prop_map(|v| digits(v, 3)),
+        (256u32..44 * 44).prop_map(|v| digits(v, 2)),
+    ];
+    (whole_groups(), bad_group, encoded()).prop_map(|(mut s, bad, rest)| {
+        s.push_str(&bad);
+        // A 2-character group must be the last one.
+        if bad.len() == 3 {
+            s.push_str(&rest);
+        }
+        Malformed::Overflow(s)
+    })
+}
+
+/// Any [`Malformed`] input.
+pub fn malformed() -> impl Strategy<Value = Malformed> {
+    prop_oneof![invalid_char(), dangling(), overflow()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode;
+
+    proptest! {
+        #[test]
+        fn roundtrip((bytes, s) in encoded_with_bytes()) {
+            prop_assert_eq!(s.len(), crate::encoded_len(bytes.len()));
+            prop_assert_eq!(decode(&s).unwrap(), bytes);
+        }
+
+        #[test]
+        fn decodable_strings_are_canonical(s in proptest::collection::vec(alphabet_char(), 0..64)) {
+            let s: String = s.into_iter().collect();
+            if let Ok(bytes) = decode(&s) {
+                prop_assert_eq!(encode(&bytes), s);
+            }
+        }
+
+        #[test]
+        fn errors_are_classified(m in malformed()) {
+            let err = decode(m.input()).unwrap_err();
+            prop_assert!(m.matches(&err), "{:?} gave {:?}", m, err);
+        }
+    }
+}
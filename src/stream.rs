@@ -0,0 +1,607 @@
+//! Streaming byte-pair codec over [`std::io::Write`].
+//!
+//! [`EncoderWriter`] encodes bytes written to it and forwards the text to an inner
+//! writer; [`DecoderWriter`] does the reverse. Both hold at most one incomplete
+//! group between calls, so arbitrarily large payloads stream in constant memory
+//! and produce exactly the output of [`encode`](crate::encode) and
+//! [`decode`](crate::decode).
+//!
+//! ```
+//! use std::io::Write;
+//! use qr_base44::{DecoderWriter, EncoderWriter};
+//!
+//! let mut enc = EncoderWriter::new(Vec::new());
+//! enc.write_all(b"Hello, ").unwrap();
+//! enc.write_all(b"World!").unwrap();
+//! let text = enc.finish().unwrap();
+//! assert_eq!(text, qr_base44::encode(b"Hello, World!").as_bytes());
+//!
+//! let mut dec = DecoderWriter::new(Vec::new());
+//! dec.write_all(&text).unwrap();
+//! assert_eq!(dec.finish().unwrap(), b"Hello, World!");
+//! ```
+
+use std::io::{self, Write};
+
+use crate::sink::{IoSink, SliceSink, encode_to_sink};
+use crate::{Base44Error, Cancelled, Observer, b44_val};
+
+/// Input bytes encoded per `write` call; the output fits a fixed stack buffer.
+const ENCODE_CHUNK: usize = 512;
+/// Input characters decoded per `write` call, so observers are polled regularly.
+const DECODE_CHUNK: usize = 768;
+
+pub(crate) fn invalid_data(err: Base44Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+fn check_cancel(observer: &impl Observer) -> io::Result<()> {
+    match observer.should_cancel() {
+        true => Err(io::Error::other(Cancelled)),
+        false => Ok(()),
+    }
+}
+
+/// Byte-pair encode `input` straight into `writer`, returning the number of
+/// characters written.
+///
+/// Output goes out in fixed-size chunks from a stack buffer, so unlike
+/// `writer.write_all(encode(input).as_bytes())` no `String` of the whole encoding
+/// is built. The writer is flushed at the end.
+///
+/// ```
+/// let mut out = Vec::new();
+/// let n = qr_base44::encode_to_writer(b"hi", &mut out).unwrap();
+/// assert_eq!((n, &out[..]), (3, &b"LZD"[..]));
+/// ```
+pub fn encode_to_writer<W: Write + ?Sized>(input: &[u8], writer: &mut W) -> io::Result<usize> {
+    encode_to_sink(input, &mut IoSink(&mut *writer))?;
+    writer.flush()?;
+    Ok(crate::encoded_len(input.len()))
+}
+
+/// Encodes everything written to it and writes the Base44 text to `W`.
+///
+/// Call [`finish`](Self::finish) to emit the final odd byte; dropping the writer
+/// without finishing loses it.
+#[derive(Debug)]
+pub struct EncoderWriter<W, O = ()> {
+    inner: W,
+    /// First byte of a pair split across `write` calls.
+    pending: Option<u8>,
+    /// Input bytes accepted so far.
+    consumed: u64,
+    observer: O,
+}
+
+impl<W: Write> EncoderWriter<W> {
+    /// Encode into `inner`.
+    pub fn new(inner: W) -> Self {
+        Self::with_observer(inner, ())
+    }
+}
+
+impl<W: Write, O: Observer> EncoderWriter<W, O> {
+    /// Encode into `inner`, reporting throughput to `observer`.
+    pub fn with_observer(inner: W, observer: O) -> Self {
+        Self {
+            inner,
+            pending: None,
+            consumed: 0,
+            observer,
+        }
+    }
+
+    /// The inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Write the trailing group (if any), flush, and return the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        check_cancel(&self.observer)?;
+        if let Some(u) = self.pending.take() {
+            encode_to_sink(&[u], &mut IoSink(&mut self.inner))?;
+            self.observer.bytes_out(2);
+        }
+        self.inner.flush()?;
+        self.observer.finished();
+        trace_event!(
+            debug,
+            bytes = self.consumed,
+            "base44 stream encode finished"
+        );
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write, O: Observer> Write for EncoderWriter<W, O> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        check_cancel(&self.observer)?;
+        let mut out = [0u8; ENCODE_CHUNK / 2 * 3 + 3];
+        let mut sink = SliceSink::new(&mut out);
+        let mut input = &buf[..buf.len().min(ENCODE_CHUNK)];
+        let consumed = input.len();
+        let mut pending = self.pending;
+        if let Some(hi) = pending.take() {
+            let Ok(()) = encode_to_sink(&[hi, input[0]], &mut sink);
+            input = &input[1..];
+        }
+        let (pairs, rest) = input.split_at(input.len() & !1);
+        let Ok(()) = encode_to_sink(pairs, &mut sink);
+        if let [u] = rest {
+            pending = Some(*u);
+        }
+        let len = sink.written();
+        self.inner.write_all(&out[..len])?;
+        self.pending = pending;
+        self.consumed += consumed as u64;
+        self.observer.bytes_in(consumed);
+        self.observer.bytes_out(len);
+        Ok(consumed)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Incremental byte-pair decoder that hands its output to a caller-provided sink.
+///
+/// All state lives inline: two pending digits, a position counter and a
+/// [`BUFFER_LEN`](Self::BUFFER_LEN)-byte output buffer, so memory use is
+/// `size_of::<StreamDecoder>()` (a little over 512 bytes) no matter how much input
+/// is fed, and the decoder never allocates. The sink is called whenever the
+/// buffer fills and at the end of every [`feed`](Self::feed).
+///
+/// The sink's error type `E` must absorb [`Base44Error`], so a single `Result`
+/// carries both decode and sink failures. Decode errors are sticky: after one,
+/// every further call returns [`consumed`](Self::consumed) unchanged and fails
+/// again. Output from groups before the error may already have reached the sink.
+///
+/// A sink error loses nothing: the bytes the sink refused stay buffered and are
+/// offered again by the next call, and [`consumed`](Self::consumed) counts only
+/// the characters absorbed so far, so feeding resumes from that offset.
+///
+/// ```
+/// use qr_base44::{Base44Error, StreamDecoder};
+///
+/// let mut out = Vec::new();
+/// let mut sink = |bytes: &[u8]| {
+///     out.extend_from_slice(bytes);
+///     Ok::<_, Base44Error>(())
+/// };
+/// let mut dec = StreamDecoder::new();
+/// dec.feed(b"J%", &mut sink).unwrap();
+/// dec.feed(b"XL1", &mut sink).unwrap();
+/// dec.finish(&mut sink).unwrap();
+/// assert_eq!(out, [0xFF, 0xFF, 0x41]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct StreamDecoder {
+    /// Digit values of an incomplete group carried across `feed` calls.
+    pending: [u16; 2],
+    pending_len: usize,
+    /// Input characters accepted so far; the error offset after a failure.
+    consumed: u64,
+    error: Option<Base44Error>,
+    buf: [u8; Self::BUFFER_LEN],
+    buf_len: usize,
+}
+
+impl Default for StreamDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StreamDecoder {
+    /// Size of the internal output buffer, and the most the sink is handed at once.
+    pub const BUFFER_LEN: usize = 512;
+
+    /// A decoder that has seen no input.
+    pub const fn new() -> Self {
+        Self {
+            pending: [0; 2],
+            pending_len: 0,
+            consumed: 0,
+            error: None,
+            buf: [0; Self::BUFFER_LEN],
+            buf_len: 0,
+        }
+    }
+
+    /// Characters accepted so far. After an error, the offset of the character
+    /// that caused it.
+    pub fn consumed(&self) -> u64 {
+        self.consumed
+    }
+
+    /// Decode `input`, passing complete output to `sink`.
+    pub fn feed<E, F>(&mut self, input: &[u8], mut sink: F) -> Result<(), E>
+    where
+        E: From<Base44Error>,
+        F: FnMut(&[u8]) -> Result<(), E>,
+    {
+        if let Some(err) = self.error {
+            return Err(err.into());
+        }
+        for &ch in input {
+            let Some(val) = b44_val(ch) else {
+                return Err(self.fail(Base44Error::InvalidChar));
+            };
+            if self.pending_len < 2 {
+                self.pending[self.pending_len] = val;
+                self.pending_len += 1;
+                self.consumed += 1;
+                continue;
+            }
+            let x = self.pending[0] as u32 + self.pending[1] as u32 * 44 + val as u32 * 44 * 44;
+            if x > 0xFFFF {
+                return Err(self.fail(Base44Error::Overflow));
+            }
+            // Make room before committing the group, so a failing sink leaves
+            // this character unconsumed.
+            if self.buf_len + 2 > Self::BUFFER_LEN {
+                self.flush(&mut sink)?;
+            }
+            self.pending_len = 0;
+            self.consumed += 1;
+            self.buf[self.buf_len] = (x / 256) as u8;
+            self.buf[self.buf_len + 1] = (x % 256) as u8;
+            self.buf_len += 2;
+        }
+        self.flush(&mut sink)
+    }
+
+    /// Decode the trailing group (if any) and flush the buffer.
+    pub fn finish<E, F>(mut self, mut sink: F) -> Result<(), E>
+    where
+        E: From<Base44Error>,
+        F: FnMut(&[u8]) -> Result<(), E>,
+    {
+        if let Some(err) = self.error {
+            return Err(err.into());
+        }
+        match self.pending_len {
+            0 => {}
+            1 => return Err(self.fail(Base44Error::Dangling)),
+            _ => {
+                let x = self.pending[0] + self.pending[1] * 44;
+                if x > 0xFF {
+                    return Err(self.fail(Base44Error::Overflow));
+                }
+                if self.buf_len == Self::BUFFER_LEN {
+                    self.flush(&mut sink)?;
+                }
+                self.buf[self.buf_len] = x as u8;
+                self.buf_len += 1;
+            }
+        }
+        self.flush(&mut sink)
+    }
+
+    fn fail<E: From<Base44Error>>(&mut self, err: Base44Error) -> E {
+        self.error = Some(err);
+        self.buf_len = 0;
+        err.into()
+    }
+
+    fn flush<E, F: FnMut(&[u8]) -> Result<(), E>>(&mut self, sink: &mut F) -> Result<(), E> {
+        if self.buf_len == 0 {
+            return Ok(());
+        }
+        sink(&self.buf[..self.buf_len])?;
+        self.buf_len = 0;
+        Ok(())
+    }
+}
+
+/// A failure inside [`DecoderWriter`]: bad input or the inner writer.
+enum Failure {
+    Decode(Base44Error),
+    Io(io::Error),
+}
+
+impl From<Base44Error> for Failure {
+    fn from(err: Base44Error) -> Self {
+        Failure::Decode(err)
+    }
+}
+
+/// Decodes Base44 text written to it and writes the bytes to `W`.
+///
+/// Invalid input fails the `write` call with [`io::ErrorKind::InvalidData`]
+/// wrapping the [`Base44Error`]. Call [`finish`](Self::finish) to validate and
+/// emit the final group. Built on [`StreamDecoder`], so memory use is bounded in
+/// the same way.
+///
+/// Once the inner writer fails, part of a `write` may already have reached it,
+/// so the writer is poisoned: every later `write` and `finish` fails too,
+/// rather than a retry duplicating output.
+#[derive(Debug)]
+pub struct DecoderWriter<W, O = ()> {
+    inner: W,
+    decoder: StreamDecoder,
+    observer: O,
+    /// Kind of an earlier inner writer failure.
+    failed: Option<io::ErrorKind>,
+}
+
+impl<W: Write> DecoderWriter<W> {
+    /// Decode into `inner`.
+    pub fn new(inner: W) -> Self {
+        Self::with_observer(inner, ())
+    }
+}
+
+impl<W: Write, O: Observer> DecoderWriter<W, O> {
+    /// Decode into `inner`, reporting throughput and rejections to `observer`.
+    pub fn with_observer(inner: W, observer: O) -> Self {
+        Self {
+            inner,
+            decoder: StreamDecoder::new(),
+            observer,
+            failed: None,
+        }
+    }
+
+    /// The inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Decode the trailing group (if any), flush, and return the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.check_poisoned()?;
+        check_cancel(&self.observer)?;
+        let (inner, observer) = (&mut self.inner, &self.observer);
+        let result = self.decoder.clone().finish(|bytes| {
+            inner.write_all(bytes).map_err(Failure::Io)?;
+            observer.bytes_out(bytes.len());
+            Ok(())
+        });
+        self.check(result)?;
+        self.inner.flush()?;
+        self.observer.finished();
+        trace_event!(
+            debug,
+            chars = self.decoder.consumed(),
+            "base44 stream decode finished"
+        );
+        Ok(self.inner)
+    }
+
+    /// Fail if the inner writer failed before.
+    fn check_poisoned(&self) -> io::Result<()> {
+        match self.failed {
+            // Never `Interrupted`, which `write_all` would retry forever.
+            Some(kind) => Err(io::Error::new(
+                match kind {
+                    io::ErrorKind::Interrupted => io::ErrorKind::Other,
+                    kind => kind,
+                },
+                "inner writer failed earlier; decoded output is incomplete",
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Turn a decoder result into an `io::Result`, reporting invalid input and
+    /// poisoning the writer on an inner writer failure.
+    fn check(&mut self, result: Result<(), Failure>) -> io::Result<()> {
+        match result {
+            Ok(()) => Ok(()),
+            Err(Failure::Io(err)) => {
+                self.failed = Some(err.kind());
+                Err(err)
+            }
+            Err(Failure::Decode(err)) => {
+                trace_event!(warn, error = %err, offset = self.decoder.consumed(), "base44 stream decode rejected input");
+                self.observer.group_rejected(err);
+                Err(invalid_data(err))
+            }
+        }
+    }
+}
+
+impl<W: Write, O: Observer> Write for DecoderWriter<W, O> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.check_poisoned()?;
+        check_cancel(&self.observer)?;
+        let input = &buf[..buf.len().min(DECODE_CHUNK)];
+        let (inner, observer) = (&mut self.inner, &self.observer);
+        let result = self.decoder.feed(input, |bytes| {
+            inner.write_all(bytes).map_err(Failure::Io)?;
+            observer.bytes_out(bytes.len());
+            Ok(())
+        });
+        self.check(result)?;
+        self.observer.bytes_in(input.len());
+        Ok(input.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode, encode};
+
+    fn encode_in_chunks(data: &[u8], chunk: usize) -> Vec<u8> {
+        let mut enc = EncoderWriter::new(Vec::new());
+        for part in data.chunks(chunk) {
+            enc.write_all(part).unwrap();
+        }
+        enc.finish().unwrap()
+    }
+
+    fn decode_in_chunks(text: &[u8], chunk: usize) -> io::Result<Vec<u8>> {
+        let mut dec = DecoderWriter::new(Vec::new());
+        for part in text.chunks(chunk) {
+            dec.write_all(part)?;
+        }
+        dec.finish()
+    }
+
+    #[test]
+    fn matches_one_shot_for_every_chunk_size() {
+        let data: Vec<u8> = (0..2000u32).map(|i| (i * 7 + i / 3) as u8).collect();
+        for len in [0, 1, 2, 3, 1025, 2000] {
+            let data = &data[..len];
+            let expected = encode(data);
+            for chunk in [1, 2, 3, 5, 512, 513, 4096] {
+                let text = encode_in_chunks(data, chunk);
+                assert_eq!(text, expected.as_bytes(), "len {len} chunk {chunk}");
+                assert_eq!(decode_in_chunks(&text, chunk).unwrap(), data);
+            }
+        }
+    }
+
+    #[test]
+    fn encode_to_writer_matches_encode() {
+        let data: Vec<u8> = (0..3001u32).map(|i| (i * 13) as u8).collect();
+        let mut out = Vec::new();
+        let n = encode_to_writer(&data, &mut out).unwrap();
+        assert_eq!(out, encode(&data).as_bytes());
+        assert_eq!(n, out.len());
+    }
+
+    #[test]
+    fn decoder_errors_match_decode() {
+        for s in ["0", "ZZZ", "000ZZ", "00a", "LZ\u{1F600}"] {
+            let expected = decode(s).unwrap_err();
+            for chunk in [1, 2, 4] {
+                let err = decode_in_chunks(s.as_bytes(), chunk).unwrap_err();
+                assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+                let inner = err.into_inner().unwrap().downcast::<Base44Error>().unwrap();
+                assert_eq!(*inner, expected, "{s:?} chunk {chunk}");
+            }
+        }
+    }
+
+    #[test]
+    fn decoder_reports_to_observer() {
+        use std::cell::Cell;
+
+        #[derive(Default)]
+        struct Totals {
+            bytes_out: Cell<usize>,
+            rejected: Cell<Option<Base44Error>>,
+        }
+        impl Observer for Totals {
+            fn bytes_out(&self, n: usize) {
+                self.bytes_out.set(self.bytes_out.get() + n);
+            }
+            fn group_rejected(&self, err: Base44Error) {
+                self.rejected.set(Some(err));
+            }
+        }
+
+        let totals = Totals::default();
+        let mut dec = DecoderWriter::with_observer(Vec::new(), &totals);
+        dec.write_all(b"J%XL1").unwrap();
+        dec.finish().unwrap();
+        assert_eq!(totals.bytes_out.get(), 3);
+
+        let mut dec = DecoderWriter::with_observer(Vec::new(), &totals);
+        assert!(dec.write_all(b"ZZZ").is_err());
+        assert_eq!(totals.rejected.get(), Some(Base44Error::Overflow));
+    }
+
+    #[test]
+    fn stream_decoder_is_bounded_and_sticky() {
+        assert!(core::mem::size_of::<StreamDecoder>() < 600);
+
+        let data: Vec<u8> = (0..5000u32).map(|i| (i * 31) as u8).collect();
+        let text = encode(&data);
+        let mut out = Vec::new();
+        let mut largest = 0;
+        let mut dec = StreamDecoder::new();
+        let mut sink = |bytes: &[u8]| {
+            largest = largest.max(bytes.len());
+            out.extend_from_slice(bytes);
+            Ok::<_, Base44Error>(())
+        };
+        dec.feed(text.as_bytes(), &mut sink).unwrap();
+        dec.finish(&mut sink).unwrap();
+        assert_eq!(out, data);
+        assert_eq!(largest, StreamDecoder::BUFFER_LEN);
+
+        let mut dec = StreamDecoder::new();
+        let sink = |_: &[u8]| Ok::<_, Base44Error>(());
+        assert_eq!(dec.feed(b"000ZZZ000", sink), Err(Base44Error::Overflow));
+        assert_eq!(dec.consumed(), 5);
+        assert_eq!(dec.feed(b"000", sink), Err(Base44Error::Overflow));
+    }
+
+    #[test]
+    fn sink_failures_lose_nothing() {
+        let data: Vec<u8> = (0..3000u32).map(|i| (i * 7) as u8).collect();
+        let text = encode(&data);
+        let mut out = Vec::new();
+        let mut refusals = 3;
+        let mut sink = |bytes: &[u8]| match refusals {
+            0 => {
+                out.extend_from_slice(bytes);
+                Ok(())
+            }
+            _ => {
+                refusals -= 1;
+                Err(Base44Error::TooLarge)
+            }
+        };
+        let mut dec = StreamDecoder::new();
+        while let Err(err) = dec.feed(&text.as_bytes()[dec.consumed() as usize..], &mut sink) {
+            assert_eq!(err, Base44Error::TooLarge);
+        }
+        dec.finish(&mut sink).unwrap();
+        assert_eq!(out, data);
+    }
+
+    #[test]
+    fn decoder_writer_is_poisoned_by_inner_failures() {
+        /// Fails its first write after `ok` bytes, then accepts everything.
+        struct FailOnce {
+            out: Vec<u8>,
+            ok: usize,
+            failed: bool,
+        }
+        impl Write for FailOnce {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                if !self.failed && self.out.len() + buf.len() > self.ok {
+                    self.failed = true;
+                    return Err(io::Error::from(io::ErrorKind::BrokenPipe));
+                }
+                self.out.extend_from_slice(buf);
+                Ok(buf.len())
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let data: Vec<u8> = (0..2000u32).map(|i| (i * 13) as u8).collect();
+        let text = encode(&data);
+        let inner = FailOnce {
+            out: Vec::new(),
+            ok: 100,
+            failed: false,
+        };
+        let mut dec = DecoderWriter::new(inner);
+        let err = dec.write_all(text.as_bytes()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+        // Retrying the same text must fail rather than duplicate output.
+        let written = dec.get_ref().out.len();
+        let err = dec.write_all(text.as_bytes()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+        assert_eq!(dec.get_ref().out.len(), written);
+        assert!(dec.finish().is_err());
+    }
+}
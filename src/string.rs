@@ -0,0 +1,224 @@
+//! [`Base44String`]: an owned string known to be valid byte-pair Base44.
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::{Base44Error, decode, encode};
+
+/// An owned string that is guaranteed to decode with [`decode`].
+///
+/// Validation happens once, at construction, so the string can be passed around and
+/// decoded later without error handling.
+///
+/// ```
+/// use qr_base44::Base44String;
+///
+/// let s = Base44String::from_bytes(b"hi");
+/// assert_eq!(s.as_str(), "LZD");
+/// let parsed: Base44String = "LZD".parse().unwrap();
+/// assert_eq!(parsed.decode(), b"hi");
+/// assert!("LZ".parse::<Base44String>().is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Text)
+)]
+pub struct Base44String(pub(crate) String);
+
+impl Base44String {
+    /// Regular expression (ECMAScript and Rust `regex` syntax) for the shape of
+    /// byte-pair Base44: alphabet characters only, and a length that is not one more
+    /// than a multiple of three.
+    ///
+    /// Groups that overflow (such as `ZZZ`) still match, so this is a first filter
+    /// for schemas and form validation, not a replacement for [`Base44String::new`].
+    pub const PATTERN: &'static str = "^(?:[0-9A-Z$%*+./:-]{3})*(?:[0-9A-Z$%*+./:-]{2})?$";
+
+    /// Validate `s` and wrap it.
+    pub fn new(s: impl Into<String>) -> Result<Self, Base44Error> {
+        let s = s.into();
+        decode(&s)?;
+        Ok(Self(s))
+    }
+
+    /// Encode `bytes` with [`encode`].
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        Self(encode(bytes))
+    }
+
+    /// The encoded text.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Decode back to bytes. Cannot fail because the contents were validated.
+    pub fn decode(&self) -> Vec<u8> {
+        decode(&self.0).expect("Base44String holds valid base44")
+    }
+
+    /// Unwrap into the inner `String`.
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl fmt::Display for Base44String {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for Base44String {
+    type Err = Base44Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<String> for Base44String {
+    type Error = Base44Error;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<&str> for Base44String {
+    type Error = Base44Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::new(s)
+    }
+}
+
+impl From<Base44String> for String {
+    fn from(s: Base44String) -> Self {
+        s.0
+    }
+}
+
+impl AsRef<str> for Base44String {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl core::ops::Deref for Base44String {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Raw bytes whose text form is byte-pair Base44.
+///
+/// Where [`Base44String`] carries the encoded text, this carries the decoded
+/// bytes: it displays and parses as Base44, and the database integrations store
+/// it in a TEXT column, so application code never handles the encoding.
+///
+/// ```
+/// use qr_base44::Base44Bytes;
+///
+/// let b = Base44Bytes::from(b"hi".to_vec());
+/// assert_eq!(b.to_string(), "LZD");
+/// assert_eq!("LZD".parse::<Base44Bytes>().unwrap(), b);
+/// assert_eq!(&*b, b"hi");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Text)
+)]
+pub struct Base44Bytes(pub Vec<u8>);
+
+impl Base44Bytes {
+    /// Encode into a validated [`Base44String`].
+    pub fn to_base44(&self) -> Base44String {
+        Base44String::from_bytes(&self.0)
+    }
+
+    /// Unwrap into the inner bytes.
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl fmt::Display for Base44Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&encode(&self.0))
+    }
+}
+
+impl FromStr for Base44Bytes {
+    type Err = Base44Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        decode(s).map(Self)
+    }
+}
+
+impl From<Vec<u8>> for Base44Bytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<Base44Bytes> for Vec<u8> {
+    fn from(b: Base44Bytes) -> Self {
+        b.0
+    }
+}
+
+impl From<&Base44String> for Base44Bytes {
+    fn from(s: &Base44String) -> Self {
+        Self(s.decode())
+    }
+}
+
+impl AsRef<[u8]> for Base44Bytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl core::ops::Deref for Base44Bytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_on_construction() {
+        let s = Base44String::try_from("J%XL1").unwrap();
+        assert_eq!(s.decode(), [0xFF, 0xFF, 0x41]);
+        assert_eq!(&*s, "J%XL1");
+        assert_eq!(String::from(s.clone()), "J%XL1");
+        assert!(matches!(Base44String::new("A"), Err(Base44Error::Dangling)));
+        assert!(matches!(
+            Base44String::try_from(String::from(":::")),
+            Err(Base44Error::Overflow)
+        ));
+        assert_eq!(Base44String::from_bytes(&[]).as_str(), "");
+    }
+
+    #[test]
+    fn bytes_wrapper_converts_through_text() {
+        let b = Base44Bytes::from(vec![0xFF, 0xFF, 0x41]);
+        assert_eq!(b.to_base44().as_str(), "J%XL1");
+        assert_eq!(Base44Bytes::from(&b.to_base44()), b);
+        assert_eq!("J%XL1".parse::<Base44Bytes>(), Ok(b.clone()));
+        assert_eq!("J%XL".parse::<Base44Bytes>(), Err(Base44Error::Dangling));
+        assert_eq!(b.into_vec(), [0xFF, 0xFF, 0x41]);
+    }
+}
@@ -0,0 +1,126 @@
+//! Payloads prefixed with a one-byte type tag.
+//!
+//! The tag is the first byte of the byte-pair encoding, so it lives in the first
+//! three characters and [`peek_tag`] can read it without decoding the rest. A
+//! scanner can dispatch on it ("wifi config", "firmware chunk", ...) and only
+//! decode payloads it handles. Tag values are application-defined; a
+//! [`TagRegistry`] names them.
+//!
+//! ```
+//! use qr_base44::tagged::{self, TagRegistry};
+//!
+//! let mut registry = TagRegistry::new();
+//! registry.register(0x01, "wifi");
+//! registry.register(0x02, "firmware");
+//!
+//! let code = tagged::encode(0x01, b"ssid=home");
+//! assert_eq!(registry.identify(&code).unwrap(), (0x01, Some("wifi")));
+//! assert_eq!(tagged::decode(&code).unwrap(), (0x01, b"ssid=home".to_vec()));
+//! ```
+
+use crate::{Base44Error, decode_bytes};
+
+/// Encode `payload` behind `tag`.
+pub fn encode(tag: u8, payload: &[u8]) -> String {
+    let mut bytes = Vec::with_capacity(payload.len() + 1);
+    bytes.push(tag);
+    bytes.extend_from_slice(payload);
+    crate::encode(&bytes)
+}
+
+/// Read the tag from the first group only.
+///
+/// The rest of `s` is not validated. An empty string is
+/// [`Base44Error::InvalidLength`]; an invalid first group is reported as by
+/// [`decode`](crate::decode).
+pub fn peek_tag(s: &str) -> Result<u8, Base44Error> {
+    let head = &s.as_bytes()[..s.len().min(3)];
+    decode_bytes(head)?
+        .first()
+        .copied()
+        .ok_or(Base44Error::InvalidLength)
+}
+
+/// Decode a tagged payload into `(tag, payload)`.
+pub fn decode(s: &str) -> Result<(u8, Vec<u8>), Base44Error> {
+    let mut bytes = crate::decode(s)?;
+    if bytes.is_empty() {
+        return Err(Base44Error::InvalidLength);
+    }
+    let tag = bytes.remove(0);
+    Ok((tag, bytes))
+}
+
+/// Names for the tags an application uses.
+#[derive(Debug, Clone)]
+pub struct TagRegistry {
+    names: [Option<&'static str>; 256],
+}
+
+impl Default for TagRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TagRegistry {
+    /// A registry with no tags.
+    pub const fn new() -> Self {
+        Self { names: [None; 256] }
+    }
+
+    /// Name `tag`, returning the name it replaced.
+    pub fn register(&mut self, tag: u8, name: &'static str) -> Option<&'static str> {
+        self.names[tag as usize].replace(name)
+    }
+
+    /// The name registered for `tag`.
+    pub fn name(&self, tag: u8) -> Option<&'static str> {
+        self.names[tag as usize]
+    }
+
+    /// The tag registered under `name`.
+    pub fn tag(&self, name: &str) -> Option<u8> {
+        (0..=255).find(|&tag| self.names[tag as usize] == Some(name))
+    }
+
+    /// [`peek_tag`] and the tag's name, if registered.
+    pub fn identify(&self, s: &str) -> Result<(u8, Option<&'static str>), Base44Error> {
+        let tag = peek_tag(s)?;
+        Ok((tag, self.name(tag)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn peeking_reads_only_the_first_group() {
+        for tag in [0x00, 0x01, 0x7F, 0xFF] {
+            for payload in [&b""[..], b"x", b"xy", b"firmware chunk"] {
+                let code = encode(tag, payload);
+                assert_eq!(peek_tag(&code), Ok(tag));
+                assert_eq!(decode(&code).unwrap(), (tag, payload.to_vec()));
+            }
+        }
+        // A corrupt body does not stop dispatch, but does stop decoding.
+        let corrupt = format!("{}ZZZ", encode(0x02, b"ab"));
+        assert_eq!(peek_tag(&corrupt), Ok(0x02));
+        assert_eq!(decode(&corrupt), Err(Base44Error::Overflow));
+        assert_eq!(peek_tag(""), Err(Base44Error::InvalidLength));
+        assert_eq!(peek_tag("L"), Err(Base44Error::Dangling));
+        assert_eq!(decode(""), Err(Base44Error::InvalidLength));
+    }
+
+    #[test]
+    fn registry_lookups() {
+        let mut registry = TagRegistry::default();
+        assert_eq!(registry.register(0x10, "credential"), None);
+        assert_eq!(registry.register(0x10, "token"), Some("credential"));
+        assert_eq!(registry.name(0x10), Some("token"));
+        assert_eq!(registry.tag("token"), Some(0x10));
+        assert_eq!(registry.tag("credential"), None);
+        assert_eq!(registry.identify(&encode(0x11, b"")).unwrap(), (0x11, None));
+    }
+}
@@ -0,0 +1,217 @@
+//! Canonical test vectors for implementations in other languages.
+//!
+//! Each table pairs inputs with the exact output of this crate. The values were
+//! cross-checked against an independent big-integer implementation, and the
+//! crate's own tests assert that the codec still produces them.
+//!
+//! ```
+//! use qr_base44::test_vectors::{BYTE_PAIR, OPTIMAL};
+//!
+//! for v in BYTE_PAIR {
+//!     assert_eq!(qr_base44::encode(v.bytes), v.encoded, "{}", v.name);
+//! }
+//! for v in OPTIMAL {
+//!     assert_eq!(qr_base44::decode_bits(v.bits, v.encoded).unwrap(), v.bytes, "{}", v.name);
+//! }
+//! ```
+
+use crate::Base44Error;
+
+/// A byte-pair ([`encode`](crate::encode)) vector.
+#[derive(Debug, Clone, Copy)]
+pub struct Vector {
+    /// Short description.
+    pub name: &'static str,
+    /// Input bytes.
+    pub bytes: &'static [u8],
+    /// Expected encoding.
+    pub encoded: &'static str,
+}
+
+/// An optimal ([`encode_bits`](crate::encode_bits)) vector.
+#[derive(Debug, Clone, Copy)]
+pub struct BitsVector {
+    /// Short description.
+    pub name: &'static str,
+    /// Bit length.
+    pub bits: usize,
+    /// Input bytes, least-significant byte first.
+    pub bytes: &'static [u8],
+    /// Expected encoding.
+    pub encoded: &'static str,
+}
+
+/// A string that [`decode`](crate::decode) must reject.
+#[derive(Debug, Clone, Copy)]
+pub struct ErrorVector {
+    /// Short description.
+    pub name: &'static str,
+    /// Input text.
+    pub encoded: &'static str,
+    /// Expected error.
+    pub error: Base44Error,
+}
+
+/// Byte-pair vectors.
+pub const BYTE_PAIR: &[Vector] = &[
+    Vector {
+        name: "empty",
+        bytes: b"",
+        encoded: "",
+    },
+    Vector {
+        name: "single zero byte",
+        bytes: &[0x00],
+        encoded: "00",
+    },
+    Vector {
+        name: "single 0xFF byte",
+        bytes: &[0xFF],
+        encoded: "Z5",
+    },
+    Vector {
+        name: "zero group",
+        bytes: &[0x00, 0x00],
+        encoded: "000",
+    },
+    Vector {
+        name: "0xFFFF group",
+        bytes: &[0xFF, 0xFF],
+        encoded: "J%X",
+    },
+    Vector {
+        name: "group and tail",
+        bytes: &[0x01, 0x02, 0xFF],
+        encoded: "*50Z5",
+    },
+    Vector {
+        name: "max group and tail",
+        bytes: &[0xFF, 0xFF, 0xFF],
+        encoded: "J%XZ5",
+    },
+    Vector {
+        name: "ascii",
+        bytes: b"hi",
+        encoded: "LZD",
+    },
+    Vector {
+        name: "ascii sentence",
+        bytes: b"Hello, World!",
+        encoded: "9P9$EE$UE7C4IWESEEX0",
+    },
+];
+
+/// Optimal bit-length vectors.
+pub const OPTIMAL: &[BitsVector] = &[
+    BitsVector {
+        name: "1 bit",
+        bits: 1,
+        bytes: &[0x01],
+        encoded: "1",
+    },
+    BitsVector {
+        name: "8-bit max",
+        bits: 8,
+        bytes: &[0xFF],
+        encoded: "5Z",
+    },
+    BitsVector {
+        name: "12-bit max",
+        bits: 12,
+        bytes: &[0xFF, 0x0F],
+        encoded: "253",
+    },
+    BitsVector {
+        name: "64-bit max",
+        bits: 64,
+        bytes: &[0xFF; 8],
+        encoded: "FIB9NX29*7WF",
+    },
+    BitsVector {
+        name: "103-bit zero",
+        bits: 103,
+        bytes: &[0x00; 13],
+        encoded: "0000000000000000000",
+    },
+    BitsVector {
+        name: "103-bit max",
+        bits: 103,
+        bytes: &[
+            0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x7F,
+        ],
+        encoded: "QN*9:63LHLRGI5HYAY7",
+    },
+    BitsVector {
+        name: "103-bit counting bytes",
+        bits: 103,
+        bytes: &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13],
+        encoded: "2V1Z7Z8$8A$:-HYL:H%",
+    },
+    BitsVector {
+        name: "128-bit max",
+        bits: 128,
+        bytes: &[0xFF; 16],
+        encoded: "5HR9W%YM3OKTNBNTFA3ZK+HZ",
+    },
+    BitsVector {
+        name: "256-bit max",
+        bits: 256,
+        bytes: &[0xFF; 32],
+        encoded: "T7AR$:N299R-%AVW4R3:TWZ-.OM757UJN+D9B//LH*XL1LJ",
+    },
+];
+
+/// Inputs that byte-pair decoding rejects.
+pub const ERRORS: &[ErrorVector] = &[
+    ErrorVector {
+        name: "lower case",
+        encoded: "lzd",
+        error: Base44Error::InvalidChar,
+    },
+    ErrorVector {
+        name: "space",
+        encoded: "LZ D",
+        error: Base44Error::InvalidChar,
+    },
+    ErrorVector {
+        name: "single character",
+        encoded: "0",
+        error: Base44Error::Dangling,
+    },
+    ErrorVector {
+        name: "group above 0xFFFF",
+        encoded: "ZZZ",
+        error: Base44Error::Overflow,
+    },
+    ErrorVector {
+        name: "tail above 0xFF",
+        encoded: "000ZZ",
+        error: Base44Error::Overflow,
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode, decode_bits, encode, encode_bits};
+
+    #[test]
+    fn codec_matches_vectors() {
+        for v in BYTE_PAIR {
+            assert_eq!(encode(v.bytes), v.encoded, "{}", v.name);
+            assert_eq!(decode(v.encoded).unwrap(), v.bytes, "{}", v.name);
+        }
+        for v in OPTIMAL {
+            assert_eq!(encode_bits(v.bits, v.bytes), v.encoded, "{}", v.name);
+            assert_eq!(
+                decode_bits(v.bits, v.encoded).unwrap(),
+                v.bytes,
+                "{}",
+                v.name
+            );
+        }
+        for v in ERRORS {
+            assert_eq!(decode(v.encoded), Err(v.error), "{}", v.name);
+        }
+    }
+}
@@ -0,0 +1,116 @@
+//! Encoding text rather than bytes.
+
+use core::fmt;
+use std::string::FromUtf8Error;
+
+use crate::{Base44Error, decode, encode};
+
+/// Why [`decode_to_string`] failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeToStringError {
+    /// The input is not valid Base44.
+    Decode(Base44Error),
+    /// The decoded bytes are not UTF-8; the error still holds them.
+    Utf8(FromUtf8Error),
+}
+
+impl fmt::Display for DecodeToStringError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeToStringError::Decode(err) => err.fmt(f),
+            DecodeToStringError::Utf8(err) => write!(f, "decoded payload is not UTF-8: {err}"),
+        }
+    }
+}
+
+impl core::error::Error for DecodeToStringError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            DecodeToStringError::Decode(err) => Some(err),
+            DecodeToStringError::Utf8(err) => Some(err),
+        }
+    }
+}
+
+impl From<Base44Error> for DecodeToStringError {
+    fn from(err: Base44Error) -> Self {
+        DecodeToStringError::Decode(err)
+    }
+}
+
+impl From<FromUtf8Error> for DecodeToStringError {
+    fn from(err: FromUtf8Error) -> Self {
+        DecodeToStringError::Utf8(err)
+    }
+}
+
+/// Byte-pair encode the UTF-8 bytes of `text`.
+///
+/// ```
+/// use qr_base44::{decode_to_string, encode_str};
+///
+/// let s = encode_str("hi");
+/// assert_eq!(s, "LZD");
+/// assert_eq!(decode_to_string(&s).unwrap(), "hi");
+/// ```
+pub fn encode_str(text: &str) -> String {
+    encode(text.as_bytes())
+}
+
+/// Decode byte-pair text whose payload is UTF-8, e.g. the output of [`encode_str`].
+pub fn decode_to_string(s: &str) -> Result<String, DecodeToStringError> {
+    Ok(String::from_utf8(decode(s)?)?)
+}
+
+/// Decode byte-pair text for display, replacing invalid UTF-8 in the payload
+/// with U+FFFD.
+///
+/// The flag is `true` if any replacement was made, i.e. the payload was not
+/// (entirely) text. Base44 errors are still reported.
+///
+/// ```
+/// use qr_base44::{decode_to_string_lossy, encode};
+///
+/// assert_eq!(decode_to_string_lossy("LZD").unwrap(), ("hi".to_string(), false));
+/// let (text, replaced) = decode_to_string_lossy(&encode(b"hi\xFF")).unwrap();
+/// assert_eq!(text, "hi\u{FFFD}");
+/// assert!(replaced);
+/// ```
+pub fn decode_to_string_lossy(s: &str) -> Result<(String, bool), Base44Error> {
+    Ok(match String::from_utf8(decode(s)?) {
+        Ok(text) => (text, false),
+        Err(err) => (String::from_utf8_lossy(err.as_bytes()).into_owned(), true),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_roundtrip_and_both_error_kinds() {
+        for text in ["", "a", "hi", "Grüße, 世界 🎉"] {
+            assert_eq!(decode_to_string(&encode_str(text)).unwrap(), text);
+        }
+        assert_eq!(
+            decode_to_string("L"),
+            Err(DecodeToStringError::Decode(Base44Error::Dangling))
+        );
+        match decode_to_string(&encode(&[0xC3, 0x28])) {
+            Err(DecodeToStringError::Utf8(err)) => assert_eq!(err.into_bytes(), [0xC3, 0x28]),
+            other => panic!("expected a UTF-8 error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn lossy_decode_flags_replacements() {
+        let text = "Grüße";
+        assert_eq!(
+            decode_to_string_lossy(&encode_str(text)).unwrap(),
+            (text.to_string(), false)
+        );
+        let (lossy, replaced) = decode_to_string_lossy(&encode(&text.as_bytes()[..3])).unwrap();
+        assert_eq!((lossy.as_str(), replaced), ("Gr\u{FFFD}", true));
+        assert_eq!(decode_to_string_lossy("ZZZ"), Err(Base44Error::Overflow));
+    }
+}
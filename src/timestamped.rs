@@ -0,0 +1,117 @@
+//! Short session codes with an embedded millisecond timestamp.
+//!
+//! A timestamped code is [`TIMESTAMP_LEN`] characters of Unix time in milliseconds,
+//! written most-significant digit first with [`SORTABLE_ALPHABET`], followed by
+//! random characters. Codes therefore sort by creation time, and the creation time
+//! can be read back with [`extract_timestamp`] (e.g. to expire old QR sessions)
+//! without any server-side lookup.
+
+#[cfg(feature = "rand")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{Base44Error, SORTABLE_ALPHABET};
+
+/// Characters used by the timestamp prefix; 44^8 ms reaches past the year 2400.
+pub const TIMESTAMP_LEN: usize = 8;
+
+fn sortable_digit(ch: u8) -> Option<u64> {
+    SORTABLE_ALPHABET
+        .iter()
+        .position(|&c| c == ch)
+        .map(|p| p as u64)
+}
+
+/// Generate a `len`-character code: timestamp prefix plus random suffix.
+///
+/// # Panics
+///
+/// Panics if `len < TIMESTAMP_LEN`.
+///
+/// ```
+/// use qr_base44::{extract_timestamp, generate_timestamped};
+///
+/// let code = generate_timestamped(16); // 8 timestamp + 8 random chars (~43 bits)
+/// assert_eq!(code.len(), 16);
+/// let ms = extract_timestamp(&code).unwrap();
+/// assert!(ms > 1_600_000_000_000);
+/// ```
+#[cfg(feature = "rand")]
+pub fn generate_timestamped(len: usize) -> String {
+    assert!(
+        len >= TIMESTAMP_LEN,
+        "length must be at least {TIMESTAMP_LEN}"
+    );
+    let ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock before 1970")
+        .as_millis() as u64;
+    let mut code = encode_timestamp(ms);
+    code.push_str(&crate::generate_token(len - TIMESTAMP_LEN));
+    code
+}
+
+/// Encode `ms` as the fixed-width timestamp prefix.
+#[cfg(any(feature = "rand", test))]
+fn encode_timestamp(mut ms: u64) -> String {
+    let mut digits = [0u8; TIMESTAMP_LEN];
+    for d in digits.iter_mut().rev() {
+        *d = SORTABLE_ALPHABET[(ms % 44) as usize];
+        ms /= 44;
+    }
+    digits.iter().map(|&b| b as char).collect()
+}
+
+/// Read the creation time (Unix milliseconds) from a timestamped code.
+pub fn extract_timestamp(code: &str) -> Result<u64, Base44Error> {
+    let prefix = code
+        .as_bytes()
+        .get(..TIMESTAMP_LEN)
+        .ok_or(Base44Error::InvalidLength)?;
+    prefix.iter().try_fold(0u64, |acc, &ch| {
+        Ok(acc * 44 + sortable_digit(ch).ok_or(Base44Error::InvalidChar)?)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamp_prefix_roundtrips_and_sorts() {
+        let times = [
+            0u64,
+            1,
+            1_700_000_000_000,
+            1_700_000_000_001,
+            44u64.pow(8) - 1,
+        ];
+        let codes: Vec<String> = times.iter().map(|&t| encode_timestamp(t)).collect();
+        assert!(codes.windows(2).all(|w| w[0] < w[1]));
+        for (&t, code) in times.iter().zip(&codes) {
+            assert_eq!(code.len(), TIMESTAMP_LEN);
+            assert_eq!(extract_timestamp(&format!("{code}XYZ")).unwrap(), t);
+        }
+        assert!(matches!(
+            extract_timestamp("ABC"),
+            Err(Base44Error::InvalidLength)
+        ));
+        assert!(matches!(
+            extract_timestamp("abcdefgh"),
+            Err(Base44Error::InvalidChar)
+        ));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn generated_codes_embed_now() {
+        let before = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        let code = generate_timestamped(12);
+        assert_eq!(code.len(), 12);
+        let ms = extract_timestamp(&code).unwrap();
+        assert!(ms >= before && ms - before < 5_000);
+        assert_eq!(generate_timestamped(TIMESTAMP_LEN).len(), TIMESTAMP_LEN);
+    }
+}
@@ -0,0 +1,61 @@
+//! Random tokens (enabled with the `rand` feature).
+//!
+//! Randomness comes from `rand::rng()`, a CSPRNG seeded by the operating system.
+
+use rand::Rng;
+
+use crate::{BASE44_ALPHABET, encode};
+
+/// Generate a token of `n_chars` characters drawn uniformly from the alphabet.
+///
+/// Characters are sampled with `random_range`, so there is no modulo bias. The
+/// token carries `n_chars * log2(44) ≈ 5.46 * n_chars` bits of entropy.
+///
+/// ```
+/// let token = qr_base44::generate_token(22); // ~120 bits
+/// assert_eq!(token.len(), 22);
+/// ```
+pub fn generate_token(n_chars: usize) -> String {
+    let mut rng = rand::rng();
+    (0..n_chars)
+        .map(|_| BASE44_ALPHABET[rng.random_range(0..BASE44_ALPHABET.len())] as char)
+        .collect()
+}
+
+/// Generate `n_bytes` random bytes and return them with their [`encode`]d form.
+///
+/// Use this when the raw secret must be stored (e.g. hashed server-side) and the
+/// encoding is what gets printed into the QR code.
+///
+/// ```
+/// let (bytes, encoded) = qr_base44::generate_token_bytes(16);
+/// assert_eq!(qr_base44::decode(&encoded).unwrap(), bytes);
+/// ```
+pub fn generate_token_bytes(n_bytes: usize) -> (Vec<u8>, String) {
+    let mut bytes = vec![0u8; n_bytes];
+    rand::rng().fill(&mut bytes[..]);
+    let encoded = encode(&bytes);
+    (bytes, encoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_use_the_whole_alphabet() {
+        let token = generate_token(4400);
+        assert_eq!(token.len(), 4400);
+        for &ch in BASE44_ALPHABET {
+            assert!(token.contains(ch as char), "{} never sampled", ch as char);
+        }
+        assert_eq!(generate_token(0), "");
+    }
+
+    #[test]
+    fn token_bytes_match_encoding() {
+        let (bytes, encoded) = generate_token_bytes(31);
+        assert_eq!(bytes.len(), 31);
+        assert_eq!(crate::decode(&encoded).unwrap(), bytes);
+    }
+}
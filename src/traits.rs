@@ -0,0 +1,71 @@
+//! Conversion traits: [`ToBase44`] and [`FromBase44`].
+
+use crate::{Base44Error, Base44String, decode, encode};
+
+/// Types that can be byte-pair encoded.
+///
+/// Implemented for everything that is `AsRef<[u8]>` (slices, arrays, `Vec<u8>`, ...).
+///
+/// ```
+/// use qr_base44::prelude::*;
+///
+/// assert_eq!(b"hi".to_base44(), "LZD");
+/// assert_eq!(vec![0xFFu8].to_base44_string().as_str(), "Z5");
+/// ```
+pub trait ToBase44 {
+    /// Encode with [`encode`](crate::encode).
+    fn to_base44(&self) -> String;
+
+    /// Encode into a [`Base44String`].
+    fn to_base44_string(&self) -> Base44String {
+        Base44String::new(self.to_base44()).expect("encoder output is valid base44")
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> ToBase44 for T {
+    fn to_base44(&self) -> String {
+        encode(self.as_ref())
+    }
+}
+
+/// Types that can be decoded from byte-pair Base44 text.
+///
+/// ```
+/// use qr_base44::prelude::*;
+///
+/// let bytes = Vec::<u8>::from_base44("LZD").unwrap();
+/// assert_eq!(bytes, b"hi");
+/// ```
+pub trait FromBase44: Sized {
+    /// Decode `s` with [`decode`](crate::decode).
+    fn from_base44(s: &str) -> Result<Self, Base44Error>;
+}
+
+impl FromBase44 for Vec<u8> {
+    fn from_base44(s: &str) -> Result<Self, Base44Error> {
+        decode(s)
+    }
+}
+
+impl FromBase44 for Base44String {
+    fn from_base44(s: &str) -> Result<Self, Base44Error> {
+        Base44String::new(s)
+    }
+}
+
+/// Fixed-size arrays fail with [`Base44Error::InvalidLength`] unless the text
+/// decodes to exactly `N` bytes.
+///
+/// ```
+/// use qr_base44::{Base44Error, FromBase44};
+///
+/// assert_eq!(<[u8; 2]>::from_base44("LZD"), Ok(*b"hi"));
+/// assert_eq!(<[u8; 3]>::from_base44("LZD"), Err(Base44Error::InvalidLength));
+/// ```
+impl<const N: usize> FromBase44 for [u8; N] {
+    fn from_base44(s: &str) -> Result<Self, Base44Error> {
+        decode(s)?
+            .try_into()
+            .map_err(|_| Base44Error::InvalidLength)
+    }
+}
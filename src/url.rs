@@ -0,0 +1,201 @@
+//! Base44 text inside URLs.
+//!
+//! Four alphabet characters (`$`, `+`, `/`, `:`) are reserved in URLs, and
+//! browsers and form encoders may percent-escape them (or `%` itself) on the way
+//! through. [`encode_url_component`] escapes them up front, with the same
+//! escapes `encodeURIComponent` uses, and [`decode_url_component`] undoes them
+//! before decoding.
+
+use core::fmt;
+use std::borrow::Cow;
+
+use crate::{Base44Error, alphabet, decode, encode};
+
+/// Why [`decode_url_component`] failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum UrlDecodeError {
+    /// The escape at this byte offset decodes to a byte outside the alphabet,
+    /// so the component was not Base44 text that a browser escaped.
+    EscapedOutsideAlphabet {
+        /// Byte offset of the `%`.
+        index: usize,
+        /// The decoded byte.
+        byte: u8,
+    },
+    /// The unescaped text is not valid Base44.
+    Decode(Base44Error),
+}
+
+impl fmt::Display for UrlDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UrlDecodeError::EscapedOutsideAlphabet { index, byte } => write!(
+                f,
+                "percent-escape at byte {index} decodes to 0x{byte:02x}, outside the Base44 alphabet"
+            ),
+            UrlDecodeError::Decode(err) => err.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for UrlDecodeError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            UrlDecodeError::Decode(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<Base44Error> for UrlDecodeError {
+    fn from(err: Base44Error) -> Self {
+        UrlDecodeError::Decode(err)
+    }
+}
+
+fn hex_val(b: u8) -> Option<u8> {
+    (b as char).to_digit(16).map(|d| d as u8)
+}
+
+/// Byte-pair encode `input`, percent-escaping `$`, `%`, `+`, `/` and `:`.
+///
+/// These are exactly the alphabet characters `encodeURIComponent` escapes, so
+/// the output is unchanged by it, and it contains nothing a URL parser or an
+/// `application/x-www-form-urlencoded` decoder rewrites (a literal `+` would
+/// become a space there).
+///
+/// ```
+/// use qr_base44::{decode_url_component, encode_url_component};
+///
+/// let s = encode_url_component(&[0xFF, 0xFF]);
+/// assert_eq!(s, "J%25X");
+/// assert_eq!(decode_url_component(&s).unwrap(), [0xFF, 0xFF]);
+/// ```
+pub fn encode_url_component(input: &[u8]) -> String {
+    let plain = encode(input);
+    let mut out = String::with_capacity(plain.len());
+    for ch in plain.chars() {
+        match ch {
+            '$' => out.push_str("%24"),
+            '%' => out.push_str("%25"),
+            '+' => out.push_str("%2B"),
+            '/' => out.push_str("%2F"),
+            ':' => out.push_str("%3A"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// Percent-decode `s`, then decode it as byte-pair Base44.
+///
+/// Escapes are case-insensitive (`%2b` and `%2B` both give `+`). As in the
+/// WHATWG URL standard, a `%` not followed by two hex digits is kept as is, so
+/// most unescaped text decodes as with [`decode`]; a raw `%` that happens to be
+/// followed by two hex digits is read as an escape, which is why
+/// [`encode_url_component`] always escapes it.
+///
+/// ```
+/// use qr_base44::{decode_url_component, encode};
+///
+/// assert_eq!(encode(&[0xFF, 0xFF]), "J%X");
+/// assert_eq!(decode_url_component("J%25X").unwrap(), [0xFF, 0xFF]);
+/// assert_eq!(decode_url_component("J%X").unwrap(), [0xFF, 0xFF]);
+/// assert!(decode_url_component("LZD%20").is_err()); // a space was never Base44
+/// ```
+pub fn decode_url_component(s: &str) -> Result<Vec<u8>, UrlDecodeError> {
+    Ok(decode(&unescape(s)?)?)
+}
+
+/// Undo the percent-escapes of [`decode_url_component`] without decoding, for
+/// callers that parse the text themselves. Borrows `s` when it has no `%`.
+pub(crate) fn unescape(s: &str) -> Result<Cow<'_, str>, UrlDecodeError> {
+    if !s.contains('%') {
+        return Ok(Cow::Borrowed(s));
+    }
+    let bytes = s.as_bytes();
+    let mut text = String::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'%' {
+            text.push(bytes[i] as char);
+            i += 1;
+            continue;
+        }
+        let Some(byte) = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| Some(hex_val(hex[0])? << 4 | hex_val(hex[1])?))
+        else {
+            text.push('%');
+            i += 1;
+            continue;
+        };
+        if !alphabet::contains(byte as char) {
+            return Err(UrlDecodeError::EscapedOutsideAlphabet { index: i, byte });
+        }
+        text.push(byte as char);
+        i += 3;
+    }
+    Ok(Cow::Owned(text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encode;
+
+    #[test]
+    fn browser_escaped_components() {
+        let data: Vec<u8> = (0..=255).collect();
+        let plain = encode(&data);
+        let escaped = plain
+            .replace('%', "%25")
+            .replace('$', "%24")
+            .replace('+', "%2B")
+            .replace('/', "%2f")
+            .replace(':', "%3A");
+        assert_ne!(escaped, plain);
+        assert_eq!(decode_url_component(&escaped).unwrap(), data);
+        assert_eq!(decode_url_component("%4C%5A%44").unwrap(), b"hi");
+    }
+
+    #[test]
+    fn encoded_components_are_url_inert() {
+        let data: Vec<u8> = (0..=255).rev().collect();
+        for len in 0..64 {
+            let s = encode_url_component(&data[..len * 4]);
+            // Only what encodeURIComponent leaves alone, plus its own escapes.
+            let unescaped = s.replace("%24", "").replace("%25", "").replace("%2B", "");
+            let unescaped = unescaped.replace("%2F", "").replace("%3A", "");
+            assert!(
+                unescaped
+                    .bytes()
+                    .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || b"*-.".contains(&b)),
+                "{s}"
+            );
+            assert_eq!(decode_url_component(&s).unwrap(), &data[..len * 4]);
+        }
+    }
+
+    #[test]
+    fn rejects_bad_escapes() {
+        // Not an escape: passed through to the decoder.
+        assert_eq!(decode_url_component("J%X"), Ok(vec![0xFF, 0xFF]));
+        assert_eq!(
+            decode_url_component("LZD%"),
+            Err(UrlDecodeError::Decode(Base44Error::Dangling))
+        );
+        assert_eq!(
+            decode_url_component("LZD%2c"),
+            Err(UrlDecodeError::EscapedOutsideAlphabet {
+                index: 3,
+                byte: b','
+            })
+        );
+        assert_eq!(
+            decode_url_component("lzd"),
+            Err(UrlDecodeError::Decode(Base44Error::InvalidChar))
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! Format v2: byte-pair groups plus an explicit tail-length marker.
+//!
+//! v1 ([`encode`](crate::encode)) ends odd-length input with a 2-character
+//! group, which is told apart from a truncated 3-character group only by its
+//! value range. v2 always writes whole 3-character groups (an odd final byte is
+//! padded with a zero byte) and appends one character giving how many bytes the
+//! last group carries: `0` (empty input), `1` or `2`. Encoded length is always
+//! `3k + 1`, so a dropped or extra character is reported as
+//! [`Base44Error::InvalidLength`] instead of surfacing as a different payload.
+//!
+//! v1 stays the default; inside [`encode_auto`](crate::encode_auto)'s envelope,
+//! header version 1 marks a v2 body (see [`Envelope::encode_v2`](crate::Envelope::encode_v2)).
+
+use crate::{Base44Error, b44_val, decode_bytes, encode, encoded_len};
+
+/// Encode `input` in the v2 framing.
+///
+/// ```
+/// use qr_base44::{decode_v2, encode_v2};
+///
+/// assert_eq!(encode_v2(b"hi"), "LZD2");
+/// assert_eq!(encode_v2(b"h"), "4XD1");
+/// assert_eq!(decode_v2("4XD1").unwrap(), b"h");
+/// assert!(decode_v2("4XD").is_err()); // truncated
+/// ```
+pub fn encode_v2(input: &[u8]) -> String {
+    let odd = input.len() % 2;
+    let mut out = String::with_capacity(encoded_len(input.len() + odd) + 1);
+    let (pairs, tail) = input.split_at(input.len() - odd);
+    out.push_str(&encode(pairs));
+    if let [byte] = tail {
+        out.push_str(&encode(&[*byte, 0]));
+    }
+    out.push(match (input.is_empty(), odd) {
+        (true, _) => '0',
+        (false, 1) => '1',
+        (false, _) => '2',
+    });
+    out
+}
+
+/// Decode the output of [`encode_v2`].
+///
+/// A length other than `3k + 1`, or a marker that does not fit the groups
+/// before it, is [`Base44Error::InvalidLength`]; a non-zero padding byte under
+/// marker `1` is [`Base44Error::NonZeroPadding`].
+pub fn decode_v2(s: &str) -> Result<Vec<u8>, Base44Error> {
+    // Split on bytes: a non-ASCII last character is not a char boundary away.
+    let (&marker, body) = s
+        .as_bytes()
+        .split_last()
+        .ok_or(Base44Error::InvalidLength)?;
+    if s.len() % 3 != 1 {
+        return Err(Base44Error::InvalidLength);
+    }
+    let mut bytes = decode_bytes(body)?;
+    match (
+        b44_val(marker).ok_or(Base44Error::InvalidChar)?,
+        bytes.len(),
+    ) {
+        (0, 0) => {}
+        (1, 1..) => {
+            if bytes.pop() != Some(0) {
+                return Err(Base44Error::NonZeroPadding);
+            }
+        }
+        (2, 1..) => {}
+        _ => return Err(Base44Error::InvalidLength),
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip_and_shape() {
+        let data: Vec<u8> = (0..=255).rev().collect();
+        for len in 0..40 {
+            let s = encode_v2(&data[..len]);
+            assert_eq!(s.len() % 3, 1);
+            assert_eq!(decode_v2(&s).unwrap(), &data[..len]);
+            // Losing one or two characters always breaks the 3k + 1 shape.
+            for cut in s.len().saturating_sub(2)..s.len() {
+                assert_eq!(decode_v2(&s[..cut]), Err(Base44Error::InvalidLength));
+            }
+        }
+    }
+
+    #[test]
+    fn inconsistent_markers() {
+        assert_eq!(decode_v2(""), Err(Base44Error::InvalidLength));
+        assert_eq!(decode_v2("1"), Err(Base44Error::InvalidLength));
+        assert_eq!(decode_v2("LZD0"), Err(Base44Error::InvalidLength));
+        assert_eq!(decode_v2("LZD3"), Err(Base44Error::InvalidLength));
+        assert_eq!(decode_v2("LZD1"), Err(Base44Error::NonZeroPadding));
+        assert_eq!(decode_v2("LZD "), Err(Base44Error::InvalidChar));
+        // Multi-byte characters anywhere, including the marker, are rejected
+        // rather than sliced through.
+        assert_eq!(decode_v2("LZé"), Err(Base44Error::InvalidChar));
+        assert_eq!(decode_v2("LZDé00"), Err(Base44Error::InvalidChar));
+        assert_eq!(decode_v2("0").unwrap(), b"");
+    }
+}
@@ -0,0 +1,95 @@
+//! [`Validator`]: incremental validity checking for byte-pair input.
+
+use crate::{Base44Error, StreamDecoder};
+
+/// Checks byte-pair Base44 text chunk by chunk without keeping the decoded bytes.
+///
+/// Accepts exactly the strings [`decode`](crate::decode) accepts and reports the
+/// same error, as soon as the offending character or group has arrived. Memory use
+/// is constant, so a server can reject a bad upload while the body is still
+/// streaming in.
+///
+/// ```
+/// use qr_base44::{Base44Error, Validator};
+///
+/// let mut v = Validator::new();
+/// v.feed("J%").unwrap();
+/// v.feed("XL1").unwrap();
+/// assert_eq!(v.finish(), Ok(3)); // decodes to 3 bytes
+///
+/// let mut v = Validator::new();
+/// assert_eq!(v.feed("ZZZ"), Err(Base44Error::Overflow)); // rejected before the end
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Validator {
+    /// The streaming decoder, run with a sink that only counts.
+    decoder: StreamDecoder,
+    /// Bytes decoded so far.
+    decoded: usize,
+}
+
+impl Validator {
+    /// A validator that has seen no input.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Validate the next chunk of input.
+    ///
+    /// After an error, every further call returns the same error.
+    pub fn feed(&mut self, chunk: &str) -> Result<(), Base44Error> {
+        let decoded = &mut self.decoded;
+        self.decoder.feed(chunk.as_bytes(), |bytes: &[u8]| {
+            *decoded += bytes.len();
+            Ok(())
+        })
+    }
+
+    /// Check the final group and return the decoded length in bytes.
+    pub fn finish(self) -> Result<usize, Base44Error> {
+        let mut decoded = self.decoded;
+        self.decoder.finish(|bytes: &[u8]| {
+            decoded += bytes.len();
+            Ok::<_, Base44Error>(())
+        })?;
+        Ok(decoded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode, encode};
+
+    #[test]
+    fn agrees_with_decode_for_every_split() {
+        let inputs = [
+            encode(b""),
+            encode(b"\xFF"),
+            encode(b"hello world"),
+            "ZZZ000".into(),
+            "000ZZ".into(),
+            "000Z".into(),
+            "00a0".into(),
+            "LZ\u{1F600}".into(),
+            "0".into(),
+        ];
+        for s in &inputs {
+            let expected = decode(s).map(|b| b.len());
+            for split in (0..=s.len()).filter(|&i| s.is_char_boundary(i)) {
+                let mut v = Validator::new();
+                let fed = v.feed(&s[..split]).and_then(|()| v.feed(&s[split..]));
+                let result = fed.and_then(|()| v.finish());
+                assert_eq!(result, expected, "{s:?} split at {split}");
+            }
+        }
+    }
+
+    #[test]
+    fn errors_are_sticky() {
+        let mut v = Validator::new();
+        assert_eq!(v.feed("a"), Err(Base44Error::InvalidChar));
+        assert_eq!(v.feed("000"), Err(Base44Error::InvalidChar));
+        assert_eq!(v.finish(), Err(Base44Error::InvalidChar));
+    }
+}
@@ -0,0 +1,97 @@
+//! Variable-length integers written directly as Base44 digits.
+//!
+//! Each character carries a base-22 digit, least significant first: values
+//! `0..22` end the number, `22..44` carry `value - 22` and continue. Lengths
+//! below 22 take one character, below 484 two. This is the length prefix used by
+//! [`seal_crc32`](crate::seal_crc32); use it for custom containers so they parse
+//! consistently.
+//!
+//! ```
+//! use qr_base44::varint;
+//!
+//! let mut s = varint::encode(300);
+//! assert_eq!(s.len(), varint::encoded_len(300));
+//! s.push_str("LZD"); // payload follows the prefix
+//! let (len, used) = varint::decode(&s).unwrap();
+//! assert_eq!(len, 300);
+//! assert_eq!(qr_base44::decode(&s[used..]).unwrap(), b"hi");
+//! ```
+
+use crate::{BASE44_ALPHABET, Base44Error, b44_val};
+
+const BASE: u64 = 22;
+
+/// Number of characters [`encode`] produces for `n`.
+pub fn encoded_len(mut n: u64) -> usize {
+    let mut len = 1;
+    while n >= BASE {
+        n /= BASE;
+        len += 1;
+    }
+    len
+}
+
+/// Encode `n` as a standalone string.
+pub fn encode(n: u64) -> String {
+    let mut out = String::with_capacity(encoded_len(n));
+    encode_into(n, &mut out);
+    out
+}
+
+/// Append the digits of `n` to `out`.
+pub fn encode_into(mut n: u64, out: &mut String) {
+    while n >= BASE {
+        out.push(BASE44_ALPHABET[(BASE + n % BASE) as usize] as char);
+        n /= BASE;
+    }
+    out.push(BASE44_ALPHABET[n as usize] as char);
+}
+
+/// Parse a number from the start of `s`, returning it and the characters consumed.
+///
+/// Returns [`Base44Error::Dangling`] if `s` ends before the final digit and
+/// [`Base44Error::Overflow`] if the value does not fit in a `u64`.
+pub fn decode(s: &str) -> Result<(u64, usize), Base44Error> {
+    let mut n: u64 = 0;
+    let mut scale: u64 = 1;
+    for (i, &ch) in s.as_bytes().iter().enumerate() {
+        let digit = b44_val(ch).ok_or(Base44Error::InvalidChar)? as u64;
+        let value = digit % BASE;
+        n = value
+            .checked_mul(scale)
+            .and_then(|v| n.checked_add(v))
+            .ok_or(Base44Error::Overflow)?;
+        if digit < BASE {
+            return Ok((n, i + 1));
+        }
+        scale = match scale.checked_mul(BASE) {
+            Some(scale) => scale,
+            None => return Err(Base44Error::Overflow),
+        };
+    }
+    Err(Base44Error::Dangling)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip_and_lengths() {
+        for (n, len) in [(0, 1), (21, 1), (22, 2), (483, 2), (484, 3), (u64::MAX, 15)] {
+            let mut s = encode(n);
+            assert_eq!(s.len(), len, "{n}");
+            assert_eq!(encoded_len(n), len);
+            s.push_str("REST");
+            assert_eq!(decode(&s), Ok((n, len)));
+        }
+    }
+
+    #[test]
+    fn malformed_varints() {
+        assert_eq!(decode(""), Err(Base44Error::Dangling));
+        assert_eq!(decode("ZZ"), Err(Base44Error::Dangling));
+        assert_eq!(decode("a"), Err(Base44Error::InvalidChar));
+        assert_eq!(decode(&"Z".repeat(20)), Err(Base44Error::Overflow));
+    }
+}
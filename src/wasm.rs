@@ -0,0 +1,46 @@
+//! WebAssembly bindings (enabled with the `wasm` feature).
+//!
+//! Build with `wasm-pack build bindings/wasm`; wasm-bindgen generates the
+//! JavaScript glue and a `.d.ts` file from the signatures below:
+//!
+//! ```ts
+//! export function encode(input: Uint8Array): string;
+//! export function decode(s: string): Uint8Array;
+//! export function encodeBits(bits: number, input: Uint8Array): string;
+//! export function decodeBits(bits: number, s: string): Uint8Array;
+//! ```
+//!
+//! Errors are thrown as JavaScript `Error` objects carrying the
+//! [`Base44Error`](crate::Base44Error) message.
+
+use wasm_bindgen::prelude::*;
+
+/// Encode bytes with the byte-pair scheme (see [`crate::encode`]).
+#[wasm_bindgen]
+pub fn encode(input: &[u8]) -> String {
+    crate::encode(input)
+}
+
+/// Decode a byte-pair encoded string (see [`crate::decode`]).
+#[wasm_bindgen]
+pub fn decode(s: &str) -> Result<Vec<u8>, JsError> {
+    crate::decode(s).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Encode exactly `bits` bits with optimal length (see [`crate::encode_bits`]).
+///
+/// Unlike the Rust function this throws instead of panicking when `bits` is zero,
+/// `input` is too short, or unused padding bits are set.
+#[wasm_bindgen(js_name = encodeBits)]
+pub fn encode_bits(bits: usize, input: &[u8]) -> Result<String, JsError> {
+    crate::try_encode_bits(bits, input).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Decode an optimal-length string holding `bits` bits (see [`crate::decode_bits`]).
+///
+/// Throws instead of panicking when `bits` is zero, and rejects strings longer
+/// than [`chars_needed`](crate::chars_needed)`(bits)` before decoding them.
+#[wasm_bindgen(js_name = decodeBits)]
+pub fn decode_bits(bits: usize, s: &str) -> Result<Vec<u8>, JsError> {
+    crate::try_decode_bits(bits, s).map_err(|e| JsError::new(&e.to_string()))
+}